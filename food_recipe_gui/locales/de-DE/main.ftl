@@ -0,0 +1,436 @@
+## Allgemein
+
+app-heading = Rezeptfinder (GUI)
+add = Hinzufügen
+cancel = Abbrechen
+clear = Leeren
+delete = Löschen
+dismiss = Schließen
+edit = Bearbeiten
+save = Speichern
+reset-defaults = Auf Standard zurücksetzen
+saved-to = Gespeichert in { $path }
+saved-path = { $path } gespeichert
+save-failed = Speichern fehlgeschlagen: { $error }
+export-failed = Export fehlgeschlagen: { $error }
+filter-any = Alle
+field-title = Titel:
+field-category = Kategorie:
+field-area = Region:
+instructions-label = Zubereitung:
+ingredients-heading = Zutaten
+hint-ingredient = Zutat
+hint-measure = Menge
+
+## Suchformular
+
+source-label = Quelle:
+offline-mode = 📴 Offline-Modus
+offline-warning = Offline: Es werden nur zwischengespeicherte, favorisierte und eigene Rezepte durchsucht. Nur für Favoriten heruntergeladene Fotos werden angezeigt.
+quota-counter = 🌐 { $today } heute · { $session } in dieser Sitzung
+quota-limit-hint = An die Rezeptanbieter gesendete Anfragen, Wiederholungen eingeschlossen. Weiches Limit: { $limit } pro Tag.
+quota-no-limit-hint = An die Rezeptanbieter gesendete Anfragen, Wiederholungen eingeschlossen. Ein Tageslimit lässt sich in den Einstellungen festlegen.
+quota-over = Die { $limit } Anfragen für heute sind aufgebraucht. Suchen laufen weiter, aber ein API-Schlüssel mit Kontingent lehnt sie womöglich ab.
+name-label = Name:
+name-hint = z. B. Carbonara
+name-search = Nach Name suchen
+browse-letters = A–Z durchblättern:
+cook-from-pantry = Mit dem kochen, was ich habe (Vorrat)
+seasonal-boost = 🌱 Saisonales bevorzugen
+seasonal-boost-hint = Rezepte punkten für jede Zutat, die gerade Saison hat
+leftover-boost = ♻ Reste bevorzugen
+leftover-boost-hint = Rezepte punkten für jeden Rest, den sie aufbrauchen
+leftovers-use-up = ♻ Reste aufbrauchen
+leftovers-use-up-hint = Mit den Resten als Nebenzutaten suchen, die bald ablaufenden zuerst
+taste-label = Geschmack:
+main-label = Hauptzutaten:
+sub-label = Nebenzutaten:
+exclude-label = Ausschließen:
+hide-excluded = Rezepte mit ausgeschlossenen Zutaten ausblenden
+hide-excluded-hint = Aus: Sie bleiben in den Ergebnissen, werden aber markiert. Allergien gelten immer als ausgeschlossen.
+diet-label = Ernährung:
+diet-vegetarian = 🥕 Vegetarisch
+diet-vegan = 🌱 Vegan
+diet-hint = Anhand der Zutatennamen beurteilt, Ungewöhnliches also bitte selbst prüfen
+budget-label = Max. Kosten:
+budget-hint = Geschätzt aus der Preistabelle in den Einstellungen; Zutaten ohne Preis zählen als kostenlos
+calorie-budget-label = Max. kcal pro Portion:
+calorie-budget-hint = Aus der Nährwertschätzung; unbekannte Zutaten zählen nicht mit
+max-ingredients-label = Einfache Rezepte, höchstens so viele Zutaten:
+max-ingredients-hint = Lässt aufwendige Rezepte weg, für unter der Woche und einen kleinen Vorrat
+max-time-label = Zeit:
+max-time-any = Egal
+max-time-minutes = max. { $minutes } Min.
+max-time-hint = Geschätzt aus den Zeiten in der Anleitung plus ein paar Minuten pro Schritt; Rezepte ohne Anleitung bleiben drin
+time-estimate = ⏱ { $minutes } Min.
+time-estimate-hint = Geschätzt aus den Zeiten in der Anleitung plus ein paar Minuten pro Schritt
+max-difficulty-label = Schwierigkeit:
+max-difficulty-any = Egal
+max-difficulty-medium = Leicht oder mittel
+max-difficulty-hint = Eine grobe Einstufung nach Zahl der Zutaten und Schritte und den Techniken, die die Anleitung nennt
+difficulty-easy = Leicht
+difficulty-medium = Mittel
+difficulty-hard = Schwer
+difficulty-summary = { $ingredients } Zutaten, { $steps } Schritte, Techniken: { $techniques }
+fetch-recipes = Rezepte suchen
+random-recipe = Zufallsrezept
+latest-recipes = 🆕 Neueste
+latest-recipes-hint = Die zuletzt zu TheMealDB hinzugefügten Gerichte
+meal-of-the-day = Gericht des Tages
+meal-of-the-day-show = Rezept anzeigen
+seasonal-now = Gerade Saison:
+seasonal-add-hint = Zu den Nebenzutaten hinzufügen
+shopping-toggle = Einkaufsliste ({ $count })
+compare-toggle = ⚖ Vergleichen ({ $count }/{ $max })
+queue-toggle = 📋 Warteschlange ({ $count })
+queue-add = ➕ Einreihen
+queue-add-hint = Diese Suche später im Hintergrund ausführen, nach den bereits eingereihten
+my-recipes-toggle = 📖 Meine Rezepte
+settings-toggle = ⚙ Einstellungen
+history-menu = 🕘 Verlauf
+history-empty = Noch keine Suchen.
+history-entry = { $summary } ({ $count } Ergebnisse)
+history-load-failed = Verlauf konnte nicht geladen werden: { $error }
+presets-menu = 📌 Vorlagen
+presets-empty = Noch keine Vorlagen. Fülle das Suchformular aus, gib unten einen Namen ein und speichere.
+presets-delete = Diese Vorlage löschen
+presets-name-hint = Name der Vorlage
+presets-save = Aktuelle Suche speichern
+presets-save-hint = Speichert Zutaten, Geschmack, Filter und Sortierung; eine Vorlage mit gleichem Namen wird ersetzt
+
+## Offline-Suche
+
+offline-search-label = Offline-Suche:
+offline-search-hint = Wörter in Titel, Zutaten oder Zubereitung
+offline-search-button = Offline suchen
+offline-matches = { $count } Offline-Treffer
+offline-matches-among = { $count } Treffer unter { $total } Rezepten auf diesem Computer
+
+## Ergebnisse
+
+search-cancelled = Suche abgebrochen.
+progress-loaded = { $done }/{ $total } Gerichte geladen
+progress-candidates = Suche nach Kandidaten...
+no-results = Keine Rezepte gefunden.
+search-failed-see-below = Suche fehlgeschlagen, siehe Fehlerbericht unten.
+details-header = ⚠ Details: { $failed } fehlgeschlagen, { $slow } langsam
+diagnostics-attempt = Versuch
+diagnostics-result = Ergebnis
+diagnostics-time = Dauer
+diagnostics-url = Anfrage
+diagnostics-slow = langsam ({ $status })
+results-heading = Beste Rezepte:
+results-keyboard-hint = Tastatur: ↑/↓ wählt ein Rezept, F schaltet Favorit um, Esc schließt die Details
+sort-selected = Sortierung: { $order }
+sort-score = Punkte
+sort-title = Titel (A–Z)
+sort-category = Kategorie
+sort-area = Region
+sort-ingredient-count = Wenigste Zutaten
+sort-rating = Meine Bewertung
+view-list = ☰ Liste
+view-grid = ▦ Raster
+tag-filter-active = mit Schlagwort #{ $tag }
+tag-filter-clear = Schlagwortfilter entfernen
+tag-filter-hint = Nur Ergebnisse mit diesem Schlagwort zeigen
+instruction-filter-label = Anleitung erwähnt:
+instruction-filter-hint = z. B. Schongarer, Ofen
+page-prev = ◀ Zurück
+page-next = Weiter ▶
+page-status = Seite { $page } von { $pages } ({ $count } Rezepte)
+score = Punkte { $score }
+result-title = { $rank }: { $title }
+score-hint = Klicken, um zu sehen, woher die Punkte kommen
+window-score-breakdown = Punkte: { $title }
+breakdown-main = { $ingredient } (Hauptzutat „{ $wanted }“)
+breakdown-sub = { $ingredient } (Nebenzutat „{ $wanted }“)
+breakdown-unmatched = { $ingredient } (weder gesucht noch vorrätig)
+breakdown-taste-title = Geschmack im Titel
+breakdown-taste-tags = Geschmack in den Schlagwörtern
+breakdown-taste-category = Geschmack in der Kategorie
+breakdown-taste-area = Geschmack in der Region
+breakdown-taste-instructions = Geschmack in der Anleitung
+breakdown-taste-ingredients = Zutaten mit dem Geschmack
+breakdown-have = { $ingredient } (vorhanden)
+breakdown-to-buy = { $ingredient } (einzukaufen)
+breakdown-seasonal = { $count } Zutat(en) mit Saison
+breakdown-leftovers = { $count } Rest(e) verwertet
+breakdown-nutrition = Ernährungsziele
+breakdown-unexplained = Nicht erklärt (mit anderen Einstellungen bewertet)
+breakdown-total = Gesamt
+breakdown-empty = Diese Bewertungsstrategie schlüsselt ihre Punkte nicht auf.
+open-window-hint = In neuem Fenster öffnen
+your-rating = Deine Bewertung
+diff-summary = Seit der letzten Suche: { $new } neu, { $dropped } weggefallen, { $rescored } neu bewertet
+diff-dropped = Weggefallen ({ $count })
+diff-dropped-title = { $title } (Punkte { $score })
+diff-dropped-hint = In den Ergebnissen der letzten Suche, aber nicht in diesen
+diff-new = 🆕 Neu
+diff-badge-hint = Verglichen mit der Suche davor
+excluded-hint = Enthält ausgeschlossene Zutaten
+to-buy = { $count } zu kaufen
+compare-pick-hint = Zwei Rezepte zum Vergleichen auswählen
+shopping-include-hint = In die Einkaufsliste aufnehmen
+favorite-add = Zu Favoriten hinzufügen
+favorite-remove = Aus Favoriten entfernen
+
+## Rezeptdetails
+
+details-heading = Rezeptdetails:
+open-in-window = 🗗 In Fenster öffnen
+detail-edit = ✏ Bearbeiten
+detail-category = Kategorie: { $category }
+detail-area = Region: { $area }
+rating-label = Meine Bewertung:
+rating-star-hint = { $stars } von { $max }
+watch-video = ▶ Video ansehen
+open-source = 🔗 Quelle öffnen
+export-markdown = Als Markdown exportieren
+export-pdf = Als PDF exportieren
+export-html = Als HTML exportieren
+export-html-open = Im Browser öffnen
+export-tasks = Fehlendes zur Aufgabenliste
+export-tasks-hint = Fügt jede Zutat, die dein Vorrat nicht abdeckt, der Aufgabenliste des Task-Managers hinzu
+export-results = 📄 Ergebnisse exportieren
+export-results-hint = Speichert ID, Titel, Kategorie, Region, Punkte und Zutatenzahl aller Ergebnisse als CSV, für eine Tabellenkalkulation
+tasks-added = { $count } Aufgabe(n) zu { $path } hinzugefügt
+servings-before = Zutaten für
+servings-after = Portionen
+servings-reset = Zurück zur Portionenzahl des Rezepts
+ingredient-highlight-legend = Fett: passt zu deinen Zutaten · normal: vorrätig · grau: nicht vorhanden
+excluded-warning = ⚠ Enthält ausgeschlossene Zutaten: { $items }
+pantry-have = Aus deinem Vorrat: { $items }
+pantry-need = Noch zu kaufen: { $items }
+notes-label = Meine Notizen:
+notes-hint = z. B. nächstes Mal weniger Salz
+timer-start-hint = Timer für diesen Schritt starten
+timer-name = { $title }, Schritt { $step }
+cooking-start = 👩‍🍳 Kochen
+cooking-start-hint = Schritt für Schritt in großer Schrift, im Vollbild. ←/→ oder Leertaste zum Blättern, Esc zum Verlassen.
+cooking-progress = Schritt { $step } von { $count }
+cooking-back = ◀ Zurück
+cooking-next = Weiter ▶
+cooking-finish = Fertig ✔
+cooking-exit = ✖ Beenden
+cooking-reminders = Du brauchst:
+speech-start = 🔊 Vorlesen
+speech-start-hint = Liest die Zubereitung Schritt für Schritt vor
+speech-progress = Lese Schritt { $step } von { $count }
+speech-back = Vorheriger Schritt
+speech-pause = Pause
+speech-resume = Diesen Schritt von vorn lesen
+speech-skip = Nächster Schritt
+speech-stop = Vorlesen beenden
+speech-step = Schritt { $step }. { $text }
+speech-failed = Vorlesen nicht möglich: { $error }
+cached-today = heute zwischengespeichert
+cached-yesterday = gestern zwischengespeichert
+cached-days = vor { $days } Tagen zwischengespeichert
+cache-stale-hint = Älter als die Cache-Lebensdauer; das Rezept hat sich online vielleicht geändert
+
+nutrition-title = Nährwerte (geschätzt)
+similar-title = Ähnliche Rezepte
+similar-empty = Noch kein gespeichertes Rezept hat eine Zutat mit diesem gemeinsam.
+nutrition-total = Gesamt
+nutrition-per-serving = Pro Portion ({ $servings })
+nutrition-calories = Kalorien (kcal)
+nutrition-protein = Eiweiß (g)
+nutrition-fat = Fett (g)
+nutrition-carbs = Kohlenhydrate (g)
+nutrition-sugar = davon Zucker (g)
+nutrition-not-counted = Nicht berücksichtigt: { $items }
+cost-title = Geschätzte Kosten
+cost-estimate = { $total } insgesamt, { $per_serving } pro Portion ({ $servings } Portionen)
+cost-not-counted = Kein Preis für: { $items }
+
+## Seitenleiste
+
+profile-title = 👤 Profil: { $name }
+profile-keep-inputs = Aktuellen Geschmack und Ernährung übernehmen
+profile-keep-inputs-hint = Wird mit diesem Profil gespeichert und bei jeder Auswahl eingesetzt
+profile-new-hint = Name des neuen Profils
+profile-add = Hinzufügen
+profile-delete = { $name } löschen
+profile-nutrition-goals = Ernährungsziele:
+profile-nutrition-goals-hint = Gilt, wenn Ergebnisse nach passenden Zutaten und Ernährungszielen bewertet werden
+goal-high-protein = Eiweißreich
+goal-low-calorie = Kalorienarm
+goal-low-sugar = Zuckerarm
+goal-low-fat = Fettarm
+profile-allergens = Warnen bei:
+allergen-gluten = Gluten
+allergen-nuts = Nüsse
+allergen-peanuts = Erdnüsse
+allergen-shellfish = Schalentiere
+allergen-fish = Fisch
+allergen-dairy = Milchprodukte
+allergen-eggs = Eier
+allergen-soy = Soja
+allergen-sesame = Sesam
+allergen-hint = Durch: { $items }. Nach Zutatennamen beurteilt, also auch die Verpackung prüfen
+profile-picker-title = Wer kocht?
+favorites-title = Favoriten
+favorites-empty = Noch keine Favoriten.
+pantry-title = Vorrat
+leftovers-title = Reste
+leftover-ingredient-hint = Zutat
+leftover-quantity-hint = Wie viel
+leftover-keeps = Hält
+leftover-days = Tag(e)
+leftover-used = Aufgebraucht
+leftover-expired = abgelaufen
+leftover-today = heute verbrauchen
+leftover-days-left = noch { $days } Tag(e)
+plan-title = Essensplan
+plan-add = 📅 Einplanen für…
+plan-empty = Plane ein Rezept in seiner Detailansicht für einen Tag ein.
+plan-remove = Aus dem Plan entfernen
+plan-export = 📅 Als .ics exportieren
+plan-export-hint = Den Plan als iCalendar-Datei speichern, ein ganztägiger Termin pro Gericht
+allergies-title = Allergien
+recent-title = Zuletzt angesehen
+recent-empty = Geöffnete Rezepte erscheinen hier.
+timers-title = Timer ({ $count })
+timers-empty = Starte einen bei einem Schritt mit Garzeit.
+timer-done = ⏰ fertig
+timer-stop = Timer stoppen
+timer-finished-title = ⏰ Timer abgelaufen
+log-title = Protokoll ({ $count } Zeilen)
+log-empty = Noch nichts protokolliert.
+log-clear = Leeren
+log-file-hint = Vollständige Protokolle liegen in { $dir }
+
+## Fenster
+
+window-shopping = Einkaufsliste
+window-queue = Such-Warteschlange
+window-compare = Rezepte vergleichen
+window-my-recipes = Meine Rezepte
+window-settings = Einstellungen
+
+queue-empty = Reihe Suchen mit ➕ Einreihen neben Rezepte suchen ein; sie laufen nacheinander.
+queue-pause = ⏸ Anhalten
+queue-resume = ▶ Fortsetzen
+queue-clear-finished = Fertige entfernen
+queue-remove = Aus der Warteschlange entfernen
+queue-waiting = Wartet
+queue-paused = Angehalten, beginnt beim Fortsetzen neu
+queue-found = { $count } Rezept(e) gefunden
+queue-errors = { $count } Fehler
+queue-show = Ergebnisse anzeigen
+queue-result = { $title } ({ $score })
+
+shopping-empty = Setze 🛒 neben Rezepten, um eine Einkaufsliste zu erstellen.
+shopping-recipes = Rezepte:
+shopping-export = Als Textdatei exportieren
+shopping-total = Geschätzt insgesamt: { $total }
+aisle-heading = { $aisle } ({ $count }) – etwa { $subtotal }
+aisle-unpriced = { $count } Artikel ohne Preis sind nicht in der Zwischensumme
+aisle-produce = Obst & Gemüse
+aisle-bakery = Backwaren
+aisle-meat-fish = Fleisch & Fisch
+aisle-dairy = Milchprodukte & Eier
+aisle-pantry = Vorratsschrank
+aisle-spices = Kräuter & Gewürze
+aisle-frozen = Tiefkühl
+aisle-other = Sonstiges
+
+compare-empty = Setze ⚖ neben zwei Ergebnissen, um sie zu vergleichen.
+compare-legend-shared = Zutaten, die beide Rezepte haben, sind normal dargestellt,
+compare-legend-unique = Zutaten, die nur eines hat, sind hervorgehoben
+compare-ingredients = Zutaten ({ $count }, { $shared } gemeinsam)
+compare-instructions = Zubereitung
+compare-clear = Vergleich leeren
+
+my-recipes-new = ➕ Neues Rezept
+my-recipes-import = 📥 Importieren...
+my-recipes-import-hint = JSON- oder YAML-Rezeptdateien oder gespeicherte TheMealDB-Antworten
+my-recipes-empty = Noch keine eigenen Rezepte.
+recipe-form-new = Neues Rezept
+recipe-form-edit = Rezept bearbeiten
+recipe-form-ingredients = Zutaten (Name, Menge):
+recipe-form-add-ingredient = + Zutat hinzufügen
+import-summary = { $count } Rezept(e) importiert
+import-duplicates = , { $count } bereits vorhanden übersprungen
+import-failures = , { $count } Datei(en) fehlgeschlagen
+
+## Einstellungen
+
+settings-language = Sprache
+settings-appearance = Darstellung
+settings-mode = Modus:
+theme-dark = 🌙 Dunkel
+theme-light = ☀ Hell
+settings-accent = Akzentfarbe:
+settings-spacing = Abstände:
+settings-accessibility = Barrierefreiheit
+settings-text-size = Textgröße:
+settings-high-contrast = Hoher Kontrast:
+settings-high-contrast-detail = Schwarz-weiß mit kräftigen Umrissen
+settings-units = Einheiten
+units-as-written = Wie angegeben
+units-metric = Metrisch (g, ml)
+units-imperial = Imperial (oz, Cups)
+settings-region = Jahreszeiten
+region-northern = Nordhalbkugel
+region-southern = Südhalbkugel
+settings-themealdb = TheMealDB
+settings-api-key = API-Schlüssel:
+settings-api-key-env = Durch THEMEALDB_API_KEY gesetzt
+settings-api-key-hint = Ein kostenpflichtiger Schlüssel schaltet die neuesten Gerichte und die Suche nach mehreren Zutaten in einer Anfrage frei. Für die kostenlose API bei 1 lassen. Wirkt ab dem nächsten Start.
+settings-request-limit = Warnen nach so vielen Anfragen pro Tag:
+settings-request-limit-hint = Ein weiches Limit für API-Schlüssel mit Kontingent. Der Zähler neben der Quelle wird kurz davor gelb und danach rot; nichts wird blockiert.
+settings-backup = Sicherung
+backup-export = 💾 Exportieren...
+backup-export-hint = Favoriten, Bewertungen und Notizen aller Profile in eine JSON-Datei speichern
+backup-import = 📂 Wiederherstellen...
+backup-import-hint = Favoriten, Bewertungen und Notizen aus einer Sicherung hinzufügen; bei Bewertungen und Notizen gewinnt die Datei
+backup-exported = { $favorites } Favorit(en), { $ratings } Bewertung(en) und { $notes } Notiz(en) exportiert
+backup-restored = { $favorites } neue(r) Favorit(en), { $ratings } Bewertung(en) und { $notes } Notiz(en) wiederhergestellt
+backup-import-failed = Wiederherstellen fehlgeschlagen: { $error }
+backup-too-new = die Datei stammt aus einer neueren Version der App
+settings-background = Hintergrund
+settings-prefetch = Kategorien meiner Favoriten zwischenspeichern
+settings-prefetch-hint = Lädt ab und zu alle Gerichte dieser Kategorien, damit sie offline durchsucht werden können. Wirkt ab dem nächsten Start.
+settings-favorites-offline = Meine Favoriten für offline aktuell halten, alle
+settings-favorites-offline-hint = Lädt beim Start und danach in diesem Abstand jeden Favoriten neu und seine Fotos herunter, damit die Favoriten offline vollständig sind. Wirkt ab dem nächsten Start.
+settings-favorites-refresh-hours = Stunden (0: nur beim Start)
+settings-prices = Preise
+settings-currency = Währung:
+price-per-kg = pro kg
+price-per-piece = pro Stück
+price-add = ➕ Preis hinzufügen
+settings-scoring = Gewichtung der Punkte
+scoring-strategy = Sortieren nach:
+scorer-ingredient-overlap = Passenden Zutaten
+scorer-pantry-coverage = Am wenigsten einzukaufen
+scorer-nutrition-aware = Passenden Zutaten und Ernährungszielen
+scored-by = Bewertet nach: { $strategy }
+weight-main = Treffer Hauptzutat:
+weight-sub = Treffer Nebenzutat:
+weight-taste-title = Geschmack im Titel:
+weight-taste-tags = Geschmack in Schlagwörtern:
+weight-taste-category = Geschmack in Kategorie:
+weight-taste-area = Geschmack in Region:
+weight-taste-instructions = Geschmack in Zubereitung:
+weight-taste-ingredients = Zutaten zum Geschmack:
+taste-words = Geschmackswörter: { $words }
+taste-words-hint = Jedes Geschmackswort steht für Zutaten, Kategorien und Schlagwörter, z. B. spicy für Chili. Änderbar unter [[scoring.taste_words]] in { $file }.
+weight-unmatched = Abzug pro weiterer Zutat:
+weight-unmatched-hint = Zutaten, nach denen du nicht gefragt hast und die nicht im Vorrat sind
+weight-seasonal = Pro saisonaler Zutat:
+weight-leftover = Pro aufgebrauchtem Rest:
+
+## Terminal
+
+tui-help = Tab: nächster Bereich · Enter: suchen · ↑/↓: auswählen · Bild↑/Bild↓: blättern · Esc: beenden
+
+## Fehler
+
+search-failed = Suche fehlgeschlagen: { $error }
+meal-failed = Gericht { $id }: { $error }
+random-failed = Zufallsgericht: { $error }
+latest-failed = Neueste Gerichte: { $error }
+fetch-network = Netzwerkfehler: { $error }
+fetch-status = Server antwortete mit { $status }
+fetch-decode = Unerwartete Antwort: { $error }
@@ -0,0 +1,436 @@
+## General
+
+app-heading = Food Recipe Finder (GUI)
+add = Add
+cancel = Cancel
+clear = Clear
+delete = Delete
+dismiss = Dismiss
+edit = Edit
+save = Save
+reset-defaults = Reset to defaults
+saved-to = Saved to { $path }
+saved-path = Saved { $path }
+save-failed = Save failed: { $error }
+export-failed = Export failed: { $error }
+filter-any = Any
+field-title = Title:
+field-category = Category:
+field-area = Area:
+instructions-label = Instructions:
+ingredients-heading = Ingredients
+hint-ingredient = ingredient
+hint-measure = measure
+
+## Search form
+
+source-label = Source:
+offline-mode = 📴 Offline mode
+offline-warning = Offline: only cached, favorite and your own recipes are searched. Only photos downloaded for favorites are shown.
+quota-counter = 🌐 { $today } today · { $session } this run
+quota-limit-hint = Requests sent to the recipe providers, retries included. Soft limit: { $limit } a day.
+quota-no-limit-hint = Requests sent to the recipe providers, retries included. A daily limit can be set in the settings.
+quota-over = Today's { $limit } requests are used up. Searches still run, but a limited API key may start refusing them.
+name-label = Name:
+name-hint = e.g. carbonara
+name-search = Search by name
+browse-letters = Browse A–Z:
+cook-from-pantry = Cook from what I have (pantry)
+seasonal-boost = 🌱 Prefer seasonal produce
+seasonal-boost-hint = Recipes score higher for every ingredient that is in season now
+leftover-boost = ♻ Prefer leftovers
+leftover-boost-hint = Recipes score higher for every leftover they use up
+leftovers-use-up = ♻ Use up leftovers
+leftovers-use-up-hint = Search with your leftovers as sub ingredients, soonest to expire first
+taste-label = Taste:
+main-label = Main ingredients:
+sub-label = Sub ingredients:
+exclude-label = Exclude:
+hide-excluded = Hide recipes with excluded ingredients
+hide-excluded-hint = Off: keep them in the results but flag them. Allergies always count as excluded.
+diet-label = Diet:
+diet-vegetarian = 🥕 Vegetarian
+diet-vegan = 🌱 Vegan
+diet-hint = Judged from ingredient names, so double-check anything unusual
+budget-label = Max. cost:
+budget-hint = Estimated from the price table in the settings; unpriced ingredients count as free
+calorie-budget-label = Max. kcal per serving:
+calorie-budget-hint = From the nutrition estimate; ingredients it doesn't know count as nothing
+max-ingredients-label = Simple recipes, at most this many ingredients:
+max-ingredients-hint = Leaves out elaborate recipes, for weeknights and a small pantry
+max-time-label = Time:
+max-time-any = Any
+max-time-minutes = max. { $minutes } min
+max-time-hint = Estimated from the times in the instructions plus a few minutes per step; recipes without instructions are kept
+time-estimate = ⏱ { $minutes } min
+time-estimate-hint = Estimated from the times in the instructions plus a few minutes per step
+max-difficulty-label = Difficulty:
+max-difficulty-any = Any
+max-difficulty-medium = Easy or Medium
+max-difficulty-hint = A rough rating from the number of ingredients and steps and the techniques the instructions mention
+difficulty-easy = Easy
+difficulty-medium = Medium
+difficulty-hard = Hard
+difficulty-summary = { $ingredients } ingredients, { $steps } steps, techniques: { $techniques }
+fetch-recipes = Fetch Recipes
+random-recipe = Random Recipe
+latest-recipes = 🆕 Latest
+latest-recipes-hint = The meals added to TheMealDB most recently
+meal-of-the-day = Meal of the day
+meal-of-the-day-show = Show recipe
+seasonal-now = In season now:
+seasonal-add-hint = Add to the sub ingredients
+shopping-toggle = Shopping list ({ $count })
+compare-toggle = ⚖ Compare ({ $count }/{ $max })
+queue-toggle = 📋 Queue ({ $count })
+queue-add = ➕ Queue
+queue-add-hint = Run this search later in the background, after the ones already queued
+my-recipes-toggle = 📖 My Recipes
+settings-toggle = ⚙ Settings
+history-menu = 🕘 History
+history-empty = No searches yet.
+history-entry = { $summary } ({ $count } results)
+history-load-failed = Couldn't load history: { $error }
+presets-menu = 📌 Presets
+presets-empty = No presets yet. Fill in the search form, name it below and save.
+presets-delete = Delete this preset
+presets-name-hint = Preset name
+presets-save = Save current search
+presets-save-hint = Saves ingredients, taste, filters and sort order; a preset with the same name is replaced
+
+## Offline search
+
+offline-search-label = Offline search:
+offline-search-hint = words in title, ingredients or instructions
+offline-search-button = Search offline
+offline-matches = { $count } offline match(es)
+offline-matches-among = { $count } match(es) among { $total } recipes on this computer
+
+## Results
+
+search-cancelled = Search cancelled.
+progress-loaded = { $done }/{ $total } meals loaded
+progress-candidates = Looking for candidates...
+no-results = No recipes found.
+search-failed-see-below = Search failed, see the error report below.
+details-header = ⚠ Details: { $failed } failed, { $slow } slow
+diagnostics-attempt = Try
+diagnostics-result = Result
+diagnostics-time = Time
+diagnostics-url = Request
+diagnostics-slow = slow ({ $status })
+results-heading = Top recipes:
+results-keyboard-hint = Keyboard: ↑/↓ pick a recipe, F toggles favorite, Esc closes the details
+sort-selected = Sort: { $order }
+sort-score = Score
+sort-title = Title (A–Z)
+sort-category = Category
+sort-area = Area
+sort-ingredient-count = Fewest ingredients
+sort-rating = My rating
+view-list = ☰ List
+view-grid = ▦ Grid
+tag-filter-active = tagged #{ $tag }
+tag-filter-clear = Clear tag filter
+tag-filter-hint = Show only results with this tag
+instruction-filter-label = Instructions mention:
+instruction-filter-hint = e.g. slow cooker, oven
+page-prev = ◀ Prev
+page-next = Next ▶
+page-status = Page { $page } of { $pages } ({ $count } recipes)
+score = Score { $score }
+result-title = { $rank }: { $title }
+score-hint = Click to see where the points come from
+window-score-breakdown = Score: { $title }
+breakdown-main = { $ingredient } (main ingredient “{ $wanted }”)
+breakdown-sub = { $ingredient } (sub ingredient “{ $wanted }”)
+breakdown-unmatched = { $ingredient } (not asked for, not on hand)
+breakdown-taste-title = Taste in the title
+breakdown-taste-tags = Taste in the tags
+breakdown-taste-category = Taste in the category
+breakdown-taste-area = Taste in the area
+breakdown-taste-instructions = Taste in the instructions
+breakdown-taste-ingredients = Ingredients with the taste
+breakdown-have = { $ingredient } (have it)
+breakdown-to-buy = { $ingredient } (to buy)
+breakdown-seasonal = { $count } ingredient(s) in season
+breakdown-leftovers = { $count } leftover(s) used up
+breakdown-nutrition = Nutrition goals
+breakdown-unexplained = Not explained (scored with other settings)
+breakdown-total = Total
+breakdown-empty = This scoring strategy doesn't break its score down.
+open-window-hint = Open in a new window
+your-rating = Your rating
+diff-summary = Since the last search: { $new } new, { $dropped } dropped out, { $rescored } rescored
+diff-dropped = Dropped out ({ $count })
+diff-dropped-title = { $title } (Score { $score })
+diff-dropped-hint = In the last search's results but not in these
+diff-new = 🆕 New
+diff-badge-hint = Compared with the search before this one
+excluded-hint = Contains excluded ingredients
+to-buy = { $count } to buy
+compare-pick-hint = Pick two recipes to compare
+shopping-include-hint = Include in shopping list
+favorite-add = Add to favorites
+favorite-remove = Remove from favorites
+
+## Recipe details
+
+details-heading = Recipe Details:
+open-in-window = 🗗 Open in window
+detail-edit = ✏ Edit
+detail-category = Category: { $category }
+detail-area = Area: { $area }
+rating-label = My rating:
+rating-star-hint = { $stars } of { $max }
+watch-video = ▶ Watch video
+open-source = 🔗 Open source
+export-markdown = Export as Markdown
+export-pdf = Export as PDF
+export-html = Export as HTML
+export-html-open = Open in browser
+export-tasks = Add missing to task list
+export-tasks-hint = Adds every ingredient your pantry doesn't cover to the task manager's todo list
+export-results = 📄 Export results
+export-results-hint = Saves ID, title, category, area, score and ingredient count of every result as CSV, for a spreadsheet
+tasks-added = Added { $count } task(s) to { $path }
+servings-before = Ingredients for
+servings-after = servings
+servings-reset = Back to the recipe's servings
+ingredient-highlight-legend = Bold: matches your ingredients · plain: at home · grey: not at hand
+excluded-warning = ⚠ Contains excluded ingredients: { $items }
+pantry-have = From your pantry: { $items }
+pantry-need = Still to buy: { $items }
+notes-label = My notes:
+notes-hint = e.g. reduce salt next time
+timer-start-hint = Start a timer for this step
+timer-name = { $title }, step { $step }
+cooking-start = 👩‍🍳 Cook
+cooking-start-hint = Step by step in large text, full screen. ←/→ or space to move, Esc to leave.
+cooking-progress = Step { $step } of { $count }
+cooking-back = ◀ Back
+cooking-next = Next ▶
+cooking-finish = Done ✔
+cooking-exit = ✖ Leave
+cooking-reminders = You'll need:
+speech-start = 🔊 Read aloud
+speech-start-hint = Reads the instructions out one step after the other
+speech-progress = Reading step { $step } of { $count }
+speech-back = Previous step
+speech-pause = Pause
+speech-resume = Resume from the start of this step
+speech-skip = Next step
+speech-stop = Stop reading
+speech-step = Step { $step }. { $text }
+speech-failed = Couldn't read aloud: { $error }
+cached-today = cached today
+cached-yesterday = cached yesterday
+cached-days = cached { $days } days ago
+cache-stale-hint = Older than the cache lifetime; the recipe may have changed online
+
+nutrition-title = Nutrition (estimated)
+similar-title = Similar recipes
+similar-empty = No cached recipe shares an ingredient with this one yet.
+nutrition-total = Total
+nutrition-per-serving = Per serving ({ $servings })
+nutrition-calories = Calories (kcal)
+nutrition-protein = Protein (g)
+nutrition-fat = Fat (g)
+nutrition-carbs = Carbs (g)
+nutrition-sugar = of which sugar (g)
+nutrition-not-counted = Not counted: { $items }
+cost-title = Estimated cost
+cost-estimate = { $total } in total, { $per_serving } per serving ({ $servings } servings)
+cost-not-counted = No price for: { $items }
+
+## Side panel
+
+profile-title = 👤 Profile: { $name }
+profile-keep-inputs = Keep current taste and diet
+profile-keep-inputs-hint = Saved with this profile and filled in whenever it is picked
+profile-new-hint = New profile name
+profile-add = Add
+profile-delete = Delete { $name }
+profile-nutrition-goals = Nutrition goals:
+profile-nutrition-goals-hint = Used when results are scored by matching ingredients and nutrition goals
+goal-high-protein = High protein
+goal-low-calorie = Low calorie
+goal-low-sugar = Low sugar
+goal-low-fat = Low fat
+profile-allergens = Warn about:
+allergen-gluten = Gluten
+allergen-nuts = Nuts
+allergen-peanuts = Peanuts
+allergen-shellfish = Shellfish
+allergen-fish = Fish
+allergen-dairy = Dairy
+allergen-eggs = Eggs
+allergen-soy = Soy
+allergen-sesame = Sesame
+allergen-hint = From: { $items }. Judged from ingredient names, so check the packaging too
+profile-picker-title = Who's cooking?
+favorites-title = Favorites
+favorites-empty = No favorites yet.
+pantry-title = Pantry
+leftovers-title = Leftovers
+leftover-ingredient-hint = Ingredient
+leftover-quantity-hint = How much
+leftover-keeps = Keeps for
+leftover-days = day(s)
+leftover-used = Used up
+leftover-expired = past its date
+leftover-today = use today
+leftover-days-left = { $days } day(s) left
+plan-title = Meal plan
+plan-add = 📅 Plan for…
+plan-empty = Plan a recipe for a day from its detail view.
+plan-remove = Remove from the plan
+plan-export = 📅 Export .ics
+plan-export-hint = Save the plan as an iCalendar file, one all-day event per meal
+allergies-title = Allergies
+recent-title = Recently viewed
+recent-empty = Recipes you open show up here.
+timers-title = Timers ({ $count })
+timers-empty = Start one from a step with a cooking time.
+timer-done = ⏰ done
+timer-stop = Stop timer
+timer-finished-title = ⏰ Timer finished
+log-title = Log ({ $count } lines)
+log-empty = Nothing logged yet.
+log-clear = Clear
+log-file-hint = Full logs are kept in { $dir }
+
+## Windows
+
+window-shopping = Shopping List
+window-queue = Search queue
+window-compare = Compare recipes
+window-my-recipes = My Recipes
+window-settings = Settings
+
+queue-empty = Queue searches with ➕ Queue next to Fetch Recipes; they run one after another.
+queue-pause = ⏸ Pause
+queue-resume = ▶ Resume
+queue-clear-finished = Clear finished
+queue-remove = Remove from the queue
+queue-waiting = Waiting
+queue-paused = Paused, starts over on resume
+queue-found = { $count } recipe(s) found
+queue-errors = { $count } error(s)
+queue-show = Show results
+queue-result = { $title } ({ $score })
+
+shopping-empty = Tick 🛒 next to recipes to build a shopping list.
+shopping-recipes = Recipes:
+shopping-export = Export to text file
+shopping-total = Estimated total: { $total }
+aisle-heading = { $aisle } ({ $count }) – about { $subtotal }
+aisle-unpriced = { $count } item(s) without a price aren't in the subtotal
+aisle-produce = Fruit & vegetables
+aisle-bakery = Bakery
+aisle-meat-fish = Meat & fish
+aisle-dairy = Dairy & eggs
+aisle-pantry = Pantry
+aisle-spices = Herbs & spices
+aisle-frozen = Frozen
+aisle-other = Other
+
+compare-empty = Tick ⚖ next to two results to compare them.
+compare-legend-shared = Ingredients in both recipes are plain,
+compare-legend-unique = ingredients only one has are highlighted
+compare-ingredients = Ingredients ({ $count }, { $shared } shared)
+compare-instructions = Instructions
+compare-clear = Clear comparison
+
+my-recipes-new = ➕ New recipe
+my-recipes-import = 📥 Import...
+my-recipes-import-hint = JSON or YAML recipe files, or saved TheMealDB responses
+my-recipes-empty = No recipes of your own yet.
+recipe-form-new = New recipe
+recipe-form-edit = Edit recipe
+recipe-form-ingredients = Ingredients (name, measure):
+recipe-form-add-ingredient = + Add ingredient
+import-summary = Imported { $count } recipe(s)
+import-duplicates = , skipped { $count } already present
+import-failures = , { $count } file(s) failed
+
+## Settings
+
+settings-language = Language
+settings-appearance = Appearance
+settings-mode = Mode:
+theme-dark = 🌙 Dark
+theme-light = ☀ Light
+settings-accent = Accent colour:
+settings-spacing = Spacing:
+settings-accessibility = Accessibility
+settings-text-size = Text size:
+settings-high-contrast = High contrast:
+settings-high-contrast-detail = Black and white with strong outlines
+settings-units = Units
+units-as-written = As written
+units-metric = Metric (g, ml)
+units-imperial = Imperial (oz, cups)
+settings-region = Seasons
+region-northern = Northern hemisphere
+region-southern = Southern hemisphere
+settings-themealdb = TheMealDB
+settings-api-key = API key:
+settings-api-key-env = Set by THEMEALDB_API_KEY
+settings-api-key-hint = A paid key unlocks the latest meals and searching several ingredients in one request. Leave it at 1 for the free API. Takes effect on the next start.
+settings-request-limit = Warn after this many requests a day:
+settings-request-limit-hint = A soft limit for rate-limited API keys. The counter next to the source turns yellow close to it and red past it; nothing is blocked.
+settings-backup = Backup
+backup-export = 💾 Export...
+backup-export-hint = Save favorites, ratings and notes of every profile to one JSON file
+backup-import = 📂 Restore...
+backup-import-hint = Add favorites, ratings and notes from a backup file; the file wins where both have a rating or note
+backup-exported = Exported { $favorites } favorite(s), { $ratings } rating(s) and { $notes } note(s)
+backup-restored = Restored { $favorites } new favorite(s), { $ratings } rating(s) and { $notes } note(s)
+backup-import-failed = Restore failed: { $error }
+backup-too-new = the file comes from a newer version of the app
+settings-background = Background
+settings-prefetch = Keep the categories of my favorites cached
+settings-prefetch-hint = Fetches every meal in those categories now and then, so they can be browsed offline. Takes effect on the next start.
+settings-favorites-offline = Keep my favorites up to date for offline use, every
+settings-favorites-offline-hint = Fetches every favorite again and downloads its photos at launch and then on this schedule, so the favorites work fully offline. Takes effect on the next start.
+settings-favorites-refresh-hours = hours (0: only at launch)
+settings-prices = Prices
+settings-currency = Currency:
+price-per-kg = per kg
+price-per-piece = per piece
+price-add = ➕ Add price
+settings-scoring = Scoring weights
+scoring-strategy = Rank by:
+scorer-ingredient-overlap = Matching ingredients
+scorer-pantry-coverage = Least left to buy
+scorer-nutrition-aware = Matching ingredients and nutrition goals
+scored-by = Scored by: { $strategy }
+weight-main = Main ingredient match:
+weight-sub = Sub ingredient match:
+weight-taste-title = Taste in title:
+weight-taste-tags = Taste in tags:
+weight-taste-category = Taste in category:
+weight-taste-area = Taste in area:
+weight-taste-instructions = Taste in instructions:
+weight-taste-ingredients = Taste's ingredients:
+taste-words = Taste words: { $words }
+taste-words-hint = Each taste word stands for ingredients, categories and tags, e.g. spicy for chili. Change them under [[scoring.taste_words]] in { $file }.
+weight-unmatched = Penalty per other ingredient:
+weight-unmatched-hint = Ingredients you didn't ask for and don't have in the pantry
+weight-seasonal = Per seasonal ingredient:
+weight-leftover = Per leftover used up:
+
+## Terminal
+
+tui-help = Tab: next pane · Enter: search · ↑/↓: choose · PgUp/PgDn: scroll · Esc: quit
+
+## Errors
+
+search-failed = Search failed: { $error }
+meal-failed = Meal { $id }: { $error }
+random-failed = Random meal: { $error }
+latest-failed = Latest meals: { $error }
+fetch-network = network error: { $error }
+fetch-status = server returned { $status }
+fetch-decode = unexpected response: { $error }
@@ -0,0 +1,49 @@
+use crate::db::Database;
+use crate::provider::themealdb::MealFull;
+use std::{io, sync::Arc, time::Duration};
+
+pub const DEFAULT_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Cache of `lookup.php` results in the app database, keyed by meal ID.
+/// An entry is stale once it is older than the TTL.
+pub struct MealCache {
+    db: Arc<Database>,
+    ttl: Duration,
+}
+
+impl MealCache {
+    pub fn new(db: Arc<Database>, ttl: Duration) -> Self {
+        Self { db, ttl }
+    }
+
+    /// Returns the cached meal if present and younger than the TTL.
+    pub fn get(&self, id: &str) -> Option<MealFull> {
+        self.db.cached_meal(id, self.ttl)
+    }
+
+    pub fn put(&self, id: &str, meal: &MealFull) -> io::Result<()> {
+        self.db.cache_meal(id, meal).map_err(io::Error::other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOOKUP_52772: &str = include_str!("../testdata/themealdb/lookup_52772.json");
+
+    fn recorded_meal() -> MealFull {
+        let mut list: serde_json::Value = serde_json::from_str(LOOKUP_52772).unwrap();
+        serde_json::from_value(list["meals"][0].take()).unwrap()
+    }
+
+    #[test]
+    fn returns_what_was_put() {
+        let cache = MealCache::new(Arc::new(Database::in_memory().unwrap()), DEFAULT_TTL);
+        let meal = recorded_meal();
+        assert!(cache.get("52772").is_none());
+        cache.put("52772", &meal).unwrap();
+        assert_eq!(cache.get("52772"), Some(meal));
+        assert!(cache.get("52771").is_none());
+    }
+}
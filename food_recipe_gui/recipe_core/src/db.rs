@@ -0,0 +1,535 @@
+use crate::provider::themealdb::MealFull;
+use crate::MealDetail;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::{
+    collections::{HashMap, HashSet},
+    fs::{self, File},
+    io::BufReader,
+    path::Path,
+    sync::Mutex,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+pub const DB_FILE: &str = "recipes.db";
+
+// Bump together with a new step in `migrate`
+const SCHEMA_VERSION: i32 = 8;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS meal_cache (
+        id         TEXT PRIMARY KEY,
+        json       TEXT NOT NULL,
+        fetched_at INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS favorites (
+        profile  TEXT NOT NULL DEFAULT 'Default',
+        position INTEGER NOT NULL,
+        id       TEXT NOT NULL,
+        json     TEXT NOT NULL,
+        PRIMARY KEY (profile, id)
+    );
+    CREATE TABLE IF NOT EXISTS my_recipes (
+        position INTEGER NOT NULL,
+        id       TEXT PRIMARY KEY,
+        json     TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS ingredient_lists (
+        list     TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        item     TEXT NOT NULL,
+        PRIMARY KEY (list, item)
+    );
+    CREATE TABLE IF NOT EXISTS notes (
+        meal_id    TEXT PRIMARY KEY,
+        body       TEXT NOT NULL,
+        updated_at INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS search_history (
+        id           INTEGER PRIMARY KEY AUTOINCREMENT,
+        provider     TEXT NOT NULL,
+        query        TEXT NOT NULL,
+        result_count INTEGER NOT NULL,
+        searched_at  INTEGER NOT NULL,
+        inputs       TEXT
+    );
+    CREATE TABLE IF NOT EXISTS recently_viewed (
+        position INTEGER NOT NULL,
+        id       TEXT PRIMARY KEY,
+        json     TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS ratings (
+        meal_id  TEXT PRIMARY KEY,
+        stars    INTEGER NOT NULL,
+        rated_at INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS profiles (
+        position INTEGER NOT NULL,
+        name     TEXT PRIMARY KEY,
+        json     TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS leftovers (
+        position INTEGER PRIMARY KEY,
+        json     TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS search_presets (
+        position INTEGER NOT NULL,
+        name     TEXT PRIMARY KEY,
+        json     TEXT NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS meal_plan (
+        position INTEGER PRIMARY KEY,
+        json     TEXT NOT NULL
+    );
+";
+
+// Version 5 moved favorites from one shared list to one list per profile
+const FAVORITES_PER_PROFILE: &str = "
+    ALTER TABLE favorites RENAME TO favorites_shared;
+    CREATE TABLE favorites (
+        profile  TEXT NOT NULL DEFAULT 'Default',
+        position INTEGER NOT NULL,
+        id       TEXT NOT NULL,
+        json     TEXT NOT NULL,
+        PRIMARY KEY (profile, id)
+    );
+    INSERT INTO favorites (position, id, json) SELECT position, id, json FROM favorites_shared;
+    DROP TABLE favorites_shared;
+";
+
+/// Profile that owns everything saved before profiles existed. It can't be deleted.
+pub const DEFAULT_PROFILE: &str = "Default";
+
+// Files the app used before everything moved into the database
+const LEGACY_FAVORITES_FILE: &str = "favorites.json";
+const LEGACY_MY_RECIPES_FILE: &str = "my_recipes.json";
+const LEGACY_LIST_FILES: &[(&str, &str)] = &[("pantry", "pantry.json"), ("allergies", "allergies.json")];
+const LEGACY_CACHE_DIR: &str = "meal_cache";
+
+/// One row of the search history.
+pub struct SearchRecord {
+    pub summary: String,
+    /// Serialized `SearchQuery`.
+    pub inputs: String,
+    pub result_count: usize,
+}
+
+/// Tables holding whole recipes as JSON, in display order.
+#[derive(Clone, Copy)]
+pub enum MealTable {
+    MyRecipes,
+    RecentlyViewed,
+}
+
+impl MealTable {
+    fn name(self) -> &'static str {
+        match self {
+            MealTable::MyRecipes => "my_recipes",
+            MealTable::RecentlyViewed => "recently_viewed",
+        }
+    }
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+fn read_json<T: serde::de::DeserializeOwned + Default>(path: &Path) -> T {
+    File::open(path)
+        .ok()
+        .and_then(|f| serde_json::from_reader(BufReader::new(f)).ok())
+        .unwrap_or_default()
+}
+
+/// All persistent state in one SQLite file. Shared between the UI and the
+/// background tasks, so the connection sits behind a mutex.
+pub struct Database {
+    conn: Mutex<Connection>,
+}
+
+impl Database {
+    pub fn open(path: impl AsRef<Path>) -> rusqlite::Result<Self> {
+        let db = Self {
+            conn: Mutex::new(Connection::open(path)?),
+        };
+        db.migrate()?;
+        Ok(db)
+    }
+
+    /// Empty database that lives only as long as the process; the fallback
+    /// when the file can't be opened.
+    pub fn in_memory() -> rusqlite::Result<Self> {
+        let db = Self {
+            conn: Mutex::new(Connection::open_in_memory()?),
+        };
+        db.migrate()?;
+        Ok(db)
+    }
+
+    fn migrate(&self) -> rusqlite::Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let version: i32 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+        if version >= SCHEMA_VERSION {
+            return Ok(());
+        }
+        let tx = conn.transaction()?;
+        tx.execute_batch(SCHEMA)?;
+        if version < 1 {
+            import_legacy_files(&tx)?;
+        }
+        if (1..4).contains(&version) {
+            tx.execute_batch("ALTER TABLE search_history ADD COLUMN inputs TEXT")?;
+        }
+        if (1..5).contains(&version) {
+            tx.execute_batch(FAVORITES_PER_PROFILE)?;
+        }
+        // Versions 6 to 8 only added the leftovers, search_presets and meal_plan tables, which SCHEMA creates
+        tx.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+        tx.commit()
+    }
+
+    pub fn load_meals(&self, table: MealTable) -> rusqlite::Result<Vec<MealDetail>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(&format!("SELECT json FROM {} ORDER BY position", table.name()))?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        let mut meals = Vec::new();
+        for json in rows {
+            // A row we can't parse any more is dropped rather than failing the whole list
+            if let Ok(meal) = serde_json::from_str(&json?) {
+                meals.push(meal);
+            }
+        }
+        Ok(meals)
+    }
+
+    /// Replaces the whole table in one transaction.
+    pub fn save_meals(&self, table: MealTable, meals: &[MealDetail]) -> rusqlite::Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        replace_meals(&tx, table, meals)?;
+        tx.commit()
+    }
+
+    pub fn load_favorites(&self, profile: &str) -> rusqlite::Result<Vec<MealDetail>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT json FROM favorites WHERE profile = ?1 ORDER BY position")?;
+        let rows = stmt.query_map([profile], |row| row.get::<_, String>(0))?;
+        let mut meals = Vec::new();
+        for json in rows {
+            if let Ok(meal) = serde_json::from_str(&json?) {
+                meals.push(meal);
+            }
+        }
+        Ok(meals)
+    }
+
+    /// Swaps in a newer copy of a favorite for every profile that has it,
+    /// leaving the lists' order and membership alone.
+    pub fn update_favorite(&self, meal: &MealDetail) -> rusqlite::Result<()> {
+        let conn = self.conn.lock().unwrap();
+        let json = serde_json::to_string(meal).unwrap();
+        conn.execute("UPDATE favorites SET json = ?2 WHERE id = ?1", params![meal.id, json])?;
+        Ok(())
+    }
+
+    /// Replaces the profile's favorites; other profiles keep theirs.
+    pub fn save_favorites(&self, profile: &str, meals: &[MealDetail]) -> rusqlite::Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        replace_favorites(&tx, profile, meals)?;
+        tx.commit()
+    }
+
+    /// Name and serialized settings of every profile, in display order.
+    pub fn load_profiles(&self) -> rusqlite::Result<Vec<(String, String)>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT name, json FROM profiles ORDER BY position")?;
+        let profiles = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?.collect();
+        profiles
+    }
+
+    /// Replaces the whole profile table in one transaction.
+    pub fn save_profiles(&self, profiles: &[(String, String)]) -> rusqlite::Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        tx.execute("DELETE FROM profiles", [])?;
+        {
+            let mut stmt = tx.prepare("INSERT OR REPLACE INTO profiles (position, name, json) VALUES (?1, ?2, ?3)")?;
+            for (position, (name, json)) in profiles.iter().enumerate() {
+                stmt.execute(params![position as i64, name, json])?;
+            }
+        }
+        tx.commit()
+    }
+
+    /// Name and serialized search of every preset, in display order.
+    pub fn load_presets(&self) -> rusqlite::Result<Vec<(String, String)>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT name, json FROM search_presets ORDER BY position")?;
+        let presets = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?.collect();
+        presets
+    }
+
+    pub fn save_presets(&self, presets: &[(String, String)]) -> rusqlite::Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        tx.execute("DELETE FROM search_presets", [])?;
+        {
+            let mut stmt = tx.prepare("INSERT OR REPLACE INTO search_presets (position, name, json) VALUES (?1, ?2, ?3)")?;
+            for (position, (name, json)) in presets.iter().enumerate() {
+                stmt.execute(params![position as i64, name, json])?;
+            }
+        }
+        tx.commit()
+    }
+
+    /// Serialized leftovers, in display order.
+    pub fn load_leftovers(&self) -> rusqlite::Result<Vec<String>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT json FROM leftovers ORDER BY position")?;
+        let leftovers = stmt.query_map([], |row| row.get(0))?.collect();
+        leftovers
+    }
+
+    pub fn save_leftovers(&self, leftovers: &[String]) -> rusqlite::Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        tx.execute("DELETE FROM leftovers", [])?;
+        {
+            let mut stmt = tx.prepare("INSERT INTO leftovers (position, json) VALUES (?1, ?2)")?;
+            for (position, json) in leftovers.iter().enumerate() {
+                stmt.execute(params![position as i64, json])?;
+            }
+        }
+        tx.commit()
+    }
+
+    /// Serialized meal plan entries, in date order.
+    pub fn load_meal_plan(&self) -> rusqlite::Result<Vec<String>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT json FROM meal_plan ORDER BY position")?;
+        let plan = stmt.query_map([], |row| row.get(0))?.collect();
+        plan
+    }
+
+    pub fn save_meal_plan(&self, plan: &[String]) -> rusqlite::Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        tx.execute("DELETE FROM meal_plan", [])?;
+        {
+            let mut stmt = tx.prepare("INSERT INTO meal_plan (position, json) VALUES (?1, ?2)")?;
+            for (position, json) in plan.iter().enumerate() {
+                stmt.execute(params![position as i64, json])?;
+            }
+        }
+        tx.commit()
+    }
+
+    pub fn load_list(&self, list: &str) -> rusqlite::Result<Vec<String>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT item FROM ingredient_lists WHERE list = ?1 ORDER BY position")?;
+        let items = stmt.query_map([list], |row| row.get(0))?.collect();
+        items
+    }
+
+    pub fn save_list(&self, list: &str, items: &[String]) -> rusqlite::Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        replace_list(&tx, list, items)?;
+        tx.commit()
+    }
+
+    /// Cached API meal, if fetched less than `ttl` ago.
+    pub fn cached_meal(&self, id: &str, ttl: Duration) -> Option<MealFull> {
+        let conn = self.conn.lock().unwrap();
+        let oldest = now() - ttl.as_secs() as i64;
+        let json: String = conn
+            .query_row(
+                "SELECT json FROM meal_cache WHERE id = ?1 AND fetched_at >= ?2",
+                params![id, oldest],
+                |row| row.get(0),
+            )
+            .optional()
+            .ok()??;
+        serde_json::from_str(&json).ok()
+    }
+
+    pub fn cache_meal(&self, id: &str, meal: &MealFull) -> rusqlite::Result<()> {
+        let json = serde_json::to_string(meal).unwrap();
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT OR REPLACE INTO meal_cache (id, json, fetched_at) VALUES (?1, ?2, ?3)",
+            params![id, json, now()],
+        )?;
+        Ok(())
+    }
+
+    /// Every cached meal regardless of age, with how long ago it was fetched.
+    pub fn all_cached_meals(&self) -> rusqlite::Result<Vec<(MealFull, Duration)>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT json, fetched_at FROM meal_cache")?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))?;
+        let now = now();
+        let mut meals = Vec::new();
+        for row in rows {
+            let (json, fetched_at) = row?;
+            if let Ok(meal) = serde_json::from_str(&json) {
+                let age = Duration::from_secs(now.saturating_sub(fetched_at).max(0) as u64);
+                meals.push((meal, age));
+            }
+        }
+        Ok(meals)
+    }
+
+    pub fn load_notes(&self) -> rusqlite::Result<HashMap<String, String>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT meal_id, body FROM notes")?;
+        let notes = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?.collect();
+        notes
+    }
+
+    /// Stores the note for a meal; an empty body deletes it.
+    pub fn save_note(&self, meal_id: &str, body: &str) -> rusqlite::Result<()> {
+        let conn = self.conn.lock().unwrap();
+        if body.trim().is_empty() {
+            conn.execute("DELETE FROM notes WHERE meal_id = ?1", [meal_id])?;
+        } else {
+            conn.execute(
+                "INSERT OR REPLACE INTO notes (meal_id, body, updated_at) VALUES (?1, ?2, ?3)",
+                params![meal_id, body, now()],
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn load_ratings(&self) -> rusqlite::Result<HashMap<String, u8>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare("SELECT meal_id, stars FROM ratings")?;
+        let ratings = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?.collect();
+        ratings
+    }
+
+    /// Stores a 1–5 star rating; 0 removes it.
+    pub fn save_rating(&self, meal_id: &str, stars: u8) -> rusqlite::Result<()> {
+        let conn = self.conn.lock().unwrap();
+        if stars == 0 {
+            conn.execute("DELETE FROM ratings WHERE meal_id = ?1", [meal_id])?;
+        } else {
+            conn.execute(
+                "INSERT OR REPLACE INTO ratings (meal_id, stars, rated_at) VALUES (?1, ?2, ?3)",
+                params![meal_id, stars, now()],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// `inputs` is the serialized query, so the search can be run again later.
+    pub fn record_search(&self, provider: &str, query: &str, result_count: usize, inputs: &str) -> rusqlite::Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO search_history (provider, query, result_count, searched_at, inputs) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![provider, query, result_count as i64, now(), inputs],
+        )?;
+        Ok(())
+    }
+
+    /// Latest re-runnable searches, newest first, each summary once.
+    pub fn recent_searches(&self, limit: usize) -> rusqlite::Result<Vec<SearchRecord>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT query, inputs, result_count FROM search_history
+             WHERE inputs IS NOT NULL ORDER BY id DESC",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(SearchRecord {
+                summary: row.get(0)?,
+                inputs: row.get(1)?,
+                result_count: row.get::<_, i64>(2)? as usize,
+            })
+        })?;
+        let mut seen = HashSet::new();
+        let mut records = Vec::new();
+        for record in rows {
+            let record = record?;
+            if seen.insert(record.summary.clone()) {
+                records.push(record);
+                if records.len() == limit {
+                    break;
+                }
+            }
+        }
+        Ok(records)
+    }
+}
+
+fn replace_meals(tx: &rusqlite::Transaction, table: MealTable, meals: &[MealDetail]) -> rusqlite::Result<()> {
+    tx.execute(&format!("DELETE FROM {}", table.name()), [])?;
+    let mut stmt = tx.prepare(&format!(
+        "INSERT OR REPLACE INTO {} (position, id, json) VALUES (?1, ?2, ?3)",
+        table.name()
+    ))?;
+    for (position, meal) in meals.iter().enumerate() {
+        let json = serde_json::to_string(meal).unwrap();
+        stmt.execute(params![position as i64, meal.id, json])?;
+    }
+    Ok(())
+}
+
+fn replace_favorites(tx: &rusqlite::Transaction, profile: &str, meals: &[MealDetail]) -> rusqlite::Result<()> {
+    tx.execute("DELETE FROM favorites WHERE profile = ?1", [profile])?;
+    let mut stmt = tx.prepare("INSERT OR REPLACE INTO favorites (profile, position, id, json) VALUES (?1, ?2, ?3, ?4)")?;
+    for (position, meal) in meals.iter().enumerate() {
+        let json = serde_json::to_string(meal).unwrap();
+        stmt.execute(params![profile, position as i64, meal.id, json])?;
+    }
+    Ok(())
+}
+
+fn replace_list(tx: &rusqlite::Transaction, list: &str, items: &[String]) -> rusqlite::Result<()> {
+    tx.execute("DELETE FROM ingredient_lists WHERE list = ?1", [list])?;
+    let mut stmt = tx.prepare("INSERT OR IGNORE INTO ingredient_lists (list, position, item) VALUES (?1, ?2, ?3)")?;
+    for (position, item) in items.iter().enumerate() {
+        stmt.execute(params![list, position as i64, item])?;
+    }
+    Ok(())
+}
+
+// One-off move of the old JSON files into a fresh database. The files are left
+// in place so going back to an older build loses nothing.
+fn import_legacy_files(tx: &rusqlite::Transaction) -> rusqlite::Result<()> {
+    let favorites: Vec<MealDetail> = read_json(Path::new(LEGACY_FAVORITES_FILE));
+    replace_favorites(tx, DEFAULT_PROFILE, &favorites)?;
+    let my_recipes: Vec<MealDetail> = read_json(Path::new(LEGACY_MY_RECIPES_FILE));
+    replace_meals(tx, MealTable::MyRecipes, &my_recipes)?;
+    for (list, file) in LEGACY_LIST_FILES {
+        let items: Vec<String> = read_json(Path::new(file));
+        replace_list(tx, list, &items)?;
+    }
+
+    let Ok(entries) = fs::read_dir(LEGACY_CACHE_DIR) else {
+        return Ok(());
+    };
+    let mut stmt = tx.prepare("INSERT OR REPLACE INTO meal_cache (id, json, fetched_at) VALUES (?1, ?2, ?3)")?;
+    for path in entries.filter_map(|e| e.ok()).map(|e| e.path()) {
+        let Some(id) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let Ok(json) = fs::read_to_string(&path) else {
+            continue;
+        };
+        // Keep the file's age so stale entries stay stale
+        let fetched_at = fs::metadata(&path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        stmt.execute(params![id, json, fetched_at])?;
+    }
+    Ok(())
+}
@@ -0,0 +1,15 @@
+//! Everything behind the recipe finder's frontends: the recipe model, the
+//! provider API clients, ingredient matching and synonyms, measure
+//! quantities, scoring and the SQLite store with the meal cache. The GUI,
+//! the terminal UI and the headless mode are thin layers over this crate.
+
+pub mod cache;
+pub mod db;
+pub mod matching;
+mod model;
+pub mod provider;
+pub mod quantity;
+pub mod scoring;
+pub mod synonyms;
+
+pub use model::MealDetail;
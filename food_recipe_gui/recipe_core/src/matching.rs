@@ -0,0 +1,188 @@
+// Loose ingredient comparison: "Tomatoes" should find "tomato", "chili" should
+// find "chilli", "cilantro" should find "coriander", without "ice" turning up
+// every recipe with rice.
+
+use crate::synonyms;
+use crate::MealDetail;
+use std::collections::HashSet;
+
+/// Words shorter than this only match exactly, after plural stripping.
+const MIN_FUZZY_LEN: usize = 5;
+
+/// Lowercases and strips common English plural endings.
+pub fn normalize(word: &str) -> String {
+    let word = word.to_lowercase();
+    if let Some(stem) = word.strip_suffix("ies") {
+        if stem.len() >= 2 {
+            return format!("{}y", stem);
+        }
+    }
+    for suffix in ["oes", "ches", "shes", "xes", "sses"] {
+        if let Some(stem) = word.strip_suffix(suffix) {
+            if stem.len() >= 2 {
+                return format!("{}{}", stem, &suffix[..suffix.len() - 2]);
+            }
+        }
+    }
+    if word.len() > 3 && word.ends_with('s') && !word.ends_with("ss") {
+        return word[..word.len() - 1].to_string();
+    }
+    word
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        cur[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitute = prev[j] + usize::from(ca != cb);
+            cur[j + 1] = substitute.min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
+// Typos allowed grow with word length; short words must match exactly
+fn allowed_distance(len: usize) -> usize {
+    match len {
+        0..MIN_FUZZY_LEN => 0,
+        MIN_FUZZY_LEN..=8 => 1,
+        _ => 2,
+    }
+}
+
+// `have` is a recipe word, `want` the user's; "pepper" also finds "peppercorns"
+fn words_match(have: &str, want: &str) -> bool {
+    if have == want || (want.len() >= 3 && have.starts_with(want)) {
+        return true;
+    }
+    let shortest = have.chars().count().min(want.chars().count());
+    let allowed = allowed_distance(shortest);
+    allowed > 0 && edit_distance(have, want) <= allowed
+}
+
+/// Normalized words of `text`, regional names left as they are.
+pub(crate) fn split_words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(normalize)
+        .collect()
+}
+
+fn words(text: &str) -> Vec<String> {
+    synonyms::canonical_words(split_words(text))
+}
+
+/// True if `ingredient` (from a recipe) is what the user meant by `wanted`:
+/// every word of `wanted` has to match some word of `ingredient`.
+pub fn ingredient_matches(ingredient: &str, wanted: &str) -> bool {
+    let have = words(ingredient);
+    let want = words(wanted);
+    !want.is_empty() && want.iter().all(|w| have.iter().any(|h| words_match(h, w)))
+}
+
+// Whole-ingredient key for set comparisons: "Chopped Tomatoes" and
+// "chopped tomato" are the same thing
+fn ingredient_key(ingredient: &str) -> String {
+    words(ingredient).join(" ")
+}
+
+/// Jaccard similarity of two ingredient lists: ingredients in common over
+/// distinct ingredients in either, from 0.0 to 1.0.
+pub fn jaccard(a: &[String], b: &[String]) -> f64 {
+    let a: HashSet<String> = a.iter().map(|i| ingredient_key(i)).collect();
+    let b: HashSet<String> = b.iter().map(|i| ingredient_key(i)).collect();
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f64 / union as f64
+}
+
+/// Up to `limit` recipes from `pool` sharing ingredients with `meal`, most
+/// similar first. `meal` itself is skipped.
+pub fn most_similar<'a>(
+    meal: &MealDetail,
+    pool: impl IntoIterator<Item = &'a MealDetail>,
+    limit: usize,
+) -> Vec<(&'a MealDetail, f64)> {
+    let mut similar: Vec<(&MealDetail, f64)> = pool
+        .into_iter()
+        .filter(|other| other.id != meal.id)
+        .map(|other| (other, jaccard(&meal.ingredients, &other.ingredients)))
+        .filter(|(_, similarity)| *similarity > 0.0)
+        .collect();
+    similar.sort_by(|a, b| b.1.total_cmp(&a.1));
+    similar.truncate(limit);
+    similar
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_plurals() {
+        assert_eq!(normalize("Tomatoes"), "tomato");
+        assert_eq!(normalize("berries"), "berry");
+        assert_eq!(normalize("peaches"), "peach");
+        assert_eq!(normalize("eggs"), "egg");
+        assert_eq!(normalize("glass"), "glass");
+        assert_eq!(normalize("gas"), "gas");
+    }
+
+    #[test]
+    fn tolerates_spelling_and_plurals() {
+        assert!(ingredient_matches("Chopped Tomatoes", "tomato"));
+        assert!(ingredient_matches("red chilli", "chili"));
+        assert!(ingredient_matches("Black Peppercorns", "pepper"));
+    }
+
+    #[test]
+    fn short_words_must_match_exactly() {
+        assert!(!ingredient_matches("Rice", "ice"));
+        assert!(!ingredient_matches("Ham", "yam"));
+    }
+
+    #[test]
+    fn every_wanted_word_has_to_match() {
+        assert!(ingredient_matches("Chicken Breasts", "chicken breast"));
+        assert!(!ingredient_matches("Chicken Thighs", "chicken breast"));
+        assert!(!ingredient_matches("Chicken", ""));
+    }
+
+    fn meal(id: &str, ingredients: &[&str]) -> MealDetail {
+        MealDetail {
+            id: id.to_string(),
+            ingredients: ingredients.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn jaccard_compares_normalized_ingredient_sets() {
+        let curry = meal("1", &["Chicken", "Onions", "Garam Masala"]).ingredients;
+        let korma = meal("2", &["chicken", "onion", "Cream"]).ingredients;
+        assert_eq!(jaccard(&curry, &korma), 2.0 / 4.0);
+        assert_eq!(jaccard(&curry, &curry), 1.0);
+        assert_eq!(jaccard(&[], &[]), 0.0);
+    }
+
+    #[test]
+    fn most_similar_ranks_and_skips_the_recipe_itself() {
+        let curry = meal("1", &["Chicken", "Onions", "Garam Masala"]);
+        let pool = [
+            curry.clone(),
+            meal("2", &["Chicken", "Cream"]),
+            meal("3", &["Chicken", "Onion", "Garam masala", "Rice"]),
+            meal("4", &["Flour", "Sugar"]),
+        ];
+        let ids: Vec<&str> = most_similar(&curry, &pool, 5).iter().map(|(m, _)| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["3", "2"]);
+        assert_eq!(most_similar(&curry, &pool, 1).len(), 1);
+    }
+}
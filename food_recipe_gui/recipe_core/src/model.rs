@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+/// A recipe as every frontend sees it, whichever provider it came from.
+/// Stored as JSON for favorites and the user's own recipes.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+pub struct MealDetail {
+    pub id: String,
+    pub title: String,
+    pub category: String,
+    pub area: String,
+    pub instructions: String,
+    pub ingredients: Vec<String>,
+    /// Free-text measure for each entry of `ingredients`, same order.
+    #[serde(default)]
+    pub measures: Vec<String>,
+    #[serde(default)]
+    pub servings: Option<u32>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Video walkthrough, empty if none.
+    #[serde(default)]
+    pub youtube: String,
+    /// Original publisher of the recipe, empty if unknown.
+    #[serde(default)]
+    pub source: String,
+    pub thumb: String,
+    pub score: i32,
+}
@@ -0,0 +1,188 @@
+use super::http::{get_json, FetchResult};
+use super::rate_limit::RateLimiter;
+use super::RecipeProvider;
+use crate::MealDetail;
+use async_trait::async_trait;
+use reqwest::{Client, RequestBuilder};
+use serde::Deserialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+pub const API_BASE: &str = "https://api.edamam.com/api/recipes/v2";
+const ID_PREFIX: &str = "edamam-";
+// Edamam has no random endpoint; a broad query with `random=true` stands in
+const RANDOM_QUERY: &str = "dinner";
+
+#[derive(Debug, Deserialize)]
+struct Ingredient {
+    food: String,
+    weight: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Recipe {
+    uri: String,
+    label: String,
+    image: Option<String>,
+    url: Option<String>,
+    #[serde(rename = "yield")]
+    servings: Option<f64>,
+    #[serde(default)]
+    cuisine_type: Vec<String>,
+    #[serde(default)]
+    dish_type: Vec<String>,
+    #[serde(default)]
+    diet_labels: Vec<String>,
+    #[serde(default)]
+    health_labels: Vec<String>,
+    #[serde(default)]
+    ingredient_lines: Vec<String>,
+    #[serde(default)]
+    ingredients: Vec<Ingredient>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Hit {
+    recipe: Recipe,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    #[serde(default)]
+    hits: Vec<Hit>,
+}
+
+// Recipe URIs look like "http://www.edamam.com/ontologies/edamam.owl#recipe_<id>"
+fn recipe_id(uri: &str) -> &str {
+    uri.rsplit("#recipe_").next().unwrap_or(uri)
+}
+
+impl From<Recipe> for MealDetail {
+    fn from(recipe: Recipe) -> Self {
+        // Edamam only links to the publisher, so the ingredient lines are the best we can show
+        let mut instructions = recipe.ingredient_lines.join("\n");
+        if let Some(url) = &recipe.url {
+            instructions.push_str(&format!("\n\nFull instructions: {}", url));
+        }
+        // Edamam lists a food once per ingredient line; merge them and sum the weights
+        let mut merged: Vec<(String, f64)> = Vec::new();
+        for ing in recipe.ingredients {
+            let weight = ing.weight.unwrap_or(0.0);
+            match merged.iter_mut().find(|(food, _)| food.eq_ignore_ascii_case(&ing.food)) {
+                Some((_, total)) => *total += weight,
+                None => merged.push((ing.food, weight)),
+            }
+        }
+        let measures = merged.iter().map(|(_, w)| format!("{:.0}g", w)).collect();
+        let ingredients = merged.into_iter().map(|(food, _)| food).collect();
+        MealDetail {
+            id: format!("{}{}", ID_PREFIX, recipe_id(&recipe.uri)),
+            title: recipe.label,
+            category: recipe.dish_type.join(", "),
+            area: recipe.cuisine_type.join(", "),
+            instructions,
+            ingredients,
+            measures,
+            servings: recipe.servings.map(|s| s.round().max(1.0) as u32),
+            tags: recipe
+                .diet_labels
+                .into_iter()
+                .chain(recipe.health_labels)
+                .collect(),
+            youtube: String::new(),
+            source: recipe.url.unwrap_or_default(),
+            thumb: recipe.image.unwrap_or_default(),
+            score: 0,
+        }
+    }
+}
+
+/// Edamam recipe search. Searches already return full recipes, so hits are
+/// kept in memory and `lookup` only goes to the network for unknown IDs.
+pub struct Edamam {
+    client: Client,
+    app_id: String,
+    app_key: String,
+    health_labels: Vec<String>,
+    seen: Mutex<HashMap<String, MealDetail>>,
+    limiter: RateLimiter,
+}
+
+impl Edamam {
+    pub fn new(
+        client: Client,
+        app_id: impl Into<String>,
+        app_key: impl Into<String>,
+        health_labels: Vec<String>,
+        requests_per_second: f64,
+    ) -> Self {
+        Self {
+            client,
+            app_id: app_id.into(),
+            app_key: app_key.into(),
+            health_labels,
+            seen: Mutex::new(HashMap::new()),
+            limiter: RateLimiter::new(requests_per_second),
+        }
+    }
+
+    fn get(&self, url: &str) -> RequestBuilder {
+        let mut query = vec![
+            ("type", "public"),
+            ("app_id", self.app_id.as_str()),
+            ("app_key", self.app_key.as_str()),
+        ];
+        for label in &self.health_labels {
+            query.push(("health", label.as_str()));
+        }
+        self.client.get(url).query(&query)
+    }
+
+    async fn search(&self, query: &[(&str, &str)]) -> FetchResult<Vec<MealDetail>> {
+        let found: SearchResponse = get_json(&self.limiter, self.get(API_BASE).query(query)).await?;
+        let meals: Vec<MealDetail> = found.hits.into_iter().map(|h| h.recipe.into()).collect();
+        let mut seen = self.seen.lock().unwrap();
+        for meal in &meals {
+            seen.insert(meal.id.clone(), meal.clone());
+        }
+        Ok(meals)
+    }
+}
+
+#[async_trait]
+impl RecipeProvider for Edamam {
+    fn name(&self) -> &str {
+        "Edamam"
+    }
+
+    async fn search_by_ingredients(&self, ingredients: &[String]) -> FetchResult<HashSet<String>> {
+        let mut ids = HashSet::new();
+        for ing in ingredients {
+            for meal in self.search(&[("q", ing.as_str())]).await? {
+                ids.insert(meal.id);
+            }
+        }
+        Ok(ids)
+    }
+
+    async fn lookup(&self, id: &str) -> FetchResult<Option<MealDetail>> {
+        if let Some(meal) = self.seen.lock().unwrap().get(id) {
+            return Ok(Some(meal.clone()));
+        }
+        let Some(raw_id) = id.strip_prefix(ID_PREFIX) else {
+            return Ok(None);
+        };
+        let hit: Hit = get_json(&self.limiter, self.get(&format!("{}/{}", API_BASE, raw_id))).await?;
+        Ok(Some(hit.recipe.into()))
+    }
+
+    async fn random(&self) -> FetchResult<Option<MealDetail>> {
+        let meals = self.search(&[("q", RANDOM_QUERY), ("random", "true")]).await?;
+        Ok(meals.into_iter().next())
+    }
+
+    async fn search_by_name(&self, name: &str) -> FetchResult<Vec<MealDetail>> {
+        self.search(&[("q", name)]).await
+    }
+}
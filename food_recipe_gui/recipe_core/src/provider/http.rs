@@ -0,0 +1,197 @@
+use super::rate_limit::RateLimiter;
+use reqwest::{Client, NoProxy, Proxy, Request, RequestBuilder, StatusCode, Url};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Attempts per request, including the first one.
+const MAX_ATTEMPTS: u32 = 3;
+/// Wait before the first retry; doubled after every further failure.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(300);
+/// Attempts taking at least this long are recorded even when they succeed.
+pub const SLOW_REQUEST: Duration = Duration::from_secs(2);
+
+/// One failed or slow request attempt, for the diagnostics panel.
+#[derive(Debug, Clone)]
+pub struct RequestRecord {
+    /// With API keys in the query string masked.
+    pub url: String,
+    /// `None` if the server never answered.
+    pub status: Option<StatusCode>,
+    /// `None` for an attempt that worked but was slow.
+    pub error: Option<String>,
+    pub elapsed: Duration,
+    /// 1 for the first try, 2 for the first retry and so on.
+    pub attempt: u32,
+}
+
+// Shared by every provider and drained by the frontend
+static DIAGNOSTICS: Mutex<Vec<RequestRecord>> = Mutex::new(Vec::new());
+
+// Every attempt that went out, retries included, by every provider
+static REQUESTS_SENT: AtomicU64 = AtomicU64::new(0);
+
+/// Requests sent since the program started, for the quota counter.
+pub fn requests_sent() -> u64 {
+    REQUESTS_SENT.load(Ordering::Relaxed)
+}
+
+/// Everything recorded since the last call.
+pub fn take_diagnostics() -> Vec<RequestRecord> {
+    std::mem::take(&mut *DIAGNOSTICS.lock().unwrap())
+}
+
+// Keys and secrets stay out of anything the user might copy into a bug report
+fn masked_url(url: &Url) -> String {
+    let mut url = url.clone();
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(k, v)| {
+            let secret = k.to_lowercase().contains("key");
+            (k.into_owned(), if secret { "***".to_string() } else { v.into_owned() })
+        })
+        .collect();
+    if !pairs.is_empty() {
+        url.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+    url.to_string()
+}
+
+/// Proxy for all provider requests. Without a URL, the usual `HTTP_PROXY` /
+/// `HTTPS_PROXY` / `NO_PROXY` environment variables still apply.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProxyConfig {
+    /// e.g. "http://proxy.example.com:3128"; used for both HTTP and HTTPS.
+    pub url: Option<String>,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Hosts that bypass the proxy, e.g. "localhost" or ".example.com".
+    pub no_proxy: Vec<String>,
+}
+
+/// The client shared by every provider. `timeout` covers the whole request
+/// including reading the body, so a connection that stalls halfway still
+/// gives up instead of hanging the search.
+pub fn build_client(connect_timeout: Duration, timeout: Duration, proxy: &ProxyConfig) -> reqwest::Result<Client> {
+    let mut builder = Client::builder().connect_timeout(connect_timeout).timeout(timeout);
+    if let Some(url) = proxy.url.as_deref().filter(|u| !u.trim().is_empty()) {
+        let mut p = Proxy::all(url.trim())?.no_proxy(NoProxy::from_string(&proxy.no_proxy.join(",")));
+        if let Some(username) = &proxy.username {
+            p = p.basic_auth(username, proxy.password.as_deref().unwrap_or(""));
+        }
+        builder = builder.proxy(p);
+    }
+    builder.build()
+}
+
+#[derive(Debug)]
+pub enum FetchError {
+    /// The request never got a response (DNS, connection, timeout).
+    Network(reqwest::Error),
+    /// The server answered with a non-success status.
+    Status(StatusCode),
+    /// The response body wasn't what we expected.
+    Decode(reqwest::Error),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::Network(e) => write!(f, "network error: {}", e),
+            FetchError::Status(status) => write!(f, "server returned {}", status),
+            FetchError::Decode(e) => write!(f, "unexpected response: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+pub type FetchResult<T> = Result<T, FetchError>;
+
+impl FetchError {
+    // Rate limits and server hiccups are worth another try; a 404 or bad JSON won't change
+    fn is_transient(&self) -> bool {
+        match self {
+            FetchError::Network(_) => true,
+            FetchError::Status(status) => {
+                *status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+            }
+            FetchError::Decode(_) => false,
+        }
+    }
+}
+
+async fn send<T: DeserializeOwned>(client: &Client, request: Request) -> (Option<StatusCode>, FetchResult<T>) {
+    let resp = match client.execute(request).await {
+        Ok(resp) => resp,
+        Err(e) => return (None, Err(FetchError::Network(e))),
+    };
+    let status = resp.status();
+    if !status.is_success() {
+        return (Some(status), Err(FetchError::Status(status)));
+    }
+    (Some(status), resp.json::<T>().await.map_err(FetchError::Decode))
+}
+
+// One try, recorded for the diagnostics panel if it failed or was slow
+async fn attempt<T: DeserializeOwned>(request: RequestBuilder, number: u32) -> FetchResult<T> {
+    let (client, request) = request.build_split();
+    let request = request.map_err(FetchError::Network)?;
+    let url = masked_url(request.url());
+    let started = Instant::now();
+    REQUESTS_SENT.fetch_add(1, Ordering::Relaxed);
+    let (status, result) = send(&client, request).await;
+    let elapsed = started.elapsed();
+    let millis = elapsed.as_millis() as u64;
+    match &result {
+        Ok(_) => tracing::debug!(%url, status = status.map(|s| s.as_u16()), millis, attempt = number, "request done"),
+        Err(e) => tracing::warn!(%url, status = status.map(|s| s.as_u16()), millis, attempt = number, error = %e, "request failed"),
+    }
+    if result.is_err() || elapsed >= SLOW_REQUEST {
+        DIAGNOSTICS.lock().unwrap().push(RequestRecord {
+            url,
+            status,
+            error: result.as_ref().err().map(|e| e.to_string()),
+            elapsed,
+            attempt: number,
+        });
+    }
+    result
+}
+
+/// Sends a GET built by the caller and decodes the JSON body, retrying
+/// transient failures with exponential backoff. Every attempt, retries
+/// included, waits for `limiter` first.
+pub async fn get_json<T: DeserializeOwned>(limiter: &RateLimiter, request: RequestBuilder) -> FetchResult<T> {
+    let mut backoff = INITIAL_BACKOFF;
+    let mut attempts = 1;
+    loop {
+        // Plain GETs without a streaming body can always be cloned
+        let this_try = request.try_clone().expect("GET requests are cloneable");
+        limiter.acquire().await;
+        match attempt(this_try, attempts).await {
+            Err(e) if e.is_transient() && attempts < MAX_ATTEMPTS => {
+                tracing::info!(backoff_ms = backoff.as_millis() as u64, "retrying after {}", e);
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+                attempts += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masks_keys_in_the_query() {
+        let url = Url::parse("https://api.example.com/search?q=curry&app_id=abc&app_key=secret").unwrap();
+        assert_eq!(masked_url(&url), "https://api.example.com/search?q=curry&app_id=abc&app_key=***");
+    }
+}
@@ -0,0 +1,102 @@
+pub mod edamam;
+pub mod http;
+pub mod rate_limit;
+pub mod spoonacular;
+pub mod themealdb;
+
+use crate::MealDetail;
+use async_trait::async_trait;
+use std::collections::HashSet;
+
+pub use edamam::Edamam;
+pub use http::FetchResult;
+pub use spoonacular::Spoonacular;
+pub use themealdb::TheMealDb;
+
+/// A recipe backend. Everything the GUI needs from an API goes through here,
+/// so a different service only has to implement the three required calls;
+/// the optional ones default to "not supported". Network failures are
+/// reported as errors rather than as empty results.
+#[async_trait]
+pub trait RecipeProvider: Send + Sync {
+    /// Short human readable name shown in the GUI.
+    fn name(&self) -> &str;
+
+    /// IDs of all meals using at least one of the given ingredients.
+    async fn search_by_ingredients(&self, ingredients: &[String]) -> FetchResult<HashSet<String>>;
+
+    /// IDs of all meals using every one of the given ingredients. By default
+    /// one search per ingredient, intersected here.
+    async fn search_by_all_ingredients(&self, ingredients: &[String]) -> FetchResult<HashSet<String>> {
+        intersect_searches(self, ingredients).await
+    }
+
+    /// Full details for one meal ID as returned by `search_by_ingredients`.
+    /// `Ok(None)` if the service doesn't know the ID.
+    async fn lookup(&self, id: &str) -> FetchResult<Option<MealDetail>>;
+
+    /// A single random meal.
+    async fn random(&self) -> FetchResult<Option<MealDetail>>;
+
+    /// Full details of every meal whose name contains `name`.
+    async fn search_by_name(&self, _name: &str) -> FetchResult<Vec<MealDetail>> {
+        Ok(Vec::new())
+    }
+
+    /// Full details of every meal whose name starts with `letter`.
+    async fn search_by_first_letter(&self, _letter: char) -> FetchResult<Vec<MealDetail>> {
+        Ok(Vec::new())
+    }
+
+    /// Full details of the meals added most recently. Only some services, and
+    /// only with a paid key, offer this; the rest return nothing.
+    async fn latest(&self) -> FetchResult<Vec<MealDetail>> {
+        Ok(Vec::new())
+    }
+
+    /// Category names for the search form.
+    async fn categories(&self) -> FetchResult<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    /// Area (cuisine) names for the search form.
+    async fn areas(&self) -> FetchResult<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    /// Known ingredient names, used for autocompletion.
+    async fn ingredient_names(&self) -> FetchResult<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    /// IDs of all meals in a category from `categories`.
+    async fn search_by_category(&self, _category: &str) -> FetchResult<HashSet<String>> {
+        Ok(HashSet::new())
+    }
+
+    /// IDs of all meals from an area from `areas`.
+    async fn search_by_area(&self, _area: &str) -> FetchResult<HashSet<String>> {
+        Ok(HashSet::new())
+    }
+}
+
+/// Searches each ingredient on its own and keeps the IDs found for all of
+/// them, stopping early once nothing is left in common.
+pub async fn intersect_searches<P: RecipeProvider + ?Sized>(
+    provider: &P,
+    ingredients: &[String],
+) -> FetchResult<HashSet<String>> {
+    let mut common: Option<HashSet<String>> = None;
+    for ing in ingredients {
+        let ids = provider.search_by_ingredients(std::slice::from_ref(ing)).await?;
+        let ids = match common {
+            Some(common) => common.intersection(&ids).cloned().collect(),
+            None => ids,
+        };
+        if ids.is_empty() {
+            return Ok(ids);
+        }
+        common = Some(ids);
+    }
+    Ok(common.unwrap_or_default())
+}
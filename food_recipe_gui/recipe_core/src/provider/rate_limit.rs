@@ -0,0 +1,73 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+struct Bucket {
+    /// Requests that may start right now; negative once callers are queued.
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+/// Token bucket shared by every request to one provider. Searches may run
+/// several lookups at once; this keeps the combined rate at the configured
+/// requests per second no matter how many are in flight.
+pub struct RateLimiter {
+    per_second: f64,
+    /// Requests allowed back to back after a quiet spell.
+    burst: f64,
+    bucket: Mutex<Bucket>,
+}
+
+impl RateLimiter {
+    pub fn new(per_second: f64) -> Self {
+        let per_second = per_second.max(0.1);
+        let burst = per_second.ceil().max(1.0);
+        Self {
+            per_second,
+            burst,
+            bucket: Mutex::new(Bucket {
+                tokens: burst,
+                refilled_at: Instant::now(),
+            }),
+        }
+    }
+
+    // Takes a token now, possibly going into debt, and returns how long the
+    // caller has to wait for it. Debt keeps waiting callers in arrival order.
+    fn reserve(&self) -> Duration {
+        let mut bucket = self.bucket.lock().unwrap();
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.per_second).min(self.burst);
+        bucket.refilled_at = now;
+        bucket.tokens -= 1.0;
+        if bucket.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-bucket.tokens / self.per_second)
+        }
+    }
+
+    /// Waits until another request may be sent.
+    pub async fn acquire(&self) {
+        let wait = self.reserve();
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allows_a_burst_then_spaces_requests_out() {
+        let limiter = RateLimiter::new(2.0);
+        assert_eq!(limiter.reserve(), Duration::ZERO);
+        assert_eq!(limiter.reserve(), Duration::ZERO);
+        let third = limiter.reserve();
+        let fourth = limiter.reserve();
+        assert!(third > Duration::from_millis(400) && third <= Duration::from_millis(500), "{:?}", third);
+        assert!(fourth > Duration::from_millis(900) && fourth <= Duration::from_secs(1), "{:?}", fourth);
+    }
+}
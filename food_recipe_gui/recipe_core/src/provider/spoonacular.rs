@@ -0,0 +1,175 @@
+use super::http::{get_json, FetchResult};
+use super::rate_limit::RateLimiter;
+use super::RecipeProvider;
+use crate::MealDetail;
+use async_trait::async_trait;
+use reqwest::{Client, RequestBuilder};
+use serde::Deserialize;
+use std::collections::HashSet;
+
+pub const API_BASE: &str = "https://api.spoonacular.com";
+const ID_PREFIX: &str = "spoonacular-";
+const MAX_RESULTS: &str = "30";
+
+#[derive(Debug, Deserialize)]
+struct FoundRecipe {
+    id: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExtendedIngredient {
+    name: String,
+    amount: Option<f64>,
+    unit: Option<String>,
+}
+
+impl ExtendedIngredient {
+    fn measure(&self) -> String {
+        match self.amount {
+            Some(amount) => format!("{} {}", amount, self.unit.as_deref().unwrap_or(""))
+                .trim()
+                .to_string(),
+            None => String::new(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RecipeInformation {
+    id: u64,
+    title: String,
+    image: Option<String>,
+    instructions: Option<String>,
+    source_url: Option<String>,
+    servings: Option<u32>,
+    #[serde(default)]
+    cuisines: Vec<String>,
+    #[serde(default)]
+    dish_types: Vec<String>,
+    #[serde(default)]
+    diets: Vec<String>,
+    #[serde(default)]
+    extended_ingredients: Vec<ExtendedIngredient>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RandomRecipes {
+    recipes: Vec<RecipeInformation>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ComplexSearch {
+    #[serde(default)]
+    results: Vec<RecipeInformation>,
+}
+
+// Spoonacular instructions frequently arrive as HTML lists
+fn strip_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                out.push(' ');
+            }
+            _ if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+impl From<RecipeInformation> for MealDetail {
+    fn from(info: RecipeInformation) -> Self {
+        MealDetail {
+            id: format!("{}{}", ID_PREFIX, info.id),
+            title: info.title,
+            category: info.dish_types.join(", "),
+            area: info.cuisines.join(", "),
+            instructions: strip_html(&info.instructions.unwrap_or_default()),
+            measures: info.extended_ingredients.iter().map(|i| i.measure()).collect(),
+            ingredients: info.extended_ingredients.into_iter().map(|i| i.name).collect(),
+            servings: info.servings,
+            tags: info.diets,
+            youtube: String::new(),
+            source: info.source_url.unwrap_or_default(),
+            thumb: info.image.unwrap_or_default(),
+            score: 0,
+        }
+    }
+}
+
+/// Spoonacular backend. Needs an API key; IDs are prefixed so they never
+/// collide with TheMealDB IDs in favorites.
+pub struct Spoonacular {
+    client: Client,
+    api_key: String,
+    limiter: RateLimiter,
+}
+
+impl Spoonacular {
+    pub fn new(client: Client, api_key: impl Into<String>, requests_per_second: f64) -> Self {
+        Self {
+            client,
+            api_key: api_key.into(),
+            limiter: RateLimiter::new(requests_per_second),
+        }
+    }
+
+    fn get(&self, path: &str) -> RequestBuilder {
+        self.client
+            .get(format!("{}{}", API_BASE, path))
+            .header("x-api-key", &self.api_key)
+    }
+}
+
+#[async_trait]
+impl RecipeProvider for Spoonacular {
+    fn name(&self) -> &str {
+        "Spoonacular"
+    }
+
+    async fn search_by_ingredients(&self, ingredients: &[String]) -> FetchResult<HashSet<String>> {
+        if ingredients.is_empty() {
+            return Ok(HashSet::new());
+        }
+        let joined = ingredients.join(",");
+        let req = self.get("/recipes/findByIngredients").query(&[
+            ("ingredients", joined.as_str()),
+            ("number", MAX_RESULTS),
+            ("ranking", "1"),
+        ]);
+        let found: Vec<FoundRecipe> = get_json(&self.limiter, req).await?;
+        Ok(found
+            .into_iter()
+            .map(|r| format!("{}{}", ID_PREFIX, r.id))
+            .collect())
+    }
+
+    async fn lookup(&self, id: &str) -> FetchResult<Option<MealDetail>> {
+        let Some(raw_id) = id.strip_prefix(ID_PREFIX) else {
+            return Ok(None);
+        };
+        let info: RecipeInformation = get_json(&self.limiter, self.get(&format!("/recipes/{}/information", raw_id))).await?;
+        Ok(Some(info.into()))
+    }
+
+    async fn random(&self) -> FetchResult<Option<MealDetail>> {
+        let random: RandomRecipes = get_json(&self.limiter, self.get("/recipes/random").query(&[("number", "1")])).await?;
+        Ok(random.recipes.into_iter().next().map(Into::into))
+    }
+
+    async fn search_by_name(&self, name: &str) -> FetchResult<Vec<MealDetail>> {
+        let req = self.get("/recipes/complexSearch").query(&[
+            ("query", name),
+            ("number", MAX_RESULTS),
+            ("addRecipeInformation", "true"),
+            ("fillIngredients", "true"),
+        ]);
+        let found: ComplexSearch = get_json(&self.limiter, req).await?;
+        Ok(found.results.into_iter().map(Into::into).collect())
+    }
+}
@@ -0,0 +1,807 @@
+use super::http::{get_json, FetchError, FetchResult};
+use super::rate_limit::RateLimiter;
+use super::{intersect_searches, RecipeProvider};
+use crate::cache::MealCache;
+use crate::MealDetail;
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::de::{self, DeserializeOwned, IgnoredAny, MapAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+pub const API_ROOT: &str = "https://www.themealdb.com/api/json/v1";
+/// TheMealDB's public key for development and education.
+pub const TEST_API_KEY: &str = "1";
+const INGREDIENT_IMAGE_ROOT: &str = "https://www.themealdb.com/images/ingredients";
+
+/// Small picture of an ingredient. TheMealDB names them after its own
+/// ingredient list, so other names may have no image.
+pub fn ingredient_image_url(ingredient: &str) -> String {
+    format!("{}/{}-Small.png", INGREDIENT_IMAGE_ROOT, ingredient.trim().replace(' ', "%20"))
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct MealShort {
+    id_meal: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+struct MealsList {
+    meals: Option<Vec<MealShort>>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct ListEntry {
+    str_category: Option<String>,
+    str_area: Option<String>,
+    str_ingredient: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+struct ListResponse {
+    meals: Option<Vec<ListEntry>>,
+}
+
+/// Ingredient/measure slots in a TheMealDB meal (`strIngredient1..20`).
+pub const MAX_INGREDIENTS: usize = 20;
+
+/// One meal as returned by `lookup.php`/`random.php`, and as stored in the disk cache.
+/// The numbered ingredient and measure keys get their own typed slots instead of
+/// being fished out of a catch-all map.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MealFull {
+    id_meal: String,
+    str_meal: String,
+    str_category: Option<String>,
+    str_area: Option<String>,
+    str_instructions: Option<String>,
+    str_meal_thumb: Option<String>,
+    str_tags: Option<String>,
+    str_youtube: Option<String>,
+    str_source: Option<String>,
+    /// `strIngredient1` is index 0.
+    str_ingredients: [Option<String>; MAX_INGREDIENTS],
+    /// `strMeasure1` is index 0.
+    str_measures: [Option<String>; MAX_INGREDIENTS],
+}
+
+// Every key of a meal object we care about; the rest are skipped
+enum MealField {
+    IdMeal,
+    StrMeal,
+    StrCategory,
+    StrArea,
+    StrInstructions,
+    StrMealThumb,
+    StrTags,
+    StrYoutube,
+    StrSource,
+    /// Zero-based slot.
+    Ingredient(usize),
+    Measure(usize),
+    Ignored,
+}
+
+impl MealField {
+    fn parse(key: &str) -> Self {
+        let slot = |n: &str| {
+            n.parse::<usize>()
+                .ok()
+                .filter(|n| (1..=MAX_INGREDIENTS).contains(n))
+                .map(|n| n - 1)
+        };
+        match key {
+            "idMeal" => MealField::IdMeal,
+            "strMeal" => MealField::StrMeal,
+            "strCategory" => MealField::StrCategory,
+            "strArea" => MealField::StrArea,
+            "strInstructions" => MealField::StrInstructions,
+            "strMealThumb" => MealField::StrMealThumb,
+            "strTags" => MealField::StrTags,
+            "strYoutube" => MealField::StrYoutube,
+            "strSource" => MealField::StrSource,
+            _ => {
+                if let Some(i) = key.strip_prefix("strIngredient").and_then(slot) {
+                    MealField::Ingredient(i)
+                } else if let Some(i) = key.strip_prefix("strMeasure").and_then(slot) {
+                    MealField::Measure(i)
+                } else {
+                    MealField::Ignored
+                }
+            }
+        }
+    }
+}
+
+struct MealFullVisitor;
+
+impl<'de> Visitor<'de> for MealFullVisitor {
+    type Value = MealFull;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a TheMealDB meal object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<MealFull, A::Error> {
+        let mut meal = MealFull::default();
+        let mut id_meal = None;
+        let mut str_meal = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match MealField::parse(&key) {
+                MealField::IdMeal => id_meal = Some(map.next_value()?),
+                MealField::StrMeal => str_meal = Some(map.next_value()?),
+                MealField::StrCategory => meal.str_category = map.next_value()?,
+                MealField::StrArea => meal.str_area = map.next_value()?,
+                MealField::StrInstructions => meal.str_instructions = map.next_value()?,
+                MealField::StrMealThumb => meal.str_meal_thumb = map.next_value()?,
+                MealField::StrTags => meal.str_tags = map.next_value()?,
+                MealField::StrYoutube => meal.str_youtube = map.next_value()?,
+                MealField::StrSource => meal.str_source = map.next_value()?,
+                MealField::Ingredient(i) => meal.str_ingredients[i] = map.next_value()?,
+                MealField::Measure(i) => meal.str_measures[i] = map.next_value()?,
+                MealField::Ignored => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        meal.id_meal = id_meal.ok_or_else(|| de::Error::missing_field("idMeal"))?;
+        meal.str_meal = str_meal.ok_or_else(|| de::Error::missing_field("strMeal"))?;
+        Ok(meal)
+    }
+}
+
+impl<'de> Deserialize<'de> for MealFull {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(MealFullVisitor)
+    }
+}
+
+// Written back in the API's own shape so cache files look like lookup.php output
+impl Serialize for MealFull {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(9 + 2 * MAX_INGREDIENTS))?;
+        map.serialize_entry("idMeal", &self.id_meal)?;
+        map.serialize_entry("strMeal", &self.str_meal)?;
+        map.serialize_entry("strCategory", &self.str_category)?;
+        map.serialize_entry("strArea", &self.str_area)?;
+        map.serialize_entry("strInstructions", &self.str_instructions)?;
+        map.serialize_entry("strMealThumb", &self.str_meal_thumb)?;
+        map.serialize_entry("strTags", &self.str_tags)?;
+        map.serialize_entry("strYoutube", &self.str_youtube)?;
+        map.serialize_entry("strSource", &self.str_source)?;
+        for (i, ingredient) in self.str_ingredients.iter().enumerate() {
+            map.serialize_entry(&format!("strIngredient{}", i + 1), ingredient)?;
+        }
+        for (i, measure) in self.str_measures.iter().enumerate() {
+            map.serialize_entry(&format!("strMeasure{}", i + 1), measure)?;
+        }
+        map.end()
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+struct MealsFull {
+    meals: Option<Vec<MealFull>>,
+}
+
+// Returns the ingredients with their matching measures, index for index
+fn extract_ingredients(full: &MealFull) -> (Vec<String>, Vec<String>) {
+    let mut list = Vec::new();
+    let mut measures = Vec::new();
+    for (ingredient, measure) in full.str_ingredients.iter().zip(&full.str_measures) {
+        let ing = ingredient.as_deref().unwrap_or("").trim();
+        if !ing.is_empty() {
+            list.push(ing.to_string());
+            measures.push(measure.as_deref().unwrap_or("").trim().to_string());
+        }
+    }
+    (list, measures)
+}
+
+impl From<MealFull> for MealDetail {
+    fn from(full: MealFull) -> Self {
+        let (ingredients, measures) = extract_ingredients(&full);
+        MealDetail {
+            id: full.id_meal,
+            title: full.str_meal,
+            category: full.str_category.unwrap_or_default(),
+            area: full.str_area.unwrap_or_default(),
+            instructions: full.str_instructions.unwrap_or_default(),
+            ingredients,
+            measures,
+            servings: None,
+            tags: full
+                .str_tags
+                .as_deref()
+                .unwrap_or("")
+                .split(',')
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty())
+                .collect(),
+            youtube: full.str_youtube.unwrap_or_default().trim().to_string(),
+            source: full.str_source.unwrap_or_default().trim().to_string(),
+            thumb: full.str_meal_thumb.unwrap_or_default(),
+            score: 0,
+        }
+    }
+}
+
+/// TheMealDB backend; `lookup.php` responses go through the meal cache.
+pub struct TheMealDb {
+    client: Client,
+    base_url: String,
+    disk: MealCache,
+    /// Every meal seen this session, so refined searches sharing candidates
+    /// neither hit the network nor decode the disk cache again.
+    memo: Mutex<HashMap<String, MealFull>>,
+    limiter: RateLimiter,
+    /// Paid keys may pass several ingredients to one `filter.php` call.
+    premium: bool,
+    /// Where to go instead if the server turns the key down.
+    fallback_url: Option<String>,
+    /// Set once the key was turned down; from then on everything goes to `fallback_url`.
+    key_rejected: AtomicBool,
+}
+
+impl TheMealDb {
+    pub fn new(client: Client, base_url: impl Into<String>, disk: MealCache, requests_per_second: f64) -> Self {
+        Self {
+            client,
+            base_url: base_url.into(),
+            disk,
+            memo: Mutex::new(HashMap::new()),
+            limiter: RateLimiter::new(requests_per_second),
+            premium: false,
+            fallback_url: None,
+            key_rejected: AtomicBool::new(false),
+        }
+    }
+
+    /// Marks the key in `base_url` as a paid one, unlocking the multi-ingredient
+    /// filter and the latest meals.
+    pub fn with_premium(mut self, premium: bool) -> Self {
+        self.premium = premium;
+        self
+    }
+
+    /// Public API to fall back on, for the rest of the session, if the server
+    /// answers 401 or 403 to the key in `base_url`.
+    pub fn with_fallback(mut self, url: impl Into<String>) -> Self {
+        self.fallback_url = Some(url.into());
+        self
+    }
+
+    fn key_rejected(&self) -> bool {
+        self.key_rejected.load(Ordering::Relaxed)
+    }
+
+    fn is_premium(&self) -> bool {
+        self.premium && !self.key_rejected()
+    }
+
+    fn base_url(&self) -> &str {
+        match &self.fallback_url {
+            Some(fallback) if self.key_rejected() => fallback,
+            _ => &self.base_url,
+        }
+    }
+
+    async fn get<T: DeserializeOwned>(&self, endpoint: &str, query: &[(&str, &str)]) -> FetchResult<T> {
+        let url = format!("{}/{}", self.base_url(), endpoint);
+        match get_json(&self.limiter, self.client.get(&url).query(query)).await {
+            Err(FetchError::Status(status))
+                if (status.as_u16() == 401 || status.as_u16() == 403)
+                    && self.fallback_url.is_some()
+                    && !self.key_rejected.swap(true, Ordering::Relaxed) =>
+            {
+                tracing::warn!(%status, "API key rejected, falling back to the public API");
+                let url = format!("{}/{}", self.base_url(), endpoint);
+                get_json(&self.limiter, self.client.get(&url).query(query)).await
+            }
+            result => result,
+        }
+    }
+
+    async fn fetch_list(&self, kind: &str) -> FetchResult<Vec<ListEntry>> {
+        let list: ListResponse = self.get("list.php", &[(kind, "list")]).await?;
+        Ok(list.meals.unwrap_or_default())
+    }
+
+    async fn filter_ids(&self, key: &str, value: &str) -> FetchResult<HashSet<String>> {
+        let list: MealsList = self.get("filter.php", &[(key, value)]).await?;
+        Ok(list.meals.unwrap_or_default().into_iter().map(|m| m.id_meal).collect())
+    }
+
+    async fn fetch_meals(&self, endpoint: &str, query: &[(&str, &str)]) -> FetchResult<Vec<MealFull>> {
+        let list: MealsFull = self.get(endpoint, query).await?;
+        Ok(list.meals.unwrap_or_default())
+    }
+
+    // search.php and latest.php return full records, so later lookups can come from the cache
+    async fn cached_meals(&self, endpoint: &str, query: &[(&str, &str)]) -> FetchResult<Vec<MealDetail>> {
+        let meals = self.fetch_meals(endpoint, query).await?;
+        for meal in &meals {
+            if let Err(e) = self.disk.put(&meal.id_meal, meal) {
+                tracing::warn!(id = meal.id_meal.as_str(), "failed to cache meal: {}", e);
+            }
+            self.remember(meal);
+        }
+        Ok(meals.into_iter().map(Into::into).collect())
+    }
+
+    fn remember(&self, meal: &MealFull) {
+        self.memo.lock().unwrap().insert(meal.id_meal.clone(), meal.clone());
+    }
+}
+
+#[async_trait]
+impl RecipeProvider for TheMealDb {
+    fn name(&self) -> &str {
+        "TheMealDB"
+    }
+
+    async fn search_by_ingredients(&self, ingredients: &[String]) -> FetchResult<HashSet<String>> {
+        let mut ids = HashSet::new();
+        for ing in ingredients {
+            ids.extend(self.filter_ids("i", ing).await?);
+        }
+        Ok(ids)
+    }
+
+    async fn search_by_all_ingredients(&self, ingredients: &[String]) -> FetchResult<HashSet<String>> {
+        if self.is_premium() && ingredients.len() > 1 {
+            let ids = self.filter_ids("i", &ingredients.join(",")).await?;
+            // If the key was turned down just now, that answer came from the
+            // public API, which can't combine ingredients
+            if !self.key_rejected() {
+                return Ok(ids);
+            }
+        }
+        intersect_searches(self, ingredients).await
+    }
+
+    async fn lookup(&self, id: &str) -> FetchResult<Option<MealDetail>> {
+        if let Some(meal) = self.memo.lock().unwrap().get(id) {
+            tracing::trace!(id, "meal from memo");
+            return Ok(Some(meal.clone().into()));
+        }
+        if let Some(meal) = self.disk.get(id) {
+            tracing::trace!(id, "meal from disk cache");
+            self.remember(&meal);
+            return Ok(Some(meal.into()));
+        }
+        let Some(meal) = self.fetch_meals("lookup.php", &[("i", id)]).await?.into_iter().next() else {
+            return Ok(None);
+        };
+        if let Err(e) = self.disk.put(id, &meal) {
+            tracing::warn!(id, "failed to cache meal: {}", e);
+        }
+        self.remember(&meal);
+        Ok(Some(meal.into()))
+    }
+
+    async fn random(&self) -> FetchResult<Option<MealDetail>> {
+        let meals = self.fetch_meals("random.php", &[]).await?;
+        Ok(meals.into_iter().next().map(Into::into))
+    }
+
+    async fn search_by_name(&self, name: &str) -> FetchResult<Vec<MealDetail>> {
+        self.cached_meals("search.php", &[("s", name)]).await
+    }
+
+    async fn search_by_first_letter(&self, letter: char) -> FetchResult<Vec<MealDetail>> {
+        self.cached_meals("search.php", &[("f", &letter.to_string())]).await
+    }
+
+    async fn latest(&self) -> FetchResult<Vec<MealDetail>> {
+        if !self.is_premium() {
+            return Ok(Vec::new());
+        }
+        let meals = self.cached_meals("latest.php", &[]).await;
+        // Only paid keys get the latest meals
+        if self.key_rejected() {
+            return Ok(Vec::new());
+        }
+        meals
+    }
+
+    async fn categories(&self) -> FetchResult<Vec<String>> {
+        let list = self.fetch_list("c").await?;
+        Ok(list.into_iter().filter_map(|e| e.str_category).collect())
+    }
+
+    async fn areas(&self) -> FetchResult<Vec<String>> {
+        let list = self.fetch_list("a").await?;
+        Ok(list.into_iter().filter_map(|e| e.str_area).collect())
+    }
+
+    async fn ingredient_names(&self) -> FetchResult<Vec<String>> {
+        let list = self.fetch_list("i").await?;
+        Ok(list.into_iter().filter_map(|e| e.str_ingredient).collect())
+    }
+
+    async fn search_by_category(&self, category: &str) -> FetchResult<HashSet<String>> {
+        self.filter_ids("c", category).await
+    }
+
+    async fn search_by_area(&self, area: &str) -> FetchResult<HashSet<String>> {
+        self.filter_ids("a", area).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Recorded from lookup.php?i=52772; blank and null slots are the API's own
+    const LOOKUP_52772: &str = include_str!("../../testdata/themealdb/lookup_52772.json");
+    const LOOKUP_NOT_FOUND: &str = include_str!("../../testdata/themealdb/lookup_not_found.json");
+
+    fn recorded_meal() -> MealFull {
+        let list: MealsFull = serde_json::from_str(LOOKUP_52772).unwrap();
+        list.meals.unwrap().remove(0)
+    }
+
+    #[test]
+    fn parses_numbered_ingredient_slots() {
+        let meal = recorded_meal();
+        assert_eq!(meal.id_meal, "52772");
+        assert_eq!(meal.str_ingredients[0].as_deref(), Some("soy sauce"));
+        assert_eq!(meal.str_measures[0].as_deref(), Some("3/4 cup"));
+        assert_eq!(meal.str_ingredients[8].as_deref(), Some("brown rice"));
+        assert_eq!(meal.str_ingredients[9].as_deref(), Some(""));
+        assert_eq!(meal.str_ingredients[19], None);
+        assert_eq!(meal.str_source, None);
+    }
+
+    #[test]
+    fn extracts_non_blank_ingredients_with_aligned_measures() {
+        let (ingredients, measures) = extract_ingredients(&recorded_meal());
+        assert_eq!(ingredients.len(), 9);
+        assert_eq!(measures.len(), 9);
+        assert_eq!(ingredients[6], "chicken breasts");
+        assert_eq!(measures[6], "2");
+        assert_eq!(measures[7], "1 (12 oz.)");
+    }
+
+    #[test]
+    fn converts_to_meal_detail() {
+        let detail: MealDetail = recorded_meal().into();
+        assert_eq!(detail.title, "Teriyaki Chicken Casserole");
+        assert_eq!(detail.category, "Chicken");
+        assert_eq!(detail.area, "Japanese");
+        assert_eq!(detail.tags, vec!["Meat", "Casserole"]);
+        assert_eq!(detail.youtube, "https://www.youtube.com/watch?v=4aZr5hZXP_s");
+        assert_eq!(detail.source, "");
+        assert_eq!(detail.ingredients.first().map(String::as_str), Some("soy sauce"));
+    }
+
+    #[test]
+    fn not_found_is_null_meals() {
+        let list: MealsFull = serde_json::from_str(LOOKUP_NOT_FOUND).unwrap();
+        assert!(list.meals.is_none());
+    }
+
+    #[test]
+    fn cache_round_trip_keeps_every_field() {
+        let meal = recorded_meal();
+        let json = serde_json::to_string(&meal).unwrap();
+        assert!(json.contains("\"strIngredient20\":null"));
+        let back: MealFull = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, meal);
+    }
+
+    #[test]
+    fn ignores_out_of_range_and_unknown_keys() {
+        let json = r#"{"idMeal":"1","strMeal":"Toast","strIngredient1":"Bread",
+            "strIngredient21":"Jam","strIngredient0":"Butter","strMeasureX":"1","dateModified":null}"#;
+        let meal: MealFull = serde_json::from_str(json).unwrap();
+        let (ingredients, measures) = extract_ingredients(&meal);
+        assert_eq!(ingredients, vec!["Bread"]);
+        assert_eq!(measures, vec![""]);
+    }
+
+    #[test]
+    fn ingredient_image_urls_escape_spaces() {
+        assert_eq!(
+            ingredient_image_url(" Chicken Breast "),
+            "https://www.themealdb.com/images/ingredients/Chicken%20Breast-Small.png"
+        );
+    }
+
+    #[test]
+    fn requires_id_and_name() {
+        assert!(serde_json::from_str::<MealFull>(r#"{"strMeal":"Toast"}"#).is_err());
+        assert!(serde_json::from_str::<MealFull>(r#"{"idMeal":"1"}"#).is_err());
+    }
+}
+
+// The provider against a local mock server replaying recorded responses
+#[cfg(test)]
+mod server_tests {
+    use super::*;
+    use crate::cache::{MealCache, DEFAULT_TTL};
+    use crate::db::Database;
+    use crate::provider::http::FetchError;
+    use std::sync::Arc;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const FILTER_CHICKEN_BREAST: &str = include_str!("../../testdata/themealdb/filter_chicken_breast.json");
+    const SEARCH_ARRABIATA: &str = include_str!("../../testdata/themealdb/search_arrabiata.json");
+    const SEARCH_NO_RESULTS: &str = include_str!("../../testdata/themealdb/search_no_results.json");
+    const LOOKUP_52772: &str = include_str!("../../testdata/themealdb/lookup_52772.json");
+    const LOOKUP_NOT_FOUND: &str = include_str!("../../testdata/themealdb/lookup_not_found.json");
+    const LOOKUP_TRUNCATED: &str = include_str!("../../testdata/themealdb/lookup_truncated.json");
+
+    fn provider(server: &MockServer) -> TheMealDb {
+        let db = Arc::new(Database::in_memory().unwrap());
+        TheMealDb::new(Client::new(), server.uri(), MealCache::new(db, DEFAULT_TTL), 100.0)
+    }
+
+    fn json(body: &str) -> ResponseTemplate {
+        ResponseTemplate::new(200).set_body_raw(body, "application/json")
+    }
+
+    #[tokio::test]
+    async fn ingredient_search_collects_filter_ids() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/filter.php"))
+            .and(query_param("i", "chicken_breast"))
+            .respond_with(json(FILTER_CHICKEN_BREAST))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let ids = provider(&server)
+            .search_by_ingredients(&["chicken_breast".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(ids.len(), 4);
+        assert!(ids.contains("52772"));
+    }
+
+    #[tokio::test]
+    async fn all_ingredients_intersects_single_filters_on_the_test_key() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/filter.php"))
+            .and(query_param("i", "chicken_breast"))
+            .respond_with(json(FILTER_CHICKEN_BREAST))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/filter.php"))
+            .and(query_param("i", "brown_rice"))
+            .respond_with(json(r#"{"meals":[{"idMeal":"52772"},{"idMeal":"52775"}]}"#))
+            .mount(&server)
+            .await;
+
+        let ids = provider(&server)
+            .search_by_all_ingredients(&["chicken_breast".to_string(), "brown_rice".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(ids, HashSet::from(["52772".to_string()]));
+    }
+
+    #[tokio::test]
+    async fn all_ingredients_stops_once_nothing_is_shared() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/filter.php"))
+            .and(query_param("i", "zzz"))
+            .respond_with(json(SEARCH_NO_RESULTS))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/filter.php"))
+            .and(query_param("i", "chicken_breast"))
+            .respond_with(json(FILTER_CHICKEN_BREAST))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let ids = provider(&server)
+            .search_by_all_ingredients(&["zzz".to_string(), "chicken_breast".to_string()])
+            .await
+            .unwrap();
+        assert!(ids.is_empty());
+    }
+
+    #[tokio::test]
+    async fn premium_key_filters_all_ingredients_in_one_request() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/filter.php"))
+            .and(query_param("i", "chicken_breast,brown_rice"))
+            .respond_with(json(r#"{"meals":[{"idMeal":"52772"}]}"#))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let ids = provider(&server)
+            .with_premium(true)
+            .search_by_all_ingredients(&["chicken_breast".to_string(), "brown_rice".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(ids, HashSet::from(["52772".to_string()]));
+    }
+
+    #[tokio::test]
+    async fn rejected_key_falls_back_to_the_public_api() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v2/secret/filter.php"))
+            .respond_with(ResponseTemplate::new(403))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/1/filter.php"))
+            .and(query_param("i", "chicken_breast"))
+            .respond_with(json(r#"{"meals":[{"idMeal":"52772"}]}"#))
+            .mount(&server)
+            .await;
+
+        let db = Arc::new(Database::in_memory().unwrap());
+        let provider = TheMealDb::new(
+            Client::new(),
+            format!("{}/v2/secret", server.uri()),
+            MealCache::new(db, DEFAULT_TTL),
+            100.0,
+        )
+        .with_premium(true)
+        .with_fallback(format!("{}/v1/1", server.uri()));
+        for _ in 0..2 {
+            let ids = provider.search_by_ingredients(&["chicken_breast".to_string()]).await.unwrap();
+            assert_eq!(ids, HashSet::from(["52772".to_string()]));
+        }
+        assert!(provider.latest().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn latest_needs_a_premium_key() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/latest.php"))
+            .respond_with(json(r#"{"meals":[{"idMeal":"52772","strMeal":"Teriyaki Chicken Casserole"}]}"#))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        assert!(provider(&server).latest().await.unwrap().is_empty());
+        let meals = provider(&server).with_premium(true).latest().await.unwrap();
+        assert_eq!(meals.len(), 1);
+        assert_eq!(meals[0].title, "Teriyaki Chicken Casserole");
+    }
+
+    #[tokio::test]
+    async fn name_search_returns_full_meals() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/search.php"))
+            .and(query_param("s", "Arrabiata"))
+            .respond_with(json(SEARCH_ARRABIATA))
+            .mount(&server)
+            .await;
+
+        let meals = provider(&server).search_by_name("Arrabiata").await.unwrap();
+        assert_eq!(meals.len(), 1);
+        assert_eq!(meals[0].title, "Spicy Arrabiata Penne");
+        assert_eq!(meals[0].ingredients.len(), 8);
+        assert_eq!(meals[0].measures[3], "1 tin");
+    }
+
+    #[tokio::test]
+    async fn empty_results_are_not_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/search.php"))
+            .respond_with(json(SEARCH_NO_RESULTS))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/filter.php"))
+            .respond_with(json(SEARCH_NO_RESULTS))
+            .mount(&server)
+            .await;
+
+        let mealdb = provider(&server);
+        assert!(mealdb.search_by_name("zzz").await.unwrap().is_empty());
+        assert!(mealdb.search_by_ingredients(&["zzz".to_string()]).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn lookup_is_served_from_cache_the_second_time() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/lookup.php"))
+            .and(query_param("i", "52772"))
+            .respond_with(json(LOOKUP_52772))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mealdb = provider(&server);
+        let first = mealdb.lookup("52772").await.unwrap().unwrap();
+        assert_eq!(first.title, "Teriyaki Chicken Casserole");
+        let second = mealdb.lookup("52772").await.unwrap().unwrap();
+        assert_eq!(second.ingredients, first.ingredients);
+    }
+
+    #[tokio::test]
+    async fn lookup_is_memoized_ahead_of_the_disk_cache() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/lookup.php"))
+            .and(query_param("i", "52772"))
+            .respond_with(json(LOOKUP_52772))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let db = Arc::new(Database::in_memory().unwrap());
+        let mealdb = TheMealDb::new(Client::new(), server.uri(), MealCache::new(Arc::clone(&db), DEFAULT_TTL), 100.0);
+        let first = mealdb.lookup("52772").await.unwrap().unwrap();
+        // Another writer changing the cache behind our back isn't seen this session
+        let mut changed: MealsFull = serde_json::from_str(LOOKUP_52772).unwrap();
+        let mut changed = changed.meals.take().unwrap().remove(0);
+        changed.str_meal = "Changed".to_string();
+        db.cache_meal("52772", &changed).unwrap();
+        let again = mealdb.lookup("52772").await.unwrap().unwrap();
+        assert_eq!(again.title, first.title);
+    }
+
+    #[tokio::test]
+    async fn unknown_id_is_none() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/lookup.php"))
+            .respond_with(json(LOOKUP_NOT_FOUND))
+            .mount(&server)
+            .await;
+
+        assert!(provider(&server).lookup("1").await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn malformed_json_is_a_decode_error_and_not_retried() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/lookup.php"))
+            .respond_with(json(LOOKUP_TRUNCATED))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let err = provider(&server).lookup("52772").await.unwrap_err();
+        assert!(matches!(err, FetchError::Decode(_)), "got {:?}", err);
+    }
+
+    #[tokio::test]
+    async fn server_errors_are_retried() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/random.php"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/random.php"))
+            .respond_with(json(LOOKUP_52772))
+            .mount(&server)
+            .await;
+
+        let meal = provider(&server).random().await.unwrap().unwrap();
+        assert_eq!(meal.id, "52772");
+    }
+}
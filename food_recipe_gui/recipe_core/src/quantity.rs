@@ -0,0 +1,308 @@
+// Amounts in the free-text measures providers hand out ("1 1/2 cups", "200g",
+// "½ tsp", "1-2 tbsp", "2 cloves, crushed"), split into a number and a unit so they can
+// be scaled, converted, priced and added together.
+
+use crate::matching::normalize;
+use std::fmt;
+
+fn unicode_fraction(c: char) -> Option<f64> {
+    match c {
+        '½' => Some(0.5),
+        '⅓' => Some(1.0 / 3.0),
+        '⅔' => Some(2.0 / 3.0),
+        '¼' => Some(0.25),
+        '¾' => Some(0.75),
+        '⅛' => Some(0.125),
+        _ => None,
+    }
+}
+
+// Anything that can be part of the amount: digits, "1/2", "½", and the
+// spaces in "1 1/2"
+fn is_amount_char(c: char) -> bool {
+    c.is_ascii_digit() || c == '.' || c == '/' || unicode_fraction(c).is_some()
+}
+
+fn parse_number_token(tok: &str) -> Option<f64> {
+    if let Some((a, b)) = tok.split_once('/') {
+        let a: f64 = a.parse().ok()?;
+        let b: f64 = b.parse().ok()?;
+        return if b == 0.0 { None } else { Some(a / b) };
+    }
+    if let Some(last) = tok.chars().last() {
+        if let Some(frac) = unicode_fraction(last) {
+            let head = &tok[..tok.len() - last.len_utf8()];
+            let whole = if head.is_empty() { 0.0 } else { head.parse::<f64>().ok()? };
+            return Some(whole + frac);
+        }
+    }
+    tok.parse().ok()
+}
+
+/// Whole numbers stay whole; common fractions print as "1½" rather than "1.5".
+pub fn format_amount(amount: f64) -> String {
+    let whole = amount.trunc();
+    let frac = amount - whole;
+    let symbol = [(0.125, "⅛"), (0.25, "¼"), (1.0 / 3.0, "⅓"), (0.5, "½"), (2.0 / 3.0, "⅔"), (0.75, "¾")]
+        .into_iter()
+        .find(|(value, _)| (frac - value).abs() < 0.02)
+        .map(|(_, symbol)| symbol);
+    match symbol {
+        _ if frac < 0.02 => format!("{}", whole),
+        _ if frac > 0.98 => format!("{}", whole + 1.0),
+        Some(symbol) if whole == 0.0 => symbol.to_string(),
+        Some(symbol) => format!("{}{}", whole, symbol),
+        None => format!("{:.2}", amount).trim_end_matches('0').to_string(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dimension {
+    Mass,
+    Volume,
+}
+
+/// A unit that converts exactly into the others of its dimension.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UnitSize {
+    pub dimension: Dimension,
+    /// Grams or millilitres in one of the unit.
+    pub base: f64,
+    /// Metric rather than imperial or US customary.
+    pub metric: bool,
+}
+
+/// The size of `unit` ("g", "Cups", "oz."), if it has an exact one. Spoons
+/// aren't included, being the same everywhere; pinches, cloves and tins vary.
+pub fn unit_size(unit: &str) -> Option<UnitSize> {
+    use Dimension::*;
+    let (dimension, base, metric) = match unit.trim_end_matches('.').to_lowercase().as_str() {
+        "g" | "gr" | "gram" | "grams" => (Mass, 1.0, true),
+        "kg" | "kilo" | "kilogram" | "kilograms" => (Mass, 1000.0, true),
+        "ml" | "millilitre" | "milliliter" | "millilitres" | "milliliters" => (Volume, 1.0, true),
+        "cl" => (Volume, 10.0, true),
+        "dl" => (Volume, 100.0, true),
+        "l" | "litre" | "liter" | "litres" | "liters" => (Volume, 1000.0, true),
+        "oz" | "ounce" | "ounces" => (Mass, 28.35, false),
+        "lb" | "lbs" | "pound" | "pounds" => (Mass, 453.6, false),
+        "cup" | "cups" => (Volume, 240.0, false),
+        "pint" | "pints" => (Volume, 473.0, false),
+        "quart" | "quarts" => (Volume, 946.0, false),
+        _ => return None,
+    };
+    Some(UnitSize { dimension, base, metric })
+}
+
+/// A measure taken apart: "1 1/2 cups" is 1.5 and "cups".
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quantity {
+    /// The leading amount; `None` for "pinch" or "to taste".
+    pub value: Option<f64>,
+    /// Where a range like "1-2 tbsp" ends; `value` is where it starts.
+    pub upper: Option<f64>,
+    /// Everything after the amount, as written: "cups", "cloves, crushed".
+    pub unit: String,
+    /// The unit follows the number without a space, as in "200g".
+    pub glued: bool,
+}
+
+impl Quantity {
+    pub fn parse(measure: &str) -> Self {
+        let measure = measure.trim();
+        let split = measure
+            .char_indices()
+            .find(|&(_, c)| !(is_amount_char(c) || c.is_whitespace()))
+            .map(|(i, _)| i)
+            .unwrap_or(measure.len());
+        let (num_part, rest) = measure.split_at(split);
+
+        let mut value: Option<f64> = None;
+        for tok in num_part.split_whitespace() {
+            match parse_number_token(tok) {
+                Some(v) => value = Some(value.unwrap_or(0.0) + v),
+                None => break,
+            }
+        }
+        // "1-2 tbsp": the unit and its spacing come after the second number
+        if let Some(tail) = rest.strip_prefix(['-', '–']).filter(|_| value.is_some()) {
+            let end = Self::parse(tail);
+            if end.value.is_some() && end.upper.is_none() {
+                return Self {
+                    value,
+                    upper: end.value,
+                    ..end
+                };
+            }
+        }
+        // Keep "200g" glued together and "2 cups" apart, as the source wrote it
+        let glued = measure.chars().find(|&c| !is_amount_char(c)).is_some_and(|c| !c.is_whitespace());
+        Self {
+            value,
+            upper: None,
+            unit: rest.trim().to_string(),
+            glued,
+        }
+    }
+
+    /// The unit itself, without what follows it: "cloves," for "cloves, crushed".
+    pub fn unit_word(&self) -> &str {
+        self.unit.split_whitespace().next().unwrap_or("")
+    }
+
+    /// What follows the unit: "crushed" for "2 cloves crushed".
+    pub fn unit_rest(&self) -> &str {
+        self.unit.split_once(char::is_whitespace).map_or("", |(_, rest)| rest.trim())
+    }
+
+    /// The unit's exact size, if it has one.
+    pub fn size(&self) -> Option<UnitSize> {
+        unit_size(self.unit_word())
+    }
+
+    /// The same measure with the amount multiplied, both ends of a range;
+    /// without an amount it is unchanged.
+    pub fn scaled(&self, factor: f64) -> Self {
+        Self {
+            value: self.value.map(|v| v * factor),
+            upper: self.upper.map(|v| v * factor),
+            ..self.clone()
+        }
+    }
+
+    /// Both quantities together, in `self`'s unit: "200g" and "300g" make
+    /// "500g", "1 kg" and "500 g" make "1½ kg", "2 cloves" and "1 clove" make
+    /// "3 cloves". `None` when they can't be added: no amount on one of them,
+    /// a range, or units that don't convert into each other.
+    pub fn checked_add(&self, other: &Quantity) -> Option<Quantity> {
+        if self.upper.is_some() || other.upper.is_some() {
+            return None;
+        }
+        let (a, b) = (self.value?, other.value?);
+        let word = |q: &Quantity| normalize(q.unit_word().trim_end_matches(|c: char| !c.is_alphanumeric()));
+        if word(self) == word(other) {
+            // The longer spelling of the same unit is the plural
+            let unit = if other.unit.len() > self.unit.len() { &other.unit } else { &self.unit };
+            return Some(Quantity {
+                value: Some(a + b),
+                upper: None,
+                unit: unit.clone(),
+                glued: self.glued,
+            });
+        }
+        let (mine, theirs) = (self.size()?, other.size()?);
+        (mine.dimension == theirs.dimension).then(|| Quantity {
+            value: Some(a + b * theirs.base / mine.base),
+            ..self.clone()
+        })
+    }
+}
+
+impl fmt::Display for Quantity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(value) = self.value else {
+            return write!(f, "{}", self.unit);
+        };
+        let amount = match self.upper {
+            Some(upper) => format!("{}-{}", format_amount(value), format_amount(upper)),
+            None => format_amount(value),
+        };
+        match self.unit.as_str() {
+            "" => write!(f, "{}", amount),
+            unit if self.glued => write!(f, "{}{}", amount, unit),
+            unit => write!(f, "{} {}", amount, unit),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn q(measure: &str) -> Quantity {
+        Quantity::parse(measure)
+    }
+
+    #[test]
+    fn parses_amount_and_unit() {
+        assert_eq!(q("1 1/2 cups").value, Some(1.5));
+        assert_eq!(q("1 1/2 cups").unit, "cups");
+        assert_eq!(q("200g").value, Some(200.0));
+        assert_eq!(q("200g").unit, "g");
+        assert_eq!(q("½ tsp").value, Some(0.5));
+        assert_eq!(q("1½ tbsp").value, Some(1.5));
+        assert_eq!(q("0.25 l").value, Some(0.25));
+        assert_eq!(q("3").unit, "");
+        assert_eq!(q("pinch").value, None);
+        assert_eq!(q("pinch").unit, "pinch");
+        assert_eq!(q("").value, None);
+    }
+
+    #[test]
+    fn remembers_how_the_unit_was_written() {
+        assert!(q("200g").glued);
+        assert!(!q("2 cups").glued);
+        assert!(!q("3").glued);
+        assert_eq!(q("2 cloves crushed").unit_word(), "cloves");
+        assert_eq!(q("2 cloves crushed").unit_rest(), "crushed");
+        assert_eq!(q("2 cups").unit_rest(), "");
+    }
+
+    #[test]
+    fn prints_back_as_written() {
+        for measure in ["200g", "2 cups", "1½ tbsp", "3", "pinch", "2 cloves crushed"] {
+            assert_eq!(q(measure).to_string(), measure);
+        }
+        assert_eq!(q("1 1/2 cups").to_string(), "1½ cups");
+    }
+
+    #[test]
+    fn scales_the_amount_only() {
+        assert_eq!(q("200g").scaled(2.0).to_string(), "400g");
+        assert_eq!(q("1 1/2 cups").scaled(2.0).to_string(), "3 cups");
+        assert_eq!(q("1 tsp").scaled(0.5).to_string(), "½ tsp");
+        assert_eq!(q("to taste").scaled(3.0).to_string(), "to taste");
+    }
+
+    #[test]
+    fn keeps_ranges_together() {
+        assert_eq!(q("1-2 tbsp").value, Some(1.0));
+        assert_eq!(q("1-2 tbsp").upper, Some(2.0));
+        assert_eq!(q("1-2 tbsp").unit, "tbsp");
+        assert_eq!(q("1-2 tbsp").to_string(), "1-2 tbsp");
+        assert_eq!(q("1-2 tbsp").scaled(2.0).to_string(), "2-4 tbsp");
+        assert_eq!(q("100-150g").scaled(2.0).to_string(), "200-300g");
+        assert_eq!(q("2–3").scaled(0.5).to_string(), "1-1½");
+        assert_eq!(q("1 - 1 1/2 cups").upper, Some(1.5));
+        assert_eq!(q("1-2 tbsp").checked_add(&q("1 tbsp")), None);
+        // Not a range: nothing numeric after the dash
+        assert_eq!(q("2 - ish").upper, None);
+    }
+
+    #[test]
+    fn knows_exact_unit_sizes() {
+        assert_eq!(q("2 kg").size().map(|s| s.base), Some(1000.0));
+        assert_eq!(q("1 Cup").size().map(|s| s.dimension), Some(Dimension::Volume));
+        assert_eq!(q("8 oz.").size().map(|s| s.metric), Some(false));
+        assert_eq!(q("1 tbsp").size(), None);
+        assert_eq!(q("2").size(), None);
+    }
+
+    #[test]
+    fn adds_matching_units() {
+        let sum = |a: &str, b: &str| q(a).checked_add(&q(b)).map(|s| s.to_string());
+        assert_eq!(sum("200g", "300g").as_deref(), Some("500g"));
+        assert_eq!(sum("1 kg", "500 g").as_deref(), Some("1½ kg"));
+        assert_eq!(sum("200 g", "1 kg").as_deref(), Some("1200 g"));
+        assert_eq!(sum("1 clove", "2 cloves").as_deref(), Some("3 cloves"));
+        assert_eq!(sum("2", "1").as_deref(), Some("3"));
+        assert_eq!(sum("1 tbsp", "2 tbsp").as_deref(), Some("3 tbsp"));
+    }
+
+    #[test]
+    fn refuses_to_add_what_does_not_match() {
+        assert_eq!(q("200g").checked_add(&q("1 cup")), None);
+        assert_eq!(q("1 tbsp").checked_add(&q("1 tsp")), None);
+        assert_eq!(q("2").checked_add(&q("200g")), None);
+        assert_eq!(q("pinch").checked_add(&q("pinch")), None);
+    }
+}
@@ -0,0 +1,392 @@
+use crate::matching::ingredient_matches;
+use crate::MealDetail;
+use serde::{Deserialize, Serialize};
+
+/// Points awarded by `score_meal`, stored in the config file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScoringConfig {
+    /// Per recipe ingredient matching a main ingredient.
+    pub main_ingredient: i32,
+    /// Per recipe ingredient matching a sub ingredient.
+    pub sub_ingredient: i32,
+    /// When the taste appears in the title.
+    pub taste: i32,
+    /// The taste field matched elsewhere; weaker signals than the title.
+    pub taste_in_tags: i32,
+    pub taste_in_category: i32,
+    pub taste_in_area: i32,
+    pub taste_in_instructions: i32,
+    /// When an ingredient is one of the taste's signals in `taste_words`.
+    pub taste_in_ingredients: i32,
+    /// What taste words mean in recipes, beyond the word itself appearing.
+    pub taste_words: Vec<TasteWord>,
+    /// Subtracted per recipe ingredient that is neither asked for nor on hand,
+    /// so long recipes don't win just by containing the main ingredient.
+    pub unmatched_ingredient: i32,
+    /// Per recipe ingredient that is in season, when the seasonal boost is on.
+    pub seasonal_ingredient: i32,
+    /// Per recipe ingredient that uses up a leftover, when the leftover boost is on.
+    pub leftover_ingredient: i32,
+}
+
+impl Default for ScoringConfig {
+    fn default() -> Self {
+        Self {
+            main_ingredient: 4,
+            sub_ingredient: 2,
+            taste: 3,
+            taste_in_tags: 2,
+            taste_in_category: 2,
+            taste_in_area: 1,
+            taste_in_instructions: 1,
+            taste_in_ingredients: 2,
+            taste_words: default_taste_words(),
+            unmatched_ingredient: 1,
+            seasonal_ingredient: 2,
+            leftover_ingredient: 3,
+        }
+    }
+}
+
+/// A taste word and the concrete signs of it: "spicy" is chili in the
+/// ingredients, "sweet" is the Dessert category. Any one sign counts.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TasteWord {
+    pub word: String,
+    pub ingredients: Vec<String>,
+    pub categories: Vec<String>,
+    pub tags: Vec<String>,
+}
+
+impl TasteWord {
+    fn new(word: &str, ingredients: &[&str], categories: &[&str], tags: &[&str]) -> Self {
+        let owned = |items: &[&str]| items.iter().map(|s| s.to_string()).collect();
+        Self {
+            word: word.to_string(),
+            ingredients: owned(ingredients),
+            categories: owned(categories),
+            tags: owned(tags),
+        }
+    }
+
+    /// Whether `taste` (lowercase) contains this word on its own.
+    fn in_taste(&self, taste: &str) -> bool {
+        let word = self.word.to_lowercase();
+        taste.split(|c: char| !c.is_alphanumeric()).any(|part| part == word)
+    }
+}
+
+fn default_taste_words() -> Vec<TasteWord> {
+    vec![
+        TasteWord::new(
+            "spicy",
+            &[
+                "chili", "chilli", "jalapeño", "jalapeno", "cayenne", "chipotle", "scotch bonnet", "harissa", "sriracha",
+                "hot sauce", "red pepper flakes",
+            ],
+            &[],
+            &["Spicy", "Chilli"],
+        ),
+        TasteWord::new(
+            "sweet",
+            &["sugar", "honey", "maple syrup", "chocolate", "caramel", "condensed milk"],
+            &["Dessert"],
+            &["Sweet", "Pudding", "Cake", "Baking"],
+        ),
+        TasteWord::new("sour", &["lemon", "lime", "vinegar", "tamarind", "sumac"], &[], &["Sour"]),
+        TasteWord::new("smoky", &["smoked paprika", "chipotle", "bacon", "liquid smoke"], &[], &["BBQ"]),
+        TasteWord::new("creamy", &["cream", "creme fraiche", "mascarpone", "coconut milk"], &[], &[]),
+        TasteWord::new("fresh", &["mint", "basil", "coriander", "parsley", "cucumber", "lime"], &[], &["Salad"]),
+        TasteWord::new("hearty", &["potatoes", "beans", "lentils"], &["Beef", "Lamb", "Pork"], &["Stew", "Casserole"]),
+        TasteWord::new("light", &[], &["Seafood", "Vegetarian", "Side", "Starter"], &["Salad"]),
+    ]
+}
+
+/// What a search asked for, as seen by a `RecipeScorer`.
+#[derive(Debug, Clone, Copy)]
+pub struct ScoreInput<'a> {
+    pub main: &'a [String],
+    pub sub: &'a [String],
+    pub taste: &'a Option<String>,
+    /// Ingredients already at home; they never count against a recipe.
+    pub on_hand: &'a [String],
+    pub weights: &'a ScoringConfig,
+}
+
+/// Where the taste was found in a recipe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TastePlace {
+    Title,
+    Tags,
+    Category,
+    Area,
+    Instructions,
+    Ingredients,
+}
+
+/// Why a recipe got some of its points.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reason {
+    /// The recipe ingredient and the main ingredient it matched.
+    MainIngredient { ingredient: String, wanted: String },
+    SubIngredient { ingredient: String, wanted: String },
+    /// Neither asked for nor on hand.
+    Unmatched(String),
+    Taste(TastePlace),
+    /// On hand or asked for, in `PantryCoverage`.
+    Have(String),
+    /// Still to buy, in `PantryCoverage`.
+    ToBuy(String),
+    /// Anything a frontend adds on top, already worded for display.
+    Other(String),
+}
+
+/// One line of a score breakdown.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contribution {
+    pub reason: Reason,
+    pub points: i32,
+}
+
+impl Contribution {
+    pub fn new(reason: Reason, points: i32) -> Self {
+        Self { reason, points }
+    }
+}
+
+/// A way of ranking search results; higher is better. Scores only need to be
+/// comparable within one search.
+pub trait RecipeScorer: Send + Sync {
+    fn score(&self, meal: &MealDetail, input: &ScoreInput) -> i32;
+
+    /// What `score` is made of, summing to it. Empty if the scorer can't
+    /// break its score down.
+    fn explain(&self, _meal: &MealDetail, _input: &ScoreInput) -> Vec<Contribution> {
+        Vec::new()
+    }
+}
+
+/// The classic ranking: `score_meal`'s weighted ingredient and taste matches.
+pub struct IngredientOverlap;
+
+impl RecipeScorer for IngredientOverlap {
+    fn score(&self, meal: &MealDetail, input: &ScoreInput) -> i32 {
+        score_meal(meal, input.main, input.sub, input.taste, input.on_hand, input.weights)
+    }
+
+    fn explain(&self, meal: &MealDetail, input: &ScoreInput) -> Vec<Contribution> {
+        explain_meal(meal, input.main, input.sub, input.taste, input.on_hand, input.weights)
+    }
+}
+
+/// Favours recipes that can be cooked now: one point per ingredient that is
+/// on hand or asked for, minus one per ingredient still to buy.
+pub struct PantryCoverage;
+
+impl RecipeScorer for PantryCoverage {
+    fn score(&self, meal: &MealDetail, input: &ScoreInput) -> i32 {
+        self.explain(meal, input).iter().map(|c| c.points).sum()
+    }
+
+    fn explain(&self, meal: &MealDetail, input: &ScoreInput) -> Vec<Contribution> {
+        let have = input.on_hand.iter().chain(input.main).chain(input.sub);
+        meal.ingredients
+            .iter()
+            .map(|ing| {
+                if have.clone().any(|h| ingredient_matches(ing, h)) {
+                    Contribution::new(Reason::Have(ing.clone()), 1)
+                } else {
+                    Contribution::new(Reason::ToBuy(ing.clone()), -1)
+                }
+            })
+            .collect()
+    }
+}
+
+// Scoring: main ingredients higher priority
+pub fn score_meal(
+    detail: &MealDetail,
+    main_ing: &[String],
+    sub_ing: &[String],
+    taste: &Option<String>,
+    on_hand: &[String],
+    weights: &ScoringConfig,
+) -> i32 {
+    explain_meal(detail, main_ing, sub_ing, taste, on_hand, weights).iter().map(|c| c.points).sum()
+}
+
+/// `score_meal` line by line: every ingredient match, every unmatched
+/// ingredient and every place the taste was found.
+pub fn explain_meal(
+    detail: &MealDetail,
+    main_ing: &[String],
+    sub_ing: &[String],
+    taste: &Option<String>,
+    on_hand: &[String],
+    weights: &ScoringConfig,
+) -> Vec<Contribution> {
+    let mut lines = Vec::new();
+    for ing in &detail.ingredients {
+        let mut matched = false;
+        for want in main_ing {
+            if ingredient_matches(ing, want) {
+                let reason = Reason::MainIngredient { ingredient: ing.clone(), wanted: want.clone() };
+                lines.push(Contribution::new(reason, weights.main_ingredient));
+                matched = true;
+            }
+        }
+        for want in sub_ing {
+            if ingredient_matches(ing, want) {
+                let reason = Reason::SubIngredient { ingredient: ing.clone(), wanted: want.clone() };
+                lines.push(Contribution::new(reason, weights.sub_ingredient));
+                matched = true;
+            }
+        }
+        if !matched && !on_hand.iter().any(|have| ingredient_matches(ing, have)) {
+            lines.push(Contribution::new(Reason::Unmatched(ing.clone()), -weights.unmatched_ingredient));
+        }
+    }
+    if let Some(t) = taste {
+        let t = t.to_lowercase();
+        let has = |text: &str| text.to_lowercase().contains(&t);
+        // The mapped words in the taste, e.g. "spicy" in "spicy and sour"
+        let signals: Vec<&TasteWord> = weights.taste_words.iter().filter(|w| w.in_taste(&t)).collect();
+        let signal_tag = |tag: &String| signals.iter().any(|w| w.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)));
+        let signal_category = signals
+            .iter()
+            .any(|w| w.categories.iter().any(|c| c.eq_ignore_ascii_case(&detail.category)));
+        let signal_ingredient = |ing: &String| {
+            signals.iter().any(|w| w.ingredients.iter().any(|want| ingredient_matches(ing, want)))
+        };
+        let places = [
+            (TastePlace::Title, has(&detail.title), weights.taste),
+            (TastePlace::Tags, detail.tags.iter().any(|tag| has(tag) || signal_tag(tag)), weights.taste_in_tags),
+            (TastePlace::Category, has(&detail.category) || signal_category, weights.taste_in_category),
+            (TastePlace::Area, has(&detail.area), weights.taste_in_area),
+            (TastePlace::Instructions, has(&detail.instructions), weights.taste_in_instructions),
+            (TastePlace::Ingredients, detail.ingredients.iter().any(signal_ingredient), weights.taste_in_ingredients),
+        ];
+        for (place, hit, points) in places {
+            if hit {
+                lines.push(Contribution::new(Reason::Taste(place), points));
+            }
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meal() -> MealDetail {
+        MealDetail {
+            title: "Spicy Chicken Curry".to_string(),
+            category: "Chicken".to_string(),
+            area: "Indian".to_string(),
+            ingredients: vec!["Chicken Thighs".to_string(), "Onions".to_string(), "Garam Masala".to_string()],
+            ..Default::default()
+        }
+    }
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn main_ingredients_outweigh_sub_ingredients() {
+        let weights = ScoringConfig::default();
+        let as_main = score_meal(&meal(), &strings(&["chicken"]), &[], &None, &[], &weights);
+        let as_sub = score_meal(&meal(), &[], &strings(&["chicken"]), &None, &[], &weights);
+        // One hit, two unmatched ingredients
+        assert_eq!(as_main, 4 - 2);
+        assert_eq!(as_sub, 2 - 2);
+    }
+
+    #[test]
+    fn on_hand_ingredients_are_not_penalized() {
+        let weights = ScoringConfig::default();
+        let on_hand = strings(&["onion", "garam masala"]);
+        let score = score_meal(&meal(), &strings(&["chicken"]), &[], &None, &on_hand, &weights);
+        assert_eq!(score, 4);
+    }
+
+    #[test]
+    fn taste_counts_once_per_place() {
+        let weights = ScoringConfig::default();
+        let taste = Some("chicken".to_string());
+        let score = score_meal(&meal(), &[], &[], &taste, &strings(&["onion", "garam masala", "chicken"]), &weights);
+        // Title and category mention it; "chicken" isn't a taste word, so the ingredients don't count
+        assert_eq!(score, weights.taste + weights.taste_in_category);
+    }
+
+    #[test]
+    fn taste_words_match_their_ingredients() {
+        let weights = ScoringConfig::default();
+        let curry = MealDetail {
+            title: "Chicken Curry".to_string(),
+            ingredients: strings(&["Chicken", "Cayenne Pepper"]),
+            ..Default::default()
+        };
+        let on_hand = strings(&["chicken", "cayenne"]);
+        let score = score_meal(&curry, &[], &[], &Some("Spicy".to_string()), &on_hand, &weights);
+        assert_eq!(score, weights.taste_in_ingredients);
+    }
+
+    #[test]
+    fn taste_words_match_their_categories_and_tags() {
+        let weights = ScoringConfig::default();
+        let pie = MealDetail {
+            title: "Apple Pie".to_string(),
+            category: "Dessert".to_string(),
+            tags: strings(&["Baking"]),
+            ..Default::default()
+        };
+        let score = score_meal(&pie, &[], &[], &Some("something sweet".to_string()), &[], &weights);
+        assert_eq!(score, weights.taste_in_category + weights.taste_in_tags);
+    }
+
+    #[test]
+    fn taste_words_are_whole_words() {
+        let weights = ScoringConfig::default();
+        let pie = MealDetail { category: "Dessert".to_string(), ..Default::default() };
+        assert_eq!(score_meal(&pie, &[], &[], &Some("sweetcorn".to_string()), &[], &weights), 0);
+    }
+
+    #[test]
+    fn ingredient_overlap_is_score_meal() {
+        let weights = ScoringConfig::default();
+        let main = strings(&["chicken"]);
+        let input = ScoreInput { main: &main, sub: &[], taste: &None, on_hand: &[], weights: &weights };
+        assert_eq!(
+            IngredientOverlap.score(&meal(), &input),
+            score_meal(&meal(), &main, &[], &None, &[], &weights)
+        );
+    }
+
+    #[test]
+    fn explanation_adds_up_to_the_score() {
+        let weights = ScoringConfig::default();
+        let main = strings(&["chicken"]);
+        let taste = Some("spicy".to_string());
+        let lines = explain_meal(&meal(), &main, &[], &taste, &[], &weights);
+        let total: i32 = lines.iter().map(|c| c.points).sum();
+        assert_eq!(total, score_meal(&meal(), &main, &[], &taste, &[], &weights));
+        let chicken = Reason::MainIngredient { ingredient: "Chicken Thighs".to_string(), wanted: "chicken".to_string() };
+        assert!(lines.contains(&Contribution::new(chicken, weights.main_ingredient)));
+        assert!(lines.contains(&Contribution::new(Reason::Unmatched("Onions".to_string()), -1)));
+        assert!(lines.contains(&Contribution::new(Reason::Taste(TastePlace::Title), weights.taste)));
+    }
+
+    #[test]
+    fn pantry_coverage_counts_what_is_left_to_buy() {
+        let weights = ScoringConfig::default();
+        let main = strings(&["chicken"]);
+        let on_hand = strings(&["onion"]);
+        let input = ScoreInput { main: &main, sub: &[], taste: &None, on_hand: &on_hand, weights: &weights };
+        // Chicken and onions covered, garam masala to buy
+        assert_eq!(PantryCoverage.score(&meal(), &input), 1);
+    }
+}
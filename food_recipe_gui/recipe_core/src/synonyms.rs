@@ -0,0 +1,116 @@
+// Regional names for the same ingredient: a recipe with "Coriander" should
+// turn up for "cilantro", and one listing "eggplant" for "aubergine". Names
+// are brought to TheMealDB's, which are British, before fetching and matching.
+
+use crate::matching::split_words;
+use std::sync::OnceLock;
+
+/// TheMealDB's name first, then the other names for it.
+const SYNONYMS: &[(&str, &[&str])] = &[
+    ("Coriander", &["cilantro", "chinese parsley"]),
+    ("Spring Onions", &["scallion", "green onion"]),
+    ("Aubergine", &["eggplant", "brinjal"]),
+    ("Courgettes", &["zucchini"]),
+    ("Chickpeas", &["garbanzo", "garbanzo bean"]),
+    ("Rocket", &["arugula"]),
+    ("Prawns", &["shrimp"]),
+    ("Minced Beef", &["ground beef"]),
+    ("Double Cream", &["heavy cream", "whipping cream"]),
+    ("Single Cream", &["light cream"]),
+    ("Caster Sugar", &["superfine sugar"]),
+    ("Icing Sugar", &["powdered sugar", "confectioners sugar"]),
+    ("Plain Flour", &["all purpose flour"]),
+    ("Bicarbonate Of Soda", &["baking soda"]),
+    ("Cornflour", &["cornstarch", "corn starch"]),
+    ("Red Pepper", &["red bell pepper"]),
+    ("Beetroot", &["beet"]),
+    ("Swede", &["rutabaga"]),
+    ("Mangetout", &["snow pea"]),
+    ("Pak Choi", &["bok choy"]),
+];
+
+// Each other name as normalized words, with the canonical name's words
+fn table() -> &'static [(Vec<String>, Vec<String>)] {
+    static TABLE: OnceLock<Vec<(Vec<String>, Vec<String>)>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        SYNONYMS
+            .iter()
+            .flat_map(|(canonical, others)| {
+                let canonical = split_words(canonical);
+                others.iter().map(move |other| (split_words(other), canonical.clone()))
+            })
+            .collect()
+    })
+}
+
+/// Replaces other names in normalized `words` with the canonical ones,
+/// e.g. "fresh cilantro" becomes "fresh coriander".
+pub(crate) fn canonical_words(words: Vec<String>) -> Vec<String> {
+    let mut out = Vec::with_capacity(words.len());
+    let mut i = 0;
+    while i < words.len() {
+        // Longest name first, so "red bell pepper" wins over anything shorter
+        let found = table()
+            .iter()
+            .filter(|(other, _)| words[i..].starts_with(other))
+            .max_by_key(|(other, _)| other.len());
+        match found {
+            Some((other, canonical)) => {
+                out.extend(canonical.iter().cloned());
+                i += other.len();
+            }
+            None => {
+                out.push(words[i].clone());
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+/// TheMealDB's name for `ingredient` if it is another name for one,
+/// otherwise `ingredient` unchanged. Used on what the user typed before it
+/// goes to a provider.
+pub fn canonical_name(ingredient: &str) -> String {
+    let words = split_words(ingredient);
+    SYNONYMS
+        .iter()
+        .find(|(_, others)| others.iter().any(|other| split_words(other) == words))
+        .map(|(canonical, _)| canonical.to_string())
+        .unwrap_or_else(|| ingredient.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matching::ingredient_matches;
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn other_names_become_the_mealdb_name() {
+        assert_eq!(canonical_name("Cilantro"), "Coriander");
+        assert_eq!(canonical_name("scallions"), "Spring Onions");
+        assert_eq!(canonical_name("chicken"), "chicken");
+        // Only whole names are replaced before fetching
+        assert_eq!(canonical_name("fresh cilantro"), "fresh cilantro");
+    }
+
+    #[test]
+    fn replaces_names_inside_longer_ingredients() {
+        assert_eq!(canonical_words(strings(&["fresh", "cilantro"])), strings(&["fresh", "coriander"]));
+        assert_eq!(canonical_words(strings(&["red", "bell", "pepper"])), strings(&["red", "pepper"]));
+        assert_eq!(canonical_words(strings(&["beetroot"])), strings(&["beetroot"]));
+    }
+
+    #[test]
+    fn matching_sees_through_regional_names() {
+        assert!(ingredient_matches("Coriander", "cilantro"));
+        assert!(ingredient_matches("Spring Onions", "scallion"));
+        assert!(ingredient_matches("eggplant", "aubergine"));
+        assert!(ingredient_matches("Ground Beef", "minced beef"));
+        assert!(!ingredient_matches("Red Onions", "scallion"));
+    }
+}
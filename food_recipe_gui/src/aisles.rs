@@ -0,0 +1,170 @@
+// Which part of the shop an ingredient is found in, so the shopping list can
+// follow a walk through the store instead of the alphabet.
+
+use crate::diet;
+
+/// In the order a typical store run passes them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Aisle {
+    Produce,
+    Bakery,
+    MeatFish,
+    Dairy,
+    Pantry,
+    Spices,
+    Frozen,
+    Other,
+}
+
+// Longest matching name wins, like the price and nutrition tables, so
+// "coconut milk" is pantry while "milk" is dairy. Names match whole words:
+// "eggplant" is produce, not an egg
+const AISLES: &[(&str, Aisle)] = &[
+    ("onion", Aisle::Produce),
+    ("garlic", Aisle::Produce),
+    ("ginger", Aisle::Produce),
+    ("potato", Aisle::Produce),
+    ("carrot", Aisle::Produce),
+    ("celery", Aisle::Produce),
+    ("tomato", Aisle::Produce),
+    ("pepper", Aisle::Produce),
+    ("chilli", Aisle::Produce),
+    ("chili", Aisle::Produce),
+    ("lettuce", Aisle::Produce),
+    ("spinach", Aisle::Produce),
+    ("cabbage", Aisle::Produce),
+    ("broccoli", Aisle::Produce),
+    ("cauliflower", Aisle::Produce),
+    ("mushroom", Aisle::Produce),
+    ("courgette", Aisle::Produce),
+    ("aubergine", Aisle::Produce),
+    ("eggplant", Aisle::Produce),
+    ("squash", Aisle::Produce),
+    ("cucumber", Aisle::Produce),
+    ("leek", Aisle::Produce),
+    ("lemon", Aisle::Produce),
+    ("lime", Aisle::Produce),
+    ("apple", Aisle::Produce),
+    ("banana", Aisle::Produce),
+    ("avocado", Aisle::Produce),
+    ("coriander", Aisle::Produce),
+    ("parsley", Aisle::Produce),
+    ("basil", Aisle::Produce),
+    ("mint", Aisle::Produce),
+    ("spring onion", Aisle::Produce),
+    ("bread", Aisle::Bakery),
+    ("baguette", Aisle::Bakery),
+    ("tortilla", Aisle::Bakery),
+    ("pitta", Aisle::Bakery),
+    ("naan", Aisle::Bakery),
+    ("chicken", Aisle::MeatFish),
+    ("beef", Aisle::MeatFish),
+    ("pork", Aisle::MeatFish),
+    ("lamb", Aisle::MeatFish),
+    ("bacon", Aisle::MeatFish),
+    ("sausage", Aisle::MeatFish),
+    ("ham", Aisle::MeatFish),
+    ("salmon", Aisle::MeatFish),
+    ("tuna", Aisle::MeatFish),
+    ("cod", Aisle::MeatFish),
+    ("prawn", Aisle::MeatFish),
+    ("fish", Aisle::MeatFish),
+    ("milk", Aisle::Dairy),
+    ("butter", Aisle::Dairy),
+    ("cream", Aisle::Dairy),
+    ("cheese", Aisle::Dairy),
+    ("parmesan", Aisle::Dairy),
+    ("mozzarella", Aisle::Dairy),
+    ("yogurt", Aisle::Dairy),
+    ("yoghurt", Aisle::Dairy),
+    ("egg", Aisle::Dairy),
+    ("flour", Aisle::Pantry),
+    ("sugar", Aisle::Pantry),
+    ("rice", Aisle::Pantry),
+    ("pasta", Aisle::Pantry),
+    ("spaghetti", Aisle::Pantry),
+    ("noodle", Aisle::Pantry),
+    ("oil", Aisle::Pantry),
+    ("vinegar", Aisle::Pantry),
+    ("stock", Aisle::Pantry),
+    ("sauce", Aisle::Pantry),
+    ("honey", Aisle::Pantry),
+    ("beans", Aisle::Pantry),
+    ("lentils", Aisle::Pantry),
+    ("chickpeas", Aisle::Pantry),
+    ("coconut milk", Aisle::Pantry),
+    ("chopped tomatoes", Aisle::Pantry),
+    ("tomato puree", Aisle::Pantry),
+    ("peanut butter", Aisle::Pantry),
+    ("salt", Aisle::Spices),
+    ("black pepper", Aisle::Spices),
+    ("peppercorns", Aisle::Spices),
+    ("cumin", Aisle::Spices),
+    ("paprika", Aisle::Spices),
+    ("turmeric", Aisle::Spices),
+    ("cinnamon", Aisle::Spices),
+    ("oregano", Aisle::Spices),
+    ("thyme", Aisle::Spices),
+    ("garam masala", Aisle::Spices),
+    ("chilli powder", Aisle::Spices),
+    ("bay leaf", Aisle::Spices),
+    ("nutmeg", Aisle::Spices),
+    ("frozen", Aisle::Frozen),
+    ("peas", Aisle::Frozen),
+    ("ice cream", Aisle::Frozen),
+];
+
+impl Aisle {
+    pub fn label(self) -> String {
+        match self {
+            Aisle::Produce => tr!("aisle-produce"),
+            Aisle::Bakery => tr!("aisle-bakery"),
+            Aisle::MeatFish => tr!("aisle-meat-fish"),
+            Aisle::Dairy => tr!("aisle-dairy"),
+            Aisle::Pantry => tr!("aisle-pantry"),
+            Aisle::Spices => tr!("aisle-spices"),
+            Aisle::Frozen => tr!("aisle-frozen"),
+            Aisle::Other => tr!("aisle-other"),
+        }
+    }
+
+    pub fn of(ingredient: &str) -> Aisle {
+        let words = diet::words(ingredient);
+        AISLES
+            .iter()
+            .filter(|(name, _)| diet::in_list(&words, &[name]))
+            .max_by_key(|(name, _)| name.len())
+            .map_or(Aisle::Other, |&(_, aisle)| aisle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_whole_words() {
+        assert_eq!(Aisle::of("Eggs"), Aisle::Dairy);
+        assert_eq!(Aisle::of("Eggplant"), Aisle::Produce);
+        assert_eq!(Aisle::of("Butternut squash"), Aisle::Produce);
+        assert_eq!(Aisle::of("Unsalted Butter"), Aisle::Dairy);
+        assert_eq!(Aisle::of("Peppercorns"), Aisle::Spices);
+        assert_eq!(Aisle::of("Red Pepper"), Aisle::Produce);
+        assert_eq!(Aisle::of("Hamburger buns"), Aisle::Other);
+    }
+
+    #[test]
+    fn prefers_the_longest_name() {
+        assert_eq!(Aisle::of("Coconut Milk"), Aisle::Pantry);
+        assert_eq!(Aisle::of("Milk"), Aisle::Dairy);
+        assert_eq!(Aisle::of("Ground Black Pepper"), Aisle::Spices);
+        assert_eq!(Aisle::of("Chopped Tomatoes"), Aisle::Pantry);
+        assert_eq!(Aisle::of("Cherry Tomatoes"), Aisle::Produce);
+    }
+
+    #[test]
+    fn unknown_ingredients_go_last() {
+        assert_eq!(Aisle::of("Saffron"), Aisle::Other);
+        assert_eq!(Aisle::of(""), Aisle::Other);
+    }
+}
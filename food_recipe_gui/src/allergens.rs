@@ -0,0 +1,129 @@
+// Common allergens recognised from ingredient names, with the same whole-word
+// matching as the diet check. Meant as a warning, not a guarantee: a recipe
+// without a badge can still contain the allergen under a brand name.
+
+use crate::diet::{in_list, words};
+use recipe_core::MealDetail;
+use serde::{Deserialize, Serialize};
+
+const GLUTEN: &[&str] = &[
+    "flour", "wheat", "bread", "breadcrumb", "pasta", "spaghetti", "penne", "fusilli", "linguine", "lasagne",
+    "tagliatelle", "macaroni", "noodle", "barley", "rye", "couscous", "semolina", "bulgur", "spelt", "pastry",
+    "filo", "tortilla", "pita", "naan", "biscuit", "cracker", "soy sauce", "beer", "malt",
+];
+const GLUTEN_FREE: &[&str] = &[
+    "rice flour", "corn flour", "almond flour", "coconut flour", "chickpea flour", "gram flour", "rice noodle",
+    "gluten free",
+];
+
+const NUTS: &[&str] = &[
+    "nut", "almond", "walnut", "pecan", "cashew", "hazelnut", "pistachio", "macadamia", "brazil nut", "pine nut",
+    "praline", "marzipan", "frangipane", "nutella",
+];
+
+const PEANUTS: &[&str] = &["peanut", "groundnut", "satay"];
+
+const SHELLFISH: &[&str] = &[
+    "prawn", "shrimp", "crab", "lobster", "mussel", "clam", "oyster", "scallop", "squid", "calamari", "octopus",
+    "crayfish", "langoustine", "cockle",
+];
+
+const FISH: &[&str] = &[
+    "fish", "salmon", "tuna", "cod", "haddock", "trout", "mackerel", "sardine", "anchovy", "anchovies", "herring",
+    "halibut", "tilapia", "sea bass", "kipper", "caviar", "worcestershire sauce",
+];
+
+const DAIRY: &[&str] = &[
+    "milk", "butter", "cream", "cheese", "yogurt", "yoghurt", "ghee", "buttermilk", "mozzarella", "parmesan",
+    "cheddar", "feta", "ricotta", "mascarpone", "creme fraiche", "custard", "whey", "paneer", "gruyere",
+];
+const DAIRY_FREE: &[&str] = &[
+    "coconut milk", "coconut cream", "almond milk", "soy milk", "oat milk", "rice milk", "peanut butter",
+    "almond butter", "cocoa butter", "butter bean", "cream of tartar",
+];
+
+const EGGS: &[&str] = &["egg", "mayonnaise", "meringue", "custard", "aioli"];
+const EGG_FREE: &[&str] = &["egg replacer"];
+
+const SOY: &[&str] = &["soy", "soya", "soy sauce", "tofu", "tempeh", "miso", "edamame", "tamari"];
+
+const SESAME: &[&str] = &["sesame", "tahini", "halva"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Allergen {
+    Gluten,
+    Nuts,
+    Peanuts,
+    Shellfish,
+    Fish,
+    Dairy,
+    Eggs,
+    Soy,
+    Sesame,
+}
+
+impl Allergen {
+    pub const ALL: [Allergen; 9] = [
+        Allergen::Gluten,
+        Allergen::Nuts,
+        Allergen::Peanuts,
+        Allergen::Shellfish,
+        Allergen::Fish,
+        Allergen::Dairy,
+        Allergen::Eggs,
+        Allergen::Soy,
+        Allergen::Sesame,
+    ];
+
+    /// Name in the current language.
+    pub fn name(self) -> String {
+        match self {
+            Allergen::Gluten => tr!("allergen-gluten"),
+            Allergen::Nuts => tr!("allergen-nuts"),
+            Allergen::Peanuts => tr!("allergen-peanuts"),
+            Allergen::Shellfish => tr!("allergen-shellfish"),
+            Allergen::Fish => tr!("allergen-fish"),
+            Allergen::Dairy => tr!("allergen-dairy"),
+            Allergen::Eggs => tr!("allergen-eggs"),
+            Allergen::Soy => tr!("allergen-soy"),
+            Allergen::Sesame => tr!("allergen-sesame"),
+        }
+    }
+
+    // What contains it, and the look-alikes that don't
+    fn lists(self) -> (&'static [&'static str], &'static [&'static str]) {
+        match self {
+            Allergen::Gluten => (GLUTEN, GLUTEN_FREE),
+            Allergen::Nuts => (NUTS, &[]),
+            Allergen::Peanuts => (PEANUTS, &[]),
+            Allergen::Shellfish => (SHELLFISH, &[]),
+            Allergen::Fish => (FISH, &[]),
+            Allergen::Dairy => (DAIRY, DAIRY_FREE),
+            Allergen::Eggs => (EGGS, EGG_FREE),
+            Allergen::Soy => (SOY, &[]),
+            Allergen::Sesame => (SESAME, &[]),
+        }
+    }
+
+    fn in_ingredient(self, ingredient: &str) -> bool {
+        let (contains, exceptions) = self.lists();
+        let ingredient = words(ingredient);
+        in_list(&ingredient, contains) && !in_list(&ingredient, exceptions)
+    }
+}
+
+/// The `enabled` allergens found in `meal`, each with the ingredients that contain it.
+pub fn find(meal: &MealDetail, enabled: &[Allergen]) -> Vec<(Allergen, Vec<String>)> {
+    enabled
+        .iter()
+        .filter_map(|&allergen| {
+            let hits: Vec<String> = meal
+                .ingredients
+                .iter()
+                .filter(|ing| allergen.in_ingredient(ing))
+                .cloned()
+                .collect();
+            (!hits.is_empty()).then_some((allergen, hits))
+        })
+        .collect()
+}
@@ -0,0 +1,118 @@
+// One JSON file with the user's favorites, ratings and notes, for backups
+// and for moving to another machine. Restoring merges into what is there:
+// the file wins for the meals it mentions, everything else is kept.
+
+use crate::profiles::Profiles;
+use recipe_core::db::Database;
+use recipe_core::MealDetail;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::{fs, path::Path, path::PathBuf};
+
+/// Bumped when the layout changes in a way older versions can't read.
+const FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Backup {
+    pub version: u32,
+    /// Favorite meals, in order, by profile name.
+    pub favorites: BTreeMap<String, Vec<MealDetail>>,
+    /// Stars by meal ID.
+    pub ratings: BTreeMap<String, u8>,
+    /// Note by meal ID.
+    pub notes: BTreeMap<String, String>,
+}
+
+impl Backup {
+    /// Everything worth keeping from the database, for every profile.
+    pub fn collect(db: &Database, profiles: &Profiles) -> rusqlite::Result<Self> {
+        let mut favorites = BTreeMap::new();
+        for profile in profiles.iter() {
+            let meals = db.load_favorites(&profile.name)?;
+            if !meals.is_empty() {
+                favorites.insert(profile.name.clone(), meals);
+            }
+        }
+        Ok(Self {
+            version: FORMAT_VERSION,
+            favorites,
+            ratings: db.load_ratings()?.into_iter().collect(),
+            notes: db.load_notes()?.into_iter().filter(|(_, body)| !body.trim().is_empty()).collect(),
+        })
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        let text = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, text).map_err(|e| e.to_string())
+    }
+
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let backup: Self = serde_json::from_str(&text).map_err(|e| e.to_string())?;
+        if backup.version > FORMAT_VERSION {
+            return Err(tr!("backup-too-new"));
+        }
+        Ok(backup)
+    }
+
+    /// Merges the file into the database, adding profiles it names that
+    /// don't exist yet. The caller reloads favorites, ratings and notes.
+    pub fn restore(self, db: &Database, profiles: &mut Profiles) -> rusqlite::Result<RestoreReport> {
+        let mut report = RestoreReport::default();
+        for (profile, meals) in self.favorites {
+            if profiles.get(&profile).is_none() && profiles.add(&profile) {
+                profiles.save(db)?;
+            }
+            let mut current = db.load_favorites(&profile)?;
+            for meal in meals {
+                if !current.iter().any(|m| m.id == meal.id) {
+                    current.push(meal);
+                    report.favorites += 1;
+                }
+            }
+            db.save_favorites(&profile, &current)?;
+        }
+        for (meal_id, stars) in self.ratings {
+            db.save_rating(&meal_id, stars)?;
+            report.ratings += 1;
+        }
+        for (meal_id, body) in self.notes {
+            db.save_note(&meal_id, &body)?;
+            report.notes += 1;
+        }
+        Ok(report)
+    }
+
+    pub fn summary(&self) -> String {
+        tr!(
+            "backup-exported",
+            favorites = self.favorites.values().map(Vec::len).sum::<usize>(),
+            ratings = self.ratings.len(),
+            notes = self.notes.len()
+        )
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct RestoreReport {
+    /// Favorites that weren't there yet.
+    pub favorites: usize,
+    pub ratings: usize,
+    pub notes: usize,
+}
+
+impl RestoreReport {
+    pub fn summary(&self) -> String {
+        tr!("backup-restored", favorites = self.favorites, ratings = self.ratings, notes = self.notes)
+    }
+}
+
+pub fn save_dialog() -> Option<PathBuf> {
+    let name = format!("recipe_finder_backup_{}.json", chrono::Local::now().format("%Y-%m-%d"));
+    rfd::FileDialog::new().set_file_name(name).add_filter("JSON", &["json"]).save_file()
+}
+
+pub fn open_dialog() -> Option<PathBuf> {
+    rfd::FileDialog::new().add_filter("JSON", &["json"]).pick_file()
+}
@@ -0,0 +1,142 @@
+// Searches queued to run one after another in the background, e.g. one per
+// planned dinner. Only one runs at a time, so the queue never competes with
+// itself for the provider's rate limit; each keeps its own progress and results.
+
+use crate::search::{self, SearchEvent, SearchProgress, SearchQuery};
+use eframe::egui;
+use recipe_core::provider::RecipeProvider;
+use recipe_core::MealDetail;
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+use tokio_util::sync::CancellationToken;
+
+pub struct QueuedSearch {
+    pub query: SearchQuery,
+    provider: Arc<dyn RecipeProvider>,
+    pub progress: SearchProgress,
+    /// Best first once the search is done.
+    pub results: Vec<MealDetail>,
+    events: Option<Receiver<SearchEvent>>,
+    started: bool,
+}
+
+impl QueuedSearch {
+    pub fn provider_name(&self) -> &str {
+        self.provider.name()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.events.is_some()
+    }
+
+    pub fn is_waiting(&self) -> bool {
+        !self.started
+    }
+}
+
+#[derive(Default)]
+pub struct SearchQueue {
+    searches: Vec<QueuedSearch>,
+    /// Stops the running search and keeps the waiting ones from starting.
+    cancel: CancellationToken,
+}
+
+impl SearchQueue {
+    /// `query` should be complete, filters and weights included; it runs as is.
+    pub fn push(&mut self, provider: Arc<dyn RecipeProvider>, query: SearchQuery) {
+        self.searches.push(QueuedSearch {
+            query,
+            provider,
+            progress: SearchProgress::default(),
+            results: Vec::new(),
+            events: None,
+            started: false,
+        });
+    }
+
+    /// Takes in what the running search has sent and starts the next one once
+    /// it is done. Call once per frame.
+    pub fn poll(&mut self, runtime: &Runtime, ctx: &egui::Context) {
+        for queued in self.searches.iter_mut().filter(|s| s.is_running()) {
+            let Some(rx) = &queued.events else {
+                continue;
+            };
+            while let Ok(event) = rx.try_recv() {
+                queued.progress.record(&event);
+                if let SearchEvent::MealLoaded(meal) = event {
+                    queued.results.push(*meal);
+                }
+            }
+            if queued.progress.done {
+                queued.query.sort(&mut queued.results);
+                queued.events = None;
+            }
+        }
+        if self.cancel.is_cancelled() || self.searches.iter().any(|s| s.is_running()) {
+            return;
+        }
+        if let Some(next) = self.searches.iter_mut().find(|s| s.is_waiting()) {
+            let (tx, rx) = mpsc::channel();
+            next.events = Some(rx);
+            next.started = true;
+            runtime.spawn(search::run_search(
+                Arc::clone(&next.provider),
+                next.query.clone(),
+                tx,
+                self.cancel.clone(),
+                ctx.clone(),
+            ));
+        }
+    }
+
+    /// Stops the running search; the waiting ones stay queued until `resume`.
+    pub fn pause(&mut self) {
+        self.cancel.cancel();
+    }
+
+    pub fn resume(&mut self) {
+        // A search stopped halfway starts over
+        for queued in self.searches.iter_mut().filter(|s| s.progress.cancelled) {
+            queued.progress = SearchProgress::default();
+            queued.results.clear();
+            queued.started = false;
+        }
+        self.cancel = CancellationToken::new();
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+
+    /// Drops a search; a running one is stopped first.
+    pub fn remove(&mut self, index: usize) {
+        if self.searches[index].is_running() {
+            // Only the running search listens to the token, so swap it out
+            self.cancel.cancel();
+            self.cancel = CancellationToken::new();
+        }
+        self.searches.remove(index);
+    }
+
+    pub fn clear_finished(&mut self) {
+        self.searches.retain(|s| !s.progress.done || s.progress.cancelled);
+    }
+
+    pub fn get(&self, index: usize) -> Option<&QueuedSearch> {
+        self.searches.get(index)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &QueuedSearch> {
+        self.searches.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.searches.is_empty()
+    }
+
+    /// Searches not finished yet, the running one included.
+    pub fn pending(&self) -> usize {
+        self.searches.iter().filter(|s| !s.progress.done).count()
+    }
+}
@@ -0,0 +1,216 @@
+use crate::cost::{self, Price};
+use crate::i18n::Language;
+use crate::measure::UnitSystem;
+use crate::search::{ScoringStrategy, SortOrder};
+use crate::seasonal::Region;
+use crate::theme::ThemeConfig;
+use recipe_core::db::DB_FILE;
+use recipe_core::provider::{http::ProxyConfig, themealdb};
+use recipe_core::scoring::ScoringConfig;
+use serde::{Deserialize, Serialize};
+use std::{
+    env,
+    fs::{self, File},
+    io::{self, BufReader, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+pub const CONFIG_FILE: &str = "recipe_config.toml";
+// Read once if there is no TOML file yet; the next save writes TOML
+const LEGACY_CONFIG_FILE: &str = "recipe_config.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct AppConfig {
+    /// TheMealDB API root, without the key.
+    pub themealdb_url: String,
+    /// Key appended to `themealdb_url`; "1" is the public test key. A paid key
+    /// unlocks the latest meals and the multi-ingredient filter.
+    /// `THEMEALDB_API_KEY` overrides it.
+    pub themealdb_api_key: String,
+    /// Give up on connecting to a server after this many seconds.
+    pub connect_timeout_secs: u64,
+    /// Give up on a request, including reading the response, after this many seconds.
+    pub request_timeout_secs: u64,
+    pub proxy: ProxyConfig,
+    /// Requests per second each provider may send, however many searches run at once.
+    pub requests_per_second: f64,
+    /// Soft daily allowance of provider requests; the counter warns as it
+    /// gets close, but nothing is blocked. `None` for no limit.
+    pub daily_request_limit: Option<u64>,
+    /// Where the database with the meal cache, favorites and history lives.
+    pub cache_dir: PathBuf,
+    /// Result order a fresh start begins with.
+    pub default_sort: SortOrder,
+    /// Keep the meals of every favorite's category cached in the background.
+    pub prefetch_favorite_categories: bool,
+    /// Minutes between two prefetch rounds.
+    pub prefetch_interval_mins: u64,
+    /// Re-fetch every favorite and download its photos at launch, so the
+    /// favorites work fully offline.
+    pub keep_favorites_offline: bool,
+    /// Hours between two refreshes after the one at launch; 0 for launch only.
+    pub favorites_refresh_hours: u64,
+    /// console_task_manager_console's tasks.json, which missing ingredients can be added to.
+    pub tasks_file: PathBuf,
+    /// Enables the Spoonacular provider. `SPOONACULAR_API_KEY` overrides it.
+    pub spoonacular_api_key: Option<String>,
+    /// Enables the Edamam provider together with `edamam_app_key`.
+    /// `EDAMAM_APP_ID` / `EDAMAM_APP_KEY` override them.
+    pub edamam_app_id: Option<String>,
+    pub edamam_app_key: Option<String>,
+    /// Edamam health labels every search must satisfy, e.g. "vegan" or "gluten-free".
+    pub edamam_health_labels: Vec<String>,
+    /// Weights used to rank search results; editable in the settings window.
+    pub scoring: ScoringConfig,
+    /// How results are ranked; `scoring` holds the weights it may use.
+    pub scoring_strategy: ScoringStrategy,
+    /// Units measures are shown and exported in.
+    pub units: UnitSystem,
+    /// Where seasonal produce is judged for.
+    pub region: Region,
+    /// Price table for cost estimates; editable in the settings window.
+    pub prices: Vec<Price>,
+    /// Written in front of every price.
+    pub currency: String,
+    pub theme: ThemeConfig,
+    pub language: Language,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            themealdb_url: themealdb::API_ROOT.to_string(),
+            themealdb_api_key: themealdb::TEST_API_KEY.to_string(),
+            connect_timeout_secs: 10,
+            request_timeout_secs: 30,
+            proxy: ProxyConfig::default(),
+            requests_per_second: 10.0,
+            daily_request_limit: None,
+            cache_dir: PathBuf::from("."),
+            default_sort: SortOrder::default(),
+            prefetch_favorite_categories: false,
+            prefetch_interval_mins: 6 * 60,
+            keep_favorites_offline: false,
+            favorites_refresh_hours: 24,
+            tasks_file: PathBuf::from("../console_task_manager_console/tasks.json"),
+            spoonacular_api_key: None,
+            edamam_app_id: None,
+            edamam_app_key: None,
+            edamam_health_labels: Vec::new(),
+            scoring: ScoringConfig::default(),
+            scoring_strategy: ScoringStrategy::default(),
+            units: UnitSystem::default(),
+            region: Region::default(),
+            prices: cost::default_prices(),
+            currency: "€".to_string(),
+            theme: ThemeConfig::default(),
+            language: Language::default(),
+        }
+    }
+}
+
+impl AppConfig {
+    /// The file contents only, without environment overrides. Use this
+    /// before saving so keys from the environment never end up on disk.
+    pub fn load_file(path: &str) -> io::Result<Self> {
+        if !Path::new(path).exists() {
+            if Path::new(LEGACY_CONFIG_FILE).exists() {
+                let f = File::open(LEGACY_CONFIG_FILE)?;
+                return Ok(serde_json::from_reader(BufReader::new(f)).unwrap_or_default());
+            }
+            return Ok(Self::default());
+        }
+        let text = fs::read_to_string(path)?;
+        Ok(toml::from_str(&text).unwrap_or_else(|e| {
+            eprintln!("Ignoring {}, using defaults: {}", path, e);
+            Self::default()
+        }))
+    }
+
+    pub fn load(path: &str) -> io::Result<Self> {
+        let mut config = Self::load_file(path)?;
+        if let Ok(key) = env::var("THEMEALDB_API_KEY") {
+            config.themealdb_api_key = key;
+        }
+        if let Ok(key) = env::var("SPOONACULAR_API_KEY") {
+            config.spoonacular_api_key = Some(key);
+        }
+        if let Ok(id) = env::var("EDAMAM_APP_ID") {
+            config.edamam_app_id = Some(id);
+        }
+        if let Ok(key) = env::var("EDAMAM_APP_KEY") {
+            config.edamam_app_key = Some(key);
+        }
+        config.spoonacular_api_key = config.spoonacular_api_key.filter(|k| !k.trim().is_empty());
+        config.edamam_app_id = config.edamam_app_id.filter(|k| !k.trim().is_empty());
+        config.edamam_app_key = config.edamam_app_key.filter(|k| !k.trim().is_empty());
+        Ok(config)
+    }
+
+    pub fn save(&self, path: &str) -> io::Result<()> {
+        let tmp = format!("{}.tmp", path);
+        let mut f = File::create(&tmp)?;
+        let text = toml::to_string_pretty(self).map_err(io::Error::other)?;
+        f.write_all(text.as_bytes())?;
+        f.flush()?;
+        fs::rename(tmp, path)?;
+        Ok(())
+    }
+
+    /// Whether `themealdb_api_key` is a paid key rather than the public test one.
+    pub fn themealdb_premium(&self) -> bool {
+        let key = self.themealdb_api_key.trim();
+        !key.is_empty() && key != themealdb::TEST_API_KEY
+    }
+
+    /// TheMealDB URL with the key, ready for endpoint names. Paid keys move
+    /// from v1 to v2, which has the premium endpoints.
+    pub fn themealdb_base(&self) -> String {
+        let url = self.themealdb_url.trim_end_matches('/');
+        let key = self.themealdb_api_key.trim();
+        match url.strip_suffix("/v1") {
+            Some(root) if self.themealdb_premium() => format!("{}/v2/{}", root, key),
+            _ => format!("{}/{}", url, key),
+        }
+    }
+
+    /// The public v1 URL to fall back on if the server rejects a paid key.
+    pub fn themealdb_fallback(&self) -> Option<String> {
+        let url = self.themealdb_url.trim_end_matches('/');
+        self.themealdb_premium()
+            .then(|| format!("{}/{}", url, themealdb::TEST_API_KEY))
+    }
+
+    pub fn connect_timeout(&self) -> Duration {
+        Duration::from_secs(self.connect_timeout_secs.max(1))
+    }
+
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_secs(self.request_timeout_secs.max(1))
+    }
+
+    pub fn prefetch_interval(&self) -> Duration {
+        Duration::from_secs(self.prefetch_interval_mins.max(1) * 60)
+    }
+
+    /// `None` when favorites are only refreshed at launch.
+    pub fn favorites_refresh_interval(&self) -> Option<Duration> {
+        (self.favorites_refresh_hours > 0).then(|| Duration::from_secs(self.favorites_refresh_hours * 60 * 60))
+    }
+
+    /// Where photos downloaded for offline use go.
+    pub fn image_dir(&self) -> PathBuf {
+        self.cache_dir.join("images")
+    }
+
+    /// Where the daily log files go.
+    pub fn log_dir(&self) -> PathBuf {
+        self.cache_dir.join("logs")
+    }
+
+    pub fn db_path(&self) -> PathBuf {
+        self.cache_dir.join(DB_FILE)
+    }
+}
@@ -0,0 +1,65 @@
+// Cooking mode: one instruction step at a time, big enough to read from the
+// other side of the kitchen, with the ingredients that step uses alongside.
+
+use crate::steps::{self, Step};
+use recipe_core::matching::ingredient_matches;
+use recipe_core::MealDetail;
+
+pub struct CookingMode {
+    pub meal: MealDetail,
+    pub steps: Vec<Step>,
+    /// Ingredient name and the line to show for it, scaled and converted the
+    /// way the detail view had them.
+    ingredients: Vec<(String, String)>,
+    /// Zero-based index into `steps`.
+    pub current: usize,
+}
+
+impl CookingMode {
+    pub fn new(meal: &MealDetail, ingredients: Vec<(String, String)>) -> Self {
+        Self {
+            meal: meal.clone(),
+            steps: steps::parse_steps(&meal.instructions),
+            ingredients,
+            current: 0,
+        }
+    }
+
+    pub fn step(&self) -> Option<&Step> {
+        self.steps.get(self.current)
+    }
+
+    pub fn is_first(&self) -> bool {
+        self.current == 0
+    }
+
+    pub fn is_last(&self) -> bool {
+        self.current + 1 >= self.steps.len()
+    }
+
+    pub fn next(&mut self) {
+        if !self.is_last() {
+            self.current += 1;
+        }
+    }
+
+    pub fn back(&mut self) {
+        self.current = self.current.saturating_sub(1);
+    }
+
+    /// Lines for the ingredients the current step mentions. "Plain Flour" is
+    /// also found by "flour", since steps rarely repeat the full name.
+    pub fn reminders(&self) -> Vec<&str> {
+        let Some(step) = self.step() else {
+            return Vec::new();
+        };
+        self.ingredients
+            .iter()
+            .filter(|(name, _)| {
+                ingredient_matches(&step.text, name)
+                    || name.split_whitespace().last().is_some_and(|word| ingredient_matches(&step.text, word))
+            })
+            .map(|(_, line)| line.as_str())
+            .collect()
+    }
+}
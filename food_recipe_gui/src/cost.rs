@@ -0,0 +1,208 @@
+// Rough recipe prices from a user-editable price table, in the same spirit as
+// the nutrition estimate: good enough to compare recipes, not to budget to the cent.
+
+use crate::diet;
+use crate::measure::{estimate_grams, unit_grams};
+use crate::nutrition::{self, DEFAULT_SERVINGS};
+use recipe_core::quantity::Quantity;
+use recipe_core::MealDetail;
+use serde::{Deserialize, Serialize};
+
+/// Weight assumed for one piece of an ingredient the nutrition table doesn't know.
+const DEFAULT_PIECE_GRAMS: f64 = 100.0;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PriceUnit {
+    #[default]
+    Kilogram,
+    Piece,
+}
+
+impl PriceUnit {
+    pub const ALL: [PriceUnit; 2] = [PriceUnit::Kilogram, PriceUnit::Piece];
+
+    pub fn label(self) -> String {
+        match self {
+            PriceUnit::Kilogram => tr!("price-per-kg"),
+            PriceUnit::Piece => tr!("price-per-piece"),
+        }
+    }
+}
+
+/// What one unit of an ingredient costs. `ingredient` matches any recipe
+/// ingredient containing it as whole words, so "chicken" also prices
+/// "chicken thighs" but "egg" doesn't price "eggplant".
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Price {
+    pub ingredient: String,
+    pub unit: PriceUnit,
+    pub amount: f64,
+}
+
+impl Price {
+    fn new(ingredient: &str, unit: PriceUnit, amount: f64) -> Self {
+        Self {
+            ingredient: ingredient.to_string(),
+            unit,
+            amount,
+        }
+    }
+}
+
+/// Starting table for a new config; supermarket prices, roughly.
+pub fn default_prices() -> Vec<Price> {
+    use PriceUnit::{Kilogram, Piece};
+    vec![
+        Price::new("chicken breast", Kilogram, 9.0),
+        Price::new("chicken", Kilogram, 6.0),
+        Price::new("beef", Kilogram, 12.0),
+        Price::new("pork", Kilogram, 8.0),
+        Price::new("lamb", Kilogram, 15.0),
+        Price::new("salmon", Kilogram, 20.0),
+        Price::new("egg", Piece, 0.3),
+        Price::new("milk", Kilogram, 1.0),
+        Price::new("butter", Kilogram, 8.0),
+        Price::new("cheese", Kilogram, 10.0),
+        Price::new("cream", Kilogram, 4.0),
+        Price::new("flour", Kilogram, 0.8),
+        Price::new("sugar", Kilogram, 1.0),
+        Price::new("rice", Kilogram, 2.0),
+        Price::new("pasta", Kilogram, 2.0),
+        Price::new("spaghetti", Kilogram, 2.0),
+        Price::new("potato", Kilogram, 1.2),
+        Price::new("onion", Piece, 0.2),
+        Price::new("garlic", Piece, 0.1),
+        Price::new("tomato", Kilogram, 3.0),
+        Price::new("carrot", Kilogram, 1.0),
+        Price::new("lemon", Piece, 0.5),
+        Price::new("olive oil", Kilogram, 9.0),
+    ]
+}
+
+pub struct CostEstimate {
+    pub total: f64,
+    pub servings: u32,
+    /// Ingredients with no price in the table; they count as free.
+    pub unpriced: Vec<String>,
+}
+
+impl CostEstimate {
+    pub fn per_serving(&self) -> f64 {
+        self.total / f64::from(self.servings.max(1))
+    }
+}
+
+// Longest matching name wins, like the nutrition table
+fn lookup<'a>(ingredient: &str, prices: &'a [Price]) -> Option<&'a Price> {
+    let words = diet::words(ingredient);
+    prices
+        .iter()
+        .filter(|p| diet::in_list(&words, &[p.ingredient.as_str()]))
+        .max_by_key(|p| p.ingredient.trim().len())
+}
+
+fn ingredient_cost(ingredient: &str, measure: &str, price: &Price) -> f64 {
+    let piece = nutrition::piece_grams(ingredient).unwrap_or(DEFAULT_PIECE_GRAMS);
+    match price.unit {
+        PriceUnit::Kilogram => estimate_grams(measure, piece) / 1000.0 * price.amount,
+        PriceUnit::Piece => {
+            // "2" is two pieces; "200g" is however many pieces weigh that
+            let quantity = Quantity::parse(measure);
+            let pieces = match unit_grams(&quantity.unit) {
+                Some(_) => estimate_grams(measure, piece) / piece,
+                None => quantity.value.unwrap_or(1.0),
+            };
+            pieces * price.amount
+        }
+    }
+}
+
+/// Cost of one shopping list entry, all its measures together; `None` if
+/// there is no price for it.
+pub fn item_cost(ingredient: &str, measures: &[String], prices: &[Price]) -> Option<f64> {
+    let price = lookup(ingredient, prices)?;
+    if measures.is_empty() {
+        return Some(ingredient_cost(ingredient, "", price));
+    }
+    Some(measures.iter().map(|m| ingredient_cost(ingredient, m, price)).sum())
+}
+
+pub fn estimate(meal: &MealDetail, prices: &[Price]) -> CostEstimate {
+    let mut total = 0.0;
+    let mut unpriced = Vec::new();
+    for (i, ing) in meal.ingredients.iter().enumerate() {
+        let measure = meal.measures.get(i).map(String::as_str).unwrap_or("");
+        match lookup(ing, prices) {
+            Some(price) => total += ingredient_cost(ing, measure, price),
+            None => unpriced.push(ing.clone()),
+        }
+    }
+    CostEstimate {
+        total,
+        servings: meal.servings.filter(|&s| s > 0).unwrap_or(DEFAULT_SERVINGS),
+        unpriced,
+    }
+}
+
+pub fn format_cost(amount: f64, currency: &str) -> String {
+    format!("{}{:.2}", currency, amount)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn prices_whole_words_only() {
+        let prices = default_prices();
+        assert_eq!(lookup("Eggplant", &prices), None);
+        assert_eq!(lookup("Butternut squash", &prices), None);
+        assert_eq!(lookup("Eggs", &prices).map(|p| p.amount), Some(0.3));
+        assert_eq!(lookup("Chicken Breasts", &prices).map(|p| p.amount), Some(9.0));
+        assert_eq!(lookup("Chicken Thighs", &prices).map(|p| p.amount), Some(6.0));
+        assert_eq!(lookup("Salt", &[Price::new("  ", PriceUnit::Kilogram, 1.0)]), None);
+    }
+
+    #[test]
+    fn prices_pieces_by_count_or_weight() {
+        let egg = Price::new("egg", PriceUnit::Piece, 0.3);
+        assert!(close(ingredient_cost("Eggs", "3", &egg), 0.9));
+        assert!(close(ingredient_cost("Eggs", "", &egg), 0.3));
+        // 100 g is two 50 g eggs
+        assert!(close(ingredient_cost("Eggs", "100g", &egg), 0.6));
+        let flour = Price::new("flour", PriceUnit::Kilogram, 0.8);
+        assert!(close(ingredient_cost("Flour", "500g", &flour), 0.4));
+    }
+
+    #[test]
+    fn adds_up_an_items_measures() {
+        let prices = default_prices();
+        let measures = ["500g".to_string(), "1 kg".to_string()];
+        assert!(close(item_cost("Flour", &measures, &prices).unwrap(), 1.2));
+        assert!(close(item_cost("Lemon", &[], &prices).unwrap(), 0.5));
+        assert_eq!(item_cost("Saffron", &measures, &prices), None);
+    }
+
+    #[test]
+    fn estimates_per_serving() {
+        let meal = MealDetail {
+            ingredients: vec!["Flour".into(), "Eggs".into(), "Eggplant".into()],
+            measures: vec!["1 kg".into(), "4".into(), "1".into()],
+            servings: Some(2),
+            ..Default::default()
+        };
+        let cost = estimate(&meal, &default_prices());
+        assert!(close(cost.total, 0.8 + 1.2));
+        assert!(close(cost.per_serving(), 1.0));
+        assert_eq!(cost.unpriced, ["Eggplant"]);
+        let unknown = MealDetail {
+            servings: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(estimate(&unknown, &[]).servings, DEFAULT_SERVINGS);
+    }
+}
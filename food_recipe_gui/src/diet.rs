@@ -0,0 +1,87 @@
+// Rough vegetarian/vegan check from ingredient names alone. Providers rarely
+// label recipes reliably, so each ingredient is classified against the lists
+// below; a single hit rules the recipe out.
+
+use recipe_core::MealDetail;
+use recipe_core::matching::normalize;
+use serde::{Deserialize, Serialize};
+
+/// Meat, poultry and the fats and stocks made from them.
+const MEAT: &[&str] = &[
+    "beef", "steak", "mince", "veal", "pork", "bacon", "ham", "prosciutto", "pancetta", "chorizo", "salami",
+    "pepperoni", "sausage", "lamb", "mutton", "chicken", "turkey", "duck", "goose", "venison", "rabbit",
+    "liver", "kidney", "oxtail", "lard", "suet", "gelatin", "gelatine", "meat", "brisket", "chuck", "sirloin",
+];
+
+const FISH: &[&str] = &[
+    "fish", "salmon", "tuna", "cod", "haddock", "trout", "mackerel", "sardine", "anchovy", "anchovies", "herring",
+    "prawn", "shrimp", "crab", "lobster", "mussel", "clam", "oyster", "scallop", "squid", "octopus", "monkfish",
+    "halibut", "tilapia", "sea bass", "kipper", "caviar",
+];
+
+/// Fine for vegetarians, not for vegans.
+const ANIMAL_PRODUCTS: &[&str] = &[
+    "milk", "butter", "cream", "cheese", "yogurt", "yoghurt", "egg", "honey", "ghee", "buttermilk", "mozzarella",
+    "parmesan", "cheddar", "feta", "ricotta", "mascarpone", "creme fraiche", "custard", "mayonnaise", "whey",
+];
+
+/// Plant-based ingredients whose names contain one of the words above.
+const PLANT_BASED: &[&str] = &[
+    "vegetable stock", "coconut milk", "coconut cream", "almond milk", "soy milk", "oat milk", "peanut butter",
+    "almond butter", "cocoa butter", "butter bean", "kidney bean", "cream of tartar", "egg replacer", "vegan",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Diet {
+    Vegetarian,
+    Vegan,
+}
+
+/// Normalized words of an ingredient name, for matching against the lists.
+pub fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(normalize)
+        .collect()
+}
+
+// Whole words only, so "egg" doesn't catch "eggplant"
+fn contains_phrase(ingredient: &[String], phrase: &str) -> bool {
+    let phrase = words(phrase);
+    !phrase.is_empty() && ingredient.windows(phrase.len()).any(|w| w == phrase.as_slice())
+}
+
+/// Whether any entry of `list` appears in the ingredient's `words`.
+pub fn in_list(ingredient: &[String], list: &[&str]) -> bool {
+    list.iter().any(|entry| contains_phrase(ingredient, entry))
+}
+
+impl Diet {
+    pub fn label(self) -> &'static str {
+        match self {
+            Diet::Vegetarian => "Vegetarian",
+            Diet::Vegan => "Vegan",
+        }
+    }
+
+    fn allows(self, ingredient: &str) -> bool {
+        let ingredient = words(ingredient);
+        if in_list(&ingredient, PLANT_BASED) {
+            return true;
+        }
+        let not_vegetarian = in_list(&ingredient, MEAT) || in_list(&ingredient, FISH);
+        match self {
+            Diet::Vegetarian => !not_vegetarian,
+            Diet::Vegan => !not_vegetarian && !in_list(&ingredient, ANIMAL_PRODUCTS),
+        }
+    }
+
+    /// Ingredients of `meal` the diet rules out; empty if it fits.
+    pub fn violations(self, meal: &MealDetail) -> Vec<String> {
+        meal.ingredients
+            .iter()
+            .filter(|ing| !self.allows(ing))
+            .cloned()
+            .collect()
+    }
+}
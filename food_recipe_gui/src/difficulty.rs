@@ -0,0 +1,88 @@
+// A rough Easy/Medium/Hard from what a recipe asks of the cook: how much
+// goes in, how many steps there are and whether the instructions mention
+// techniques that take practice.
+
+use crate::diet::{in_list, words};
+use crate::steps;
+use recipe_core::MealDetail;
+use serde::{Deserialize, Serialize};
+
+/// Techniques that make a recipe harder, as they appear in instructions.
+const TECHNIQUES: &[&str] = &[
+    "knead", "prove", "temper", "flambe", "flambé", "deglaze", "braise", "emulsify", "caramelise", "caramelize",
+    "julienne", "fold in", "blind bake", "deep fry", "roux", "poach", "laminate", "pipe", "confit", "sous vide",
+    "debone", "butterfly", "clarify", "stiff peaks",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn label(self) -> String {
+        match self {
+            Difficulty::Easy => tr!("difficulty-easy"),
+            Difficulty::Medium => tr!("difficulty-medium"),
+            Difficulty::Hard => tr!("difficulty-hard"),
+        }
+    }
+
+    /// Untranslated, for the search history and the log.
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+        }
+    }
+}
+
+/// The rating and what it is based on, for the badge's tooltip.
+pub struct Assessment {
+    pub difficulty: Difficulty,
+    pub ingredients: usize,
+    pub steps: usize,
+    pub techniques: Vec<&'static str>,
+}
+
+impl Assessment {
+    pub fn summary(&self) -> String {
+        let techniques = if self.techniques.is_empty() { "-".to_string() } else { self.techniques.join(", ") };
+        tr!("difficulty-summary", ingredients = self.ingredients, steps = self.steps, techniques = techniques)
+    }
+}
+
+/// Up to two points each for ingredients and steps, one per technique up to
+/// three: Easy up to 1 point, Medium up to 3, Hard above.
+pub fn assess(meal: &MealDetail) -> Assessment {
+    let ingredients = meal.ingredients.len();
+    let steps = steps::parse_steps(&meal.instructions).len();
+    let instruction_words = words(&meal.instructions);
+    let techniques: Vec<&'static str> = TECHNIQUES
+        .iter()
+        .copied()
+        .filter(|technique| in_list(&instruction_words, &[*technique]))
+        .collect();
+    let points = match ingredients {
+        0..=7 => 0,
+        8..=12 => 1,
+        _ => 2,
+    } + match steps {
+        0..=5 => 0,
+        6..=10 => 1,
+        _ => 2,
+    } + techniques.len().min(3);
+    let difficulty = match points {
+        0..=1 => Difficulty::Easy,
+        2..=3 => Difficulty::Medium,
+        _ => Difficulty::Hard,
+    };
+    Assessment { difficulty, ingredients, steps, techniques }
+}
+
+pub fn rate(meal: &MealDetail) -> Difficulty {
+    assess(meal).difficulty
+}
@@ -0,0 +1,87 @@
+use crate::steps;
+use recipe_core::MealDetail;
+use std::{fs, io, path::Path};
+
+// Embedded so the file stands alone. Printing shrinks the photo, drops the
+// page margins and link colours, and never splits an ingredient or step.
+const STYLE: &str = "\
+body { font-family: Georgia, 'Times New Roman', serif; max-width: 42em; margin: 2em auto; padding: 0 1em; color: #222; line-height: 1.5; }
+h1 { margin-bottom: 0.2em; }
+.meta { color: #555; margin-top: 0; }
+img { max-width: 100%; max-height: 20em; border-radius: 6px; }
+ul.ingredients { columns: 2; padding-left: 1.2em; }
+li { break-inside: avoid; margin-bottom: 0.3em; }
+.measure { font-weight: bold; }
+@media print {
+  body { margin: 0; max-width: none; font-size: 11pt; color: #000; }
+  img { max-height: 12em; }
+  a { color: inherit; text-decoration: none; }
+  h2 { break-after: avoid; }
+}";
+
+/// Escapes text for use in HTML element content and quoted attributes.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+pub fn render(meal: &MealDetail) -> String {
+    let title = escape(&meal.title);
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>\n{}\n</style>\n</head>\n<body>\n<h1>{}</h1>\n",
+        title, STYLE, title
+    );
+    let meta: Vec<String> = [&meal.category, &meal.area]
+        .iter()
+        .filter(|s| !s.is_empty())
+        .map(|s| escape(s))
+        .collect();
+    if !meta.is_empty() {
+        html.push_str(&format!("<p class=\"meta\">{}</p>\n", meta.join(" · ")));
+    }
+    if !meal.thumb.is_empty() {
+        html.push_str(&format!("<img src=\"{}\" alt=\"{}\">\n", escape(&meal.thumb), title));
+    }
+
+    html.push_str("<h2>Ingredients</h2>\n<ul class=\"ingredients\">\n");
+    for (i, ing) in meal.ingredients.iter().enumerate() {
+        match meal.measures.get(i).filter(|m| !m.is_empty()) {
+            Some(measure) => html.push_str(&format!(
+                "<li><span class=\"measure\">{}</span> {}</li>\n",
+                escape(measure),
+                escape(ing)
+            )),
+            None => html.push_str(&format!("<li>{}</li>\n", escape(ing))),
+        }
+    }
+    html.push_str("</ul>\n");
+
+    html.push_str("<h2>Instructions</h2>\n<ol>\n");
+    for step in steps::parse_steps(&meal.instructions) {
+        html.push_str(&format!("<li>{}</li>\n", escape(&step.text)));
+    }
+    html.push_str("</ol>\n");
+
+    if !meal.source.is_empty() {
+        html.push_str(&format!(
+            "<p class=\"meta\">Source: <a href=\"{0}\">{0}</a></p>\n",
+            escape(&meal.source)
+        ));
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+pub fn export(path: &Path, meal: &MealDetail) -> io::Result<()> {
+    fs::write(path, render(meal))
+}
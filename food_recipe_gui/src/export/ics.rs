@@ -0,0 +1,141 @@
+// The meal plan as an iCalendar file (RFC 5545): one all-day event per
+// planned meal, which Google Calendar, Outlook and Apple Calendar import.
+// Written by hand; the few rules that matter are escaping and line folding.
+
+use crate::meal_plan::PlannedMeal;
+use chrono::{DateTime, Utc};
+use std::{fs, io, path::Path};
+
+const PRODID: &str = "-//Food Recipe Finder//Meal Plan//EN";
+/// Longest content line allowed, in octets, before it has to be folded.
+const MAX_LINE: usize = 75;
+
+// Backslashes, semicolons, commas and newlines are special in TEXT values
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            ';' => out.push_str("\\;"),
+            ',' => out.push_str("\\,"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+// Long lines continue on the next one after a space, never inside a character
+fn fold(line: &str) -> String {
+    let mut out = String::with_capacity(line.len() + line.len() / MAX_LINE * 3);
+    let mut length = 0;
+    for c in line.chars() {
+        if length + c.len_utf8() > MAX_LINE {
+            out.push_str("\r\n ");
+            // The space starts the continuation line
+            length = 1;
+        }
+        out.push(c);
+        length += c.len_utf8();
+    }
+    out
+}
+
+// Unique per day and recipe, so importing the file again updates events
+// instead of duplicating them
+fn uid(planned: &PlannedMeal) -> String {
+    let id: String = planned
+        .meal_id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    format!("{}-{}@food-recipe-finder", planned.day.format("%Y%m%d"), id)
+}
+
+pub fn render(plan: &[PlannedMeal], stamp: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{}", PRODID),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+    for planned in plan {
+        let end = planned.day.succ_opt().unwrap_or(planned.day);
+        lines.extend([
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:{}", uid(planned)),
+            format!("DTSTAMP:{}", stamp.format("%Y%m%dT%H%M%SZ")),
+            format!("DTSTART;VALUE=DATE:{}", planned.day.format("%Y%m%d")),
+            // All-day events end on the next day, exclusively
+            format!("DTEND;VALUE=DATE:{}", end.format("%Y%m%d")),
+            format!("SUMMARY:{}", escape(&planned.title)),
+            "TRANSP:TRANSPARENT".to_string(),
+            "END:VEVENT".to_string(),
+        ]);
+    }
+    lines.push("END:VCALENDAR".to_string());
+    lines.iter().map(|line| fold(line) + "\r\n").collect()
+}
+
+pub fn export(path: &Path, plan: &[PlannedMeal]) -> io::Result<()> {
+    fs::write(path, render(plan, Utc::now()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, TimeZone};
+
+    fn planned(day: u32, meal_id: &str, title: &str) -> PlannedMeal {
+        PlannedMeal {
+            day: NaiveDate::from_ymd_opt(2026, 3, day).unwrap(),
+            meal_id: meal_id.to_string(),
+            title: title.to_string(),
+        }
+    }
+
+    fn stamp() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 1, 12, 30, 0).unwrap()
+    }
+
+    #[test]
+    fn escapes_special_characters() {
+        assert_eq!(escape("Fish, chips; peas"), "Fish\\, chips\\; peas");
+        assert_eq!(escape("a\\b"), "a\\\\b");
+        assert_eq!(escape("two\r\nlines"), "two\\nlines");
+    }
+
+    #[test]
+    fn folds_long_lines_without_splitting_characters() {
+        let line = format!("SUMMARY:{}", "Crème brûlée ".repeat(12));
+        let folded = fold(&line);
+        for part in folded.split("\r\n") {
+            assert!(part.len() <= MAX_LINE, "{:?} is {} octets", part, part.len());
+        }
+        assert_eq!(folded.replace("\r\n ", ""), line);
+        assert_eq!(fold("SUMMARY:Short"), "SUMMARY:Short");
+    }
+
+    #[test]
+    fn one_all_day_event_per_planned_meal() {
+        let plan = [planned(2, "52772", "Teriyaki Chicken"), planned(31, "52772", "Teriyaki Chicken")];
+        let ics = render(&plan, stamp());
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 2);
+        assert!(ics.contains("DTSTART;VALUE=DATE:20260302\r\nDTEND;VALUE=DATE:20260303\r\n"));
+        // The end rolls over into the next month
+        assert!(ics.contains("DTSTART;VALUE=DATE:20260331\r\nDTEND;VALUE=DATE:20260401\r\n"));
+        assert!(ics.contains("DTSTAMP:20260301T123000Z\r\n"));
+        assert!(ics.contains("UID:20260302-52772@food-recipe-finder\r\n"));
+        assert!(!ics.replace("\r\n", "").contains('\n'));
+    }
+
+    #[test]
+    fn titles_are_escaped_and_ids_made_safe() {
+        let ics = render(&[planned(5, "spoonacular:716429", "Pasta, with garlic")], stamp());
+        assert!(ics.contains("SUMMARY:Pasta\\, with garlic\r\n"));
+        assert!(ics.contains("UID:20260305-spoonacular-716429@food-recipe-finder\r\n"));
+    }
+}
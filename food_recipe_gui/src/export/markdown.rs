@@ -0,0 +1,34 @@
+use crate::steps;
+use recipe_core::MealDetail;
+use std::{fs, io, path::Path};
+
+pub fn render(meal: &MealDetail) -> String {
+    let mut md = format!("# {}\n\n", meal.title);
+    if !meal.thumb.is_empty() {
+        md.push_str(&format!("![{}]({})\n\n", meal.title, meal.thumb));
+    }
+    if !meal.category.is_empty() {
+        md.push_str(&format!("**Category:** {}  \n", meal.category));
+    }
+    if !meal.area.is_empty() {
+        md.push_str(&format!("**Area:** {}  \n", meal.area));
+    }
+
+    md.push_str("\n## Ingredients\n\n");
+    for (i, ing) in meal.ingredients.iter().enumerate() {
+        match meal.measures.get(i).filter(|m| !m.is_empty()) {
+            Some(measure) => md.push_str(&format!("- {} {}\n", measure, ing)),
+            None => md.push_str(&format!("- {}\n", ing)),
+        }
+    }
+
+    md.push_str("\n## Instructions\n\n");
+    for (i, step) in steps::parse_steps(&meal.instructions).iter().enumerate() {
+        md.push_str(&format!("{}. {}\n", i + 1, step.text));
+    }
+    md
+}
+
+pub fn export(path: &Path, meal: &MealDetail) -> io::Result<()> {
+    fs::write(path, render(meal))
+}
@@ -0,0 +1,30 @@
+pub mod csv;
+pub mod html;
+pub mod ics;
+pub mod markdown;
+pub mod pdf;
+pub mod tasks;
+
+use std::path::PathBuf;
+
+/// File name derived from a recipe title, safe on every platform.
+pub fn file_stem(title: &str) -> String {
+    let stem: String = title
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    let stem = stem.trim_matches('_').to_string();
+    if stem.is_empty() {
+        "recipe".to_string()
+    } else {
+        stem
+    }
+}
+
+/// Asks the user where to save, suggesting `<title>.<extension>`.
+pub fn save_dialog(title: &str, extension: &str, filter_name: &str) -> Option<PathBuf> {
+    rfd::FileDialog::new()
+        .set_file_name(format!("{}.{}", file_stem(title), extension))
+        .add_filter(filter_name, &[extension])
+        .save_file()
+}
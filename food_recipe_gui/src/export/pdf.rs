@@ -0,0 +1,127 @@
+use crate::steps;
+use printpdf::{BuiltinFont, Mm, PdfDocument};
+use recipe_core::MealDetail;
+use std::{
+    fs::File,
+    io::{self, BufWriter},
+    path::Path,
+};
+
+const PAGE_W: f32 = 210.0;
+const PAGE_H: f32 = 297.0;
+const MARGIN: f32 = 15.0;
+const PT_TO_MM: f32 = 0.3528;
+const LINE_SPACING: f32 = 1.35;
+const MAX_BODY_SIZE: f32 = 11.0;
+const MIN_BODY_SIZE: f32 = 6.0;
+
+struct PdfLine {
+    text: String,
+    size: f32,
+    bold: bool,
+    gap_before: f32,
+}
+
+impl PdfLine {
+    fn height(&self) -> f32 {
+        self.gap_before + self.size * PT_TO_MM * LINE_SPACING
+    }
+}
+
+// Helvetica averages roughly half an em per character
+fn chars_per_line(width_mm: f32, font_size: f32) -> usize {
+    ((width_mm / (font_size * PT_TO_MM * 0.5)) as usize).max(10)
+}
+
+fn wrap(text: &str, max_chars: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        if !current.is_empty() && current.chars().count() + 1 + word.chars().count() > max_chars {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+fn push_wrapped(lines: &mut Vec<PdfLine>, text: &str, size: f32, bold: bool, gap_before: f32, indent: &str) {
+    let max = chars_per_line(PAGE_W - 2.0 * MARGIN, size);
+    for (i, part) in wrap(text, max).into_iter().enumerate() {
+        lines.push(PdfLine {
+            text: if i == 0 { part } else { format!("{}{}", indent, part) },
+            size,
+            bold,
+            gap_before: if i == 0 { gap_before } else { 0.0 },
+        });
+    }
+}
+
+fn layout(meal: &MealDetail, body: f32) -> Vec<PdfLine> {
+    let mut lines = Vec::new();
+    push_wrapped(&mut lines, &meal.title, body * 2.0, true, 0.0, "");
+
+    let meta: Vec<&str> = [meal.category.as_str(), meal.area.as_str()]
+        .into_iter()
+        .filter(|s| !s.is_empty())
+        .collect();
+    if !meta.is_empty() {
+        push_wrapped(&mut lines, &meta.join(" · "), body, false, 1.0, "");
+    }
+
+    push_wrapped(&mut lines, "Ingredients", body * 1.3, true, body * 0.6, "");
+    for (i, ing) in meal.ingredients.iter().enumerate() {
+        let text = match meal.measures.get(i).filter(|m| !m.is_empty()) {
+            Some(measure) => format!("- {} {}", measure, ing),
+            None => format!("- {}", ing),
+        };
+        push_wrapped(&mut lines, &text, body, false, 0.0, "  ");
+    }
+
+    push_wrapped(&mut lines, "Instructions", body * 1.3, true, body * 0.6, "");
+    for (i, step) in steps::parse_steps(&meal.instructions).iter().enumerate() {
+        push_wrapped(&mut lines, &format!("{}. {}", i + 1, step.text), body, false, 0.8, "    ");
+    }
+    lines
+}
+
+/// Writes a single A4 recipe card. The body font shrinks until everything
+/// fits; whatever still doesn't fit at the minimum size is cut off.
+pub fn export(path: &Path, meal: &MealDetail) -> io::Result<()> {
+    let mut body = MAX_BODY_SIZE;
+    let mut lines = layout(meal, body);
+    while body > MIN_BODY_SIZE && lines.iter().map(PdfLine::height).sum::<f32>() > PAGE_H - 2.0 * MARGIN {
+        body -= 0.5;
+        lines = layout(meal, body);
+    }
+
+    let (doc, page, layer) = PdfDocument::new(&meal.title, Mm(PAGE_W), Mm(PAGE_H), "Recipe");
+    let layer = doc.get_page(page).get_layer(layer);
+    let regular = doc
+        .add_builtin_font(BuiltinFont::Helvetica)
+        .map_err(|e| io::Error::other(e.to_string()))?;
+    let bold = doc
+        .add_builtin_font(BuiltinFont::HelveticaBold)
+        .map_err(|e| io::Error::other(e.to_string()))?;
+
+    let mut y = PAGE_H - MARGIN;
+    for line in &lines {
+        y -= line.height();
+        if y < MARGIN {
+            break;
+        }
+        let font = if line.bold { &bold } else { &regular };
+        layer.use_text(line.text.as_str(), line.size, Mm(MARGIN), Mm(y), font);
+    }
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    doc.save(&mut writer)
+        .map_err(|e| io::Error::other(e.to_string()))?;
+    Ok(())
+}
@@ -0,0 +1,71 @@
+// Appends shopping items to console_task_manager_console's tasks.json, so
+// they show up in that app's todo list next to everything else.
+
+use crate::shopping::{self, ShoppingItem};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, File},
+    io::{self, BufReader, Write},
+    path::Path,
+};
+
+/// Same fields and layout as the task manager's own `Task`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct Task {
+    id: u64,
+    title: String,
+    done: bool,
+    created_at: DateTime<Local>,
+}
+
+fn load_tasks(path: &Path) -> io::Result<Vec<Task>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let f = File::open(path)?;
+    // Unlike the task manager, refuse to go on with a file we can't read:
+    // saving would replace the user's tasks with ours
+    serde_json::from_reader(BufReader::new(f)).map_err(io::Error::other)
+}
+
+fn save_tasks(path: &Path, tasks: &[Task]) -> io::Result<()> {
+    let tmp = path.with_extension("json.tmp");
+    let mut f = File::create(&tmp)?;
+    let json = serde_json::to_string_pretty(tasks).map_err(io::Error::other)?;
+    f.write_all(json.as_bytes())?;
+    f.flush()?;
+    fs::rename(tmp, path)?;
+    Ok(())
+}
+
+fn task_title(item: &ShoppingItem) -> String {
+    format!("Buy {}", shopping::item_line(item))
+}
+
+/// Adds one open task per item and returns how many were added. Items that
+/// already have an open task with the same title are skipped, so pressing
+/// the button twice doesn't double the list.
+pub fn append(path: &Path, items: &[ShoppingItem]) -> io::Result<usize> {
+    let mut tasks = load_tasks(path)?;
+    let mut next_id = tasks.iter().map(|t| t.id).max().unwrap_or(0) + 1;
+    let mut added = 0;
+    for item in items {
+        let title = task_title(item);
+        if tasks.iter().any(|t| !t.done && t.title == title) {
+            continue;
+        }
+        tasks.push(Task {
+            id: next_id,
+            title,
+            done: false,
+            created_at: Local::now(),
+        });
+        next_id += 1;
+        added += 1;
+    }
+    if added > 0 {
+        save_tasks(path, &tasks)?;
+    }
+    Ok(added)
+}
@@ -0,0 +1,59 @@
+use recipe_core::MealDetail;
+use recipe_core::db::{Database, DEFAULT_PROFILE};
+
+// Favorites keep the full meal so they can be browsed without re-searching
+pub struct Favorites {
+    /// Profile the list belongs to and is saved under.
+    profile: String,
+    meals: Vec<MealDetail>,
+}
+
+impl Default for Favorites {
+    fn default() -> Self {
+        Self {
+            profile: DEFAULT_PROFILE.to_string(),
+            meals: Vec::new(),
+        }
+    }
+}
+
+impl Favorites {
+    pub fn load(db: &Database, profile: &str) -> rusqlite::Result<Self> {
+        Ok(Self {
+            profile: profile.to_string(),
+            meals: db.load_favorites(profile)?,
+        })
+    }
+
+    pub fn save(&self, db: &Database) -> rusqlite::Result<()> {
+        db.save_favorites(&self.profile, &self.meals)
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.meals.iter().any(|m| m.id == id)
+    }
+
+    pub fn get(&self, id: &str) -> Option<&MealDetail> {
+        self.meals.iter().find(|m| m.id == id)
+    }
+
+    /// Adds the meal if it isn't a favorite yet, removes it otherwise.
+    /// Returns whether the meal is a favorite afterwards.
+    pub fn toggle(&mut self, meal: &MealDetail) -> bool {
+        if self.contains(&meal.id) {
+            self.meals.retain(|m| m.id != meal.id);
+            false
+        } else {
+            self.meals.push(meal.clone());
+            true
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &MealDetail> {
+        self.meals.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.meals.is_empty()
+    }
+}
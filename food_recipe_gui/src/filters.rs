@@ -0,0 +1,32 @@
+use crate::ingredient_list::IngredientList;
+use recipe_core::MealDetail;
+use recipe_core::matching::ingredient_matches;
+
+/// Name of the allergy list in the database.
+pub const ALLERGIES_LIST: &str = "allergies";
+
+/// Ingredients that must never end up on the plate.
+pub type Allergies = IngredientList;
+
+/// The excluded terms that appear in any of the meal's ingredients.
+pub fn excluded_matches(meal: &MealDetail, excluded: &[String]) -> Vec<String> {
+    excluded
+        .iter()
+        .filter(|term| {
+            // Err on the side of excluding: "nut" must still catch "peanuts"
+            let term_lower = term.to_lowercase();
+            meal.ingredients
+                .iter()
+                .any(|ing| ing.to_lowercase().contains(&term_lower) || ingredient_matches(ing, term))
+        })
+        .cloned()
+        .collect()
+}
+
+/// Whether the instructions contain `phrase`, ignoring case and extra
+/// spaces, e.g. "slow cooker". A blank phrase matches everything.
+pub fn instructions_mention(meal: &MealDetail, phrase: &str) -> bool {
+    let squash = |text: &str| text.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    let phrase = squash(phrase);
+    phrase.is_empty() || squash(&meal.instructions).contains(&phrase)
+}
@@ -0,0 +1,297 @@
+// `--headless`: runs one search from command line flags and prints the ranked
+// results instead of opening a window, so searches can be scripted. Uses the
+// same config, database, providers and scoring as the GUI.
+
+use crate::config::{AppConfig, CONFIG_FILE};
+use crate::diet::Diet;
+use crate::difficulty::Difficulty;
+use crate::filters::Allergies;
+use crate::my_recipes::MyRecipes;
+use crate::pantry::{Pantry, PANTRY_LIST};
+use crate::profiles::{self, Profiles};
+use crate::ratings::Ratings;
+use crate::search::{self, SearchEvent, SearchQuery, SortOrder};
+use crate::{build_providers, i18n, open_database};
+use eframe::egui;
+use recipe_core::db::{Database, DEFAULT_PROFILE};
+use recipe_core::MealDetail;
+use std::sync::{mpsc, Arc};
+use tokio_util::sync::CancellationToken;
+
+pub const FLAG: &str = "--headless";
+
+const USAGE: &str = "\
+Usage: food_recipe_finder_gui --headless [OPTIONS]
+
+Search:
+  --main <a,b,...>      main ingredients, all required
+  --sub <a,b,...>       sub ingredients, any of them
+  --taste <word>        taste, e.g. spicy
+  --name <dish>         search by dish name instead of ingredients
+  --pantry              cook from the saved pantry
+  --exclude <a,b,...>   skip recipes with these (saved allergies always apply)
+  --category <name>     only this category
+  --area <name>         only this area
+  --diet <diet>         vegetarian or vegan
+  --max-cost <amount>   skip recipes estimated to cost more in total
+  --max-kcal <kcal>     skip recipes estimated above this per serving
+  --max-ingredients <n> skip recipes with more ingredients
+  --max-minutes <n>     skip recipes estimated to take longer
+  --max-difficulty <d>  easy, medium or hard
+  --provider <name>     TheMealDB (default), Spoonacular or Edamam
+  --profile <name>      whose allergies, taste and diet apply (default: Default)
+
+Output:
+  --sort <order>        score (default), title, category, area, ingredients or rating
+  --limit <n>           print at most n results (default 10, 0 for all)
+  --format <format>     table (default) or json
+  -h, --help            show this help";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Table,
+    Json,
+}
+
+struct Options {
+    query: SearchQuery,
+    from_pantry: bool,
+    provider: Option<String>,
+    profile: String,
+    sort: SortOrder,
+    limit: usize,
+    format: Format,
+}
+
+pub fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+fn parse_sort(value: &str) -> Option<SortOrder> {
+    match value.to_lowercase().as_str() {
+        "score" => Some(SortOrder::Score),
+        "title" => Some(SortOrder::Title),
+        "category" => Some(SortOrder::Category),
+        "area" => Some(SortOrder::Area),
+        "ingredients" => Some(SortOrder::IngredientCount),
+        "rating" => Some(SortOrder::Rating),
+        _ => None,
+    }
+}
+
+fn parse_diet(value: &str) -> Option<Diet> {
+    match value.to_lowercase().as_str() {
+        "vegetarian" => Some(Diet::Vegetarian),
+        "vegan" => Some(Diet::Vegan),
+        _ => None,
+    }
+}
+
+fn parse_difficulty(value: &str) -> Option<Difficulty> {
+    match value.to_lowercase().as_str() {
+        "easy" => Some(Difficulty::Easy),
+        "medium" => Some(Difficulty::Medium),
+        "hard" => Some(Difficulty::Hard),
+        _ => None,
+    }
+}
+
+// Ok(None) means help was asked for
+fn parse_args(args: &[String], config: &AppConfig) -> Result<Option<Options>, String> {
+    let mut options = Options {
+        query: SearchQuery::default(),
+        from_pantry: false,
+        provider: None,
+        profile: DEFAULT_PROFILE.to_string(),
+        sort: config.default_sort,
+        limit: 10,
+        format: Format::Table,
+    };
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        if flag == "-h" || flag == "--help" {
+            return Ok(None);
+        }
+        if flag == "--pantry" {
+            options.from_pantry = true;
+            continue;
+        }
+        let value = args.next().ok_or_else(|| format!("{} needs a value", flag))?;
+        let query = &mut options.query;
+        match flag.as_str() {
+            "--main" => query.main = split_list(value),
+            "--sub" => query.sub = split_list(value),
+            "--taste" => query.taste = Some(value.clone()),
+            "--name" => query.name = Some(value.clone()),
+            "--exclude" => query.excluded = split_list(value),
+            "--category" => query.category = Some(value.clone()),
+            "--area" => query.area = Some(value.clone()),
+            "--diet" => query.diet = Some(parse_diet(value).ok_or_else(|| format!("unknown diet: {}", value))?),
+            "--max-cost" => query.max_cost = Some(value.parse().map_err(|_| format!("not a number: {}", value))?),
+            "--max-kcal" => query.max_calories = Some(value.parse().map_err(|_| format!("not a number: {}", value))?),
+            "--max-ingredients" => {
+                query.max_ingredients = Some(value.parse().map_err(|_| format!("not a number: {}", value))?)
+            }
+            "--max-difficulty" => {
+                query.max_difficulty =
+                    Some(parse_difficulty(value).ok_or_else(|| format!("unknown difficulty: {}", value))?)
+            }
+            "--max-minutes" => {
+                query.max_minutes = Some(value.parse().map_err(|_| format!("not a number: {}", value))?)
+            }
+            "--provider" => options.provider = Some(value.clone()),
+            "--profile" => options.profile = value.clone(),
+            "--sort" => options.sort = parse_sort(value).ok_or_else(|| format!("unknown sort order: {}", value))?,
+            "--limit" => options.limit = value.parse().map_err(|_| format!("not a number: {}", value))?,
+            "--format" => {
+                options.format = match value.as_str() {
+                    "table" => Format::Table,
+                    "json" => Format::Json,
+                    _ => return Err(format!("unknown format: {}", value)),
+                }
+            }
+            _ => return Err(format!("unknown option: {}", flag)),
+        }
+    }
+    Ok(Some(options))
+}
+
+fn print_table(meals: &[MealDetail]) {
+    println!("{:>4}  {:>5}  {:<40}  {:<14}  area", "#", "score", "title", "category");
+    for (i, meal) in meals.iter().enumerate() {
+        let title: String = meal.title.chars().take(40).collect();
+        println!(
+            "{:>4}  {:>5}  {:<40}  {:<14}  {}",
+            i + 1,
+            meal.score,
+            title,
+            meal.category,
+            meal.area
+        );
+    }
+}
+
+/// Fills in what a search from the window takes from saved state: the
+/// pantry counts as on hand, the profile's allergies are excluded and its
+/// taste and diet fill in what the query leaves open, prices and scoring
+/// come from the config.
+pub fn add_saved_inputs(query: &mut SearchQuery, db: &Database, config: &AppConfig, profile: &str) {
+    query.on_hand = Pantry::load(db, PANTRY_LIST).unwrap_or_default().items().to_vec();
+    let allergies = Allergies::load(db, &profiles::allergies_list(profile)).unwrap_or_default();
+    query.excluded.extend(allergies.items().iter().cloned());
+    if let Some(profile) = Profiles::load(db).unwrap_or_default().get(profile) {
+        if query.taste.is_none() && !profile.taste.trim().is_empty() {
+            query.taste = Some(profile.taste.trim().to_string());
+        }
+        query.diet = query.diet.or(profile.diet);
+        query.nutrition_goals = profile.nutrition_goals;
+    }
+    query.prices = config.prices.clone();
+    query.weights = config.scoring.clone();
+    query.strategy = config.scoring_strategy;
+}
+
+/// The user's own recipes the query selects, scored. They never need the network.
+pub fn own_recipes(db: &Database, query: &SearchQuery) -> Vec<MealDetail> {
+    MyRecipes::load(db)
+        .unwrap_or_default()
+        .iter()
+        .filter(|r| query.selects(r))
+        .map(|r| {
+            let mut recipe = r.clone();
+            recipe.score = query.score(&recipe);
+            recipe
+        })
+        .collect()
+}
+
+/// Runs the headless search for the arguments after `--headless` and
+/// returns the process exit code.
+pub fn run(args: &[String]) -> i32 {
+    let config = AppConfig::load(CONFIG_FILE).unwrap_or_default();
+    i18n::set_language(config.language);
+    let mut options = match parse_args(args, &config) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return 0;
+        }
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return 2;
+        }
+    };
+
+    let db = open_database(&config);
+    let providers = build_providers(&config, &db);
+    let provider = match &options.provider {
+        None => Arc::clone(&providers[0]),
+        Some(name) => match providers.iter().find(|p| p.name().eq_ignore_ascii_case(name)) {
+            Some(provider) => Arc::clone(provider),
+            None => {
+                eprintln!("Provider {} isn't available; check the API keys in {}", name, CONFIG_FILE);
+                return 2;
+            }
+        },
+    };
+
+    let query = &mut options.query;
+    if options.from_pantry {
+        let items = Pantry::load(&db, PANTRY_LIST).unwrap_or_default().items().to_vec();
+        query.main = items.clone();
+        query.pantry = Some(items);
+    }
+    if Profiles::load(&db).unwrap_or_default().get(&options.profile).is_none() {
+        eprintln!("No profile named {}", options.profile);
+        return 2;
+    }
+    add_saved_inputs(query, &db, &config, &options.profile);
+    let has_input = query.name.is_some() || !query.main.is_empty() || !query.sub.is_empty();
+    if !has_input && query.category.is_none() && query.area.is_none() {
+        eprintln!("Nothing to search for\n\n{}", USAGE);
+        return 2;
+    }
+
+    let mut results = own_recipes(&db, &options.query);
+
+    let runtime = tokio::runtime::Runtime::new().expect("failed to start tokio runtime");
+    let (tx, rx) = mpsc::channel();
+    // Nothing is drawn; the context only absorbs repaint requests
+    runtime.block_on(search::run_search(
+        provider,
+        options.query.clone(),
+        tx,
+        CancellationToken::new(),
+        egui::Context::default(),
+    ));
+    let mut failed = false;
+    for event in rx.try_iter() {
+        match event {
+            SearchEvent::MealLoaded(meal) => results.push(*meal),
+            SearchEvent::Error(e) => {
+                eprintln!("{}", e);
+                failed = true;
+            }
+            _ => {}
+        }
+    }
+
+    let ratings = Ratings::load(&db).unwrap_or_default();
+    options.sort.apply(&options.query, &ratings, &mut results);
+    if options.limit > 0 {
+        results.truncate(options.limit);
+    }
+    match options.format {
+        Format::Json => println!("{}", serde_json::to_string_pretty(&results).unwrap()),
+        Format::Table => print_table(&results),
+    }
+    if failed && results.is_empty() {
+        1
+    } else {
+        0
+    }
+}
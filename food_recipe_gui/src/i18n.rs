@@ -0,0 +1,116 @@
+// UI text lookup. Every user-facing string goes through `tr!` with a Fluent
+// message ID; the messages live in locales/<lang>/main.ftl and are compiled
+// into the binary. Anything missing from a translation falls back to English.
+
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource};
+use recipe_core::provider::http::FetchError;
+use serde::{Deserialize, Serialize};
+use std::sync::{OnceLock, RwLock};
+use unic_langid::LanguageIdentifier;
+
+const EN_US: &str = include_str!("../locales/en-US/main.ftl");
+const DE_DE: &str = include_str!("../locales/de-DE/main.ftl");
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::German];
+
+    /// The language's own name, so it can be found without reading the current one.
+    pub fn native_name(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+        }
+    }
+
+    fn id(self) -> &'static str {
+        match self {
+            Language::English => "en-US",
+            Language::German => "de-DE",
+        }
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Language::English => EN_US,
+            Language::German => DE_DE,
+        }
+    }
+}
+
+struct Translator {
+    language: Language,
+    bundle: FluentBundle<FluentResource>,
+    fallback: FluentBundle<FluentResource>,
+}
+
+fn bundle(language: Language) -> FluentBundle<FluentResource> {
+    let id: LanguageIdentifier = language.id().parse().expect("valid language ID");
+    let mut bundle = FluentBundle::new_concurrent(vec![id]);
+    // egui draws the Unicode isolation marks Fluent puts around arguments
+    bundle.set_use_isolating(false);
+    let resource = FluentResource::try_new(language.source().to_string()).expect("valid bundled FTL file");
+    bundle.add_resource(resource).expect("no duplicate message IDs");
+    bundle
+}
+
+fn translator() -> &'static RwLock<Translator> {
+    static TRANSLATOR: OnceLock<RwLock<Translator>> = OnceLock::new();
+    TRANSLATOR.get_or_init(|| {
+        RwLock::new(Translator {
+            language: Language::English,
+            bundle: bundle(Language::English),
+            fallback: bundle(Language::English),
+        })
+    })
+}
+
+pub fn set_language(language: Language) {
+    let mut translator = translator().write().unwrap();
+    if translator.language != language {
+        translator.language = language;
+        translator.bundle = bundle(language);
+    }
+}
+
+fn format(bundle: &FluentBundle<FluentResource>, id: &str, args: Option<&FluentArgs>) -> Option<String> {
+    let pattern = bundle.get_message(id)?.value()?;
+    let mut errors = Vec::new();
+    Some(bundle.format_pattern(pattern, args, &mut errors).into_owned())
+}
+
+/// The message in the current language; the ID itself if nobody knows it.
+pub fn translate(id: &str, args: Option<&FluentArgs>) -> String {
+    let translator = translator().read().unwrap();
+    format(&translator.bundle, id, args)
+        .or_else(|| format(&translator.fallback, id, args))
+        .unwrap_or_else(|| id.to_string())
+}
+
+/// `tr!("message-id")` or `tr!("message-id", count = n, name = text)`.
+macro_rules! tr {
+    ($id:expr) => {
+        $crate::i18n::translate($id, None)
+    };
+    ($id:expr, $($name:ident = $value:expr),+ $(,)?) => {{
+        let mut args = fluent_bundle::FluentArgs::new();
+        $(args.set(stringify!($name), $value);)+
+        $crate::i18n::translate($id, Some(&args))
+    }};
+}
+
+/// A provider error in the current language; its `Display` text is English only.
+pub fn fetch_error(e: &FetchError) -> String {
+    match e {
+        FetchError::Network(e) => tr!("fetch-network", error = e.to_string()),
+        FetchError::Status(status) => tr!("fetch-status", status = status.to_string()),
+        FetchError::Decode(e) => tr!("fetch-decode", error = e.to_string()),
+    }
+}
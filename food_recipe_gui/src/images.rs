@@ -0,0 +1,192 @@
+// Recipe photos without stutter. egui_extras decodes images on the UI thread
+// the first frame they are shown, which a page of thumbnails turns into a
+// visible hitch; `ThreadedImageLoader` decodes on a worker thread instead.
+// `thumbnail` draws a placeholder until the image is there, then fades it in.
+
+use eframe::egui::{
+    self,
+    load::{BytesPoll, ImageLoadResult, ImageLoader, ImagePoll, LoadError, SizeHint},
+    ColorImage,
+};
+use std::collections::HashMap;
+use std::mem::size_of;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Seconds a thumbnail takes to fade in once decoded.
+const FADE_SECS: f32 = 0.25;
+
+enum Entry {
+    Decoding,
+    Ready(Arc<ColorImage>),
+    Failed(String),
+}
+
+/// Decodes JPEG and PNG bytes on worker threads. Anything else is left to
+/// the loaders installed before it.
+#[derive(Default)]
+pub struct ThreadedImageLoader {
+    cache: Arc<Mutex<HashMap<String, Entry>>>,
+}
+
+impl ThreadedImageLoader {
+    pub const ID: &'static str = egui::generate_loader_id!(ThreadedImageLoader);
+
+    /// Installs the loader in front of egui_extras' own, which must be installed first.
+    pub fn install(ctx: &egui::Context) {
+        ctx.add_image_loader(Arc::new(Self::default()));
+    }
+}
+
+fn decode(bytes: &[u8]) -> Result<ColorImage, String> {
+    let image = image::load_from_memory(bytes).map_err(|e| e.to_string())?;
+    let size = [image.width() as usize, image.height() as usize];
+    Ok(ColorImage::from_rgba_unmultiplied(size, image.to_rgba8().as_flat_samples().as_slice()))
+}
+
+impl ImageLoader for ThreadedImageLoader {
+    fn id(&self) -> &str {
+        Self::ID
+    }
+
+    fn load(&self, ctx: &egui::Context, uri: &str, _: SizeHint) -> ImageLoadResult {
+        let mut cache = self.cache.lock().unwrap();
+        match cache.get(uri) {
+            Some(Entry::Decoding) => return Ok(ImagePoll::Pending { size: None }),
+            Some(Entry::Ready(image)) => return Ok(ImagePoll::Ready { image: Arc::clone(image) }),
+            Some(Entry::Failed(e)) => return Err(LoadError::Loading(e.clone())),
+            None => {}
+        }
+        let bytes = match ctx.try_load_bytes(uri)? {
+            BytesPoll::Pending { size } => return Ok(ImagePoll::Pending { size }),
+            BytesPoll::Ready { bytes, .. } => bytes,
+        };
+        if !matches!(image::guess_format(&bytes), Ok(image::ImageFormat::Jpeg | image::ImageFormat::Png)) {
+            return Err(LoadError::NotSupported);
+        }
+        cache.insert(uri.to_string(), Entry::Decoding);
+        let (uri, cache, ctx) = (uri.to_string(), Arc::clone(&self.cache), ctx.clone());
+        std::thread::spawn(move || {
+            let entry = match decode(&bytes) {
+                Ok(image) => Entry::Ready(Arc::new(image)),
+                Err(e) => Entry::Failed(e),
+            };
+            cache.lock().unwrap().insert(uri, entry);
+            ctx.request_repaint();
+        });
+        Ok(ImagePoll::Pending { size: None })
+    }
+
+    fn forget(&self, uri: &str) {
+        self.cache.lock().unwrap().remove(uri);
+    }
+
+    fn forget_all(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+
+    fn byte_size(&self) -> usize {
+        self.cache
+            .lock()
+            .unwrap()
+            .values()
+            .map(|entry| match entry {
+                Entry::Ready(image) => image.pixels.len() * size_of::<egui::Color32>(),
+                Entry::Failed(e) => e.len(),
+                Entry::Decoding => 0,
+            })
+            .sum()
+    }
+}
+
+/// Where the photo at `url` is kept for offline use, named after the URL.
+pub fn offline_file(dir: &Path, url: &str) -> PathBuf {
+    let name: String = url
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '.' { c } else { '_' })
+        .collect();
+    dir.join(name)
+}
+
+/// The downloaded copy of `url` as an image URI, if there is one.
+pub fn offline_uri(dir: &Path, url: &str) -> Option<String> {
+    let path = offline_file(dir, url);
+    path.exists().then(|| format!("file://{}", path.display()))
+}
+
+/// The recipe photo to show: its web address online, or with `offline_dir`
+/// the downloaded copy, if there is one.
+pub fn photo_uri(thumb: &str, preview: bool, offline_dir: Option<&Path>) -> Option<String> {
+    if thumb.is_empty() {
+        return None;
+    }
+    let url = match preview_url(thumb) {
+        Some(small) if preview => small,
+        _ => thumb.to_string(),
+    };
+    match offline_dir {
+        Some(dir) => offline_uri(dir, &url),
+        None => Some(url),
+    }
+}
+
+/// TheMealDB serves a 250px preview next to every full-size photo. Other
+/// hosts don't, so their photos are shown at full size everywhere.
+fn preview_url(thumb: &str) -> Option<String> {
+    let host = url::Url::parse(thumb).ok()?.host_str()?.to_string();
+    (host == "themealdb.com" || host.ends_with(".themealdb.com")).then(|| format!("{}/preview", thumb))
+}
+
+/// A recipe photo and the smaller preview the result list shows, if its host has one.
+pub fn recipe_photos(thumb: &str) -> Vec<String> {
+    if thumb.is_empty() {
+        return Vec::new();
+    }
+    std::iter::once(thumb.to_string()).chain(preview_url(thumb)).collect()
+}
+
+/// An image filling exactly `size`: a placeholder rectangle until it has
+/// loaded, then the image fading in. Off-screen thumbnails aren't loaded at all.
+pub fn thumbnail(ui: &mut egui::Ui, uri: &str, size: egui::Vec2, rounding: f32) -> egui::Response {
+    let (rect, response) = ui.allocate_exact_size(size, egui::Sense::click());
+    if !ui.is_rect_visible(rect) {
+        return response;
+    }
+    let image = egui::Image::new(uri).fit_to_exact_size(size).rounding(rounding);
+    let ready = matches!(image.load_for_size(ui.ctx(), size), Ok(egui::load::TexturePoll::Ready { .. }));
+    let opacity = ui.ctx().animate_bool_with_time(egui::Id::new(("thumbnail", uri)), ready, FADE_SECS);
+    if opacity < 1.0 {
+        ui.painter().rect_filled(rect, rounding, ui.visuals().extreme_bg_color);
+    }
+    if ready {
+        image.tint(egui::Color32::WHITE.gamma_multiply(opacity)).paint_at(ui, rect);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MEALDB: &str = "https://www.themealdb.com/images/media/meals/wvpsxx1468256321.jpg";
+
+    #[test]
+    fn only_themealdb_photos_get_a_preview() {
+        assert_eq!(photo_uri(MEALDB, true, None), Some(format!("{}/preview", MEALDB)));
+        assert_eq!(photo_uri(MEALDB, false, None).as_deref(), Some(MEALDB));
+        let signed = "https://edamam-product-images.s3.amazonaws.com/web-img/abc.jpg?X-Amz-Signature=1f";
+        assert_eq!(photo_uri(signed, true, None).as_deref(), Some(signed));
+        let spoonacular = "https://img.spoonacular.com/recipes/716429-556x370.jpg";
+        assert_eq!(photo_uri(spoonacular, true, None).as_deref(), Some(spoonacular));
+        assert_eq!(photo_uri("", true, None), None);
+    }
+
+    #[test]
+    fn downloads_previews_only_where_they_exist() {
+        assert_eq!(recipe_photos(MEALDB).len(), 2);
+        assert_eq!(recipe_photos("file:///home/me/photos/stew.jpg"), ["file:///home/me/photos/stew.jpg"]);
+        assert!(recipe_photos("").is_empty());
+    }
+}
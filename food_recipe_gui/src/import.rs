@@ -0,0 +1,189 @@
+//! Bulk import into "My Recipes".
+//!
+//! Files ending in `.yaml`/`.yml` are read as YAML, anything else as JSON.
+//! A file holds either one recipe or a list of them in this schema (only
+//! `title` is required):
+//!
+//! ```yaml
+//! - title: Garlic Butter Prawns
+//!   category: Seafood
+//!   area: Spanish
+//!   servings: 2
+//!   tags: [Quick, Shellfish]
+//!   ingredients:
+//!     - name: Prawns
+//!       measure: 300g
+//!     - Garlic            # a bare string is an ingredient without a measure
+//!   instructions: |
+//!     Melt the butter.
+//!     Fry the garlic and prawns.
+//!   source: https://example.com/prawns
+//!   youtube: https://www.youtube.com/watch?v=...
+//!   thumb: https://example.com/prawns.jpg
+//! ```
+//!
+//! Raw TheMealDB responses are accepted too, either a whole `lookup.php`
+//! body (`{"meals": [...]}`) or a single meal object from one.
+
+use crate::my_recipes::MyRecipes;
+use recipe_core::MealDetail;
+use recipe_core::provider::themealdb::MealFull;
+use serde::Deserialize;
+use std::{fs, path::Path, path::PathBuf};
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ImportedIngredient {
+    Plain(String),
+    Measured {
+        name: String,
+        #[serde(default)]
+        measure: String,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+struct ImportedRecipe {
+    title: String,
+    #[serde(default)]
+    category: String,
+    #[serde(default)]
+    area: String,
+    #[serde(default)]
+    servings: Option<u32>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    ingredients: Vec<ImportedIngredient>,
+    #[serde(default)]
+    instructions: String,
+    #[serde(default)]
+    source: String,
+    #[serde(default)]
+    youtube: String,
+    #[serde(default)]
+    thumb: String,
+}
+
+impl From<ImportedRecipe> for MealDetail {
+    fn from(recipe: ImportedRecipe) -> Self {
+        let (ingredients, measures) = recipe
+            .ingredients
+            .into_iter()
+            .map(|ing| match ing {
+                ImportedIngredient::Plain(name) => (name, String::new()),
+                ImportedIngredient::Measured { name, measure } => (name, measure),
+            })
+            .map(|(name, measure)| (name.trim().to_string(), measure.trim().to_string()))
+            .filter(|(name, _)| !name.is_empty())
+            .unzip();
+        MealDetail {
+            title: recipe.title.trim().to_string(),
+            category: recipe.category,
+            area: recipe.area,
+            instructions: recipe.instructions,
+            ingredients,
+            measures,
+            servings: recipe.servings,
+            tags: recipe.tags,
+            youtube: recipe.youtube,
+            source: recipe.source,
+            thumb: recipe.thumb,
+            ..Default::default()
+        }
+    }
+}
+
+// Tried in order. The response wrapper goes last: with `meals` optional it
+// would accept any object at all.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ImportFile {
+    MealDbMeal(Box<MealFull>),
+    One(ImportedRecipe),
+    Many(Vec<ImportedRecipe>),
+    MealDbResponse { meals: Option<Vec<MealFull>> },
+}
+
+impl ImportFile {
+    fn into_meals(self) -> Vec<MealDetail> {
+        match self {
+            ImportFile::MealDbResponse { meals } => meals.unwrap_or_default().into_iter().map(Into::into).collect(),
+            ImportFile::MealDbMeal(meal) => vec![(*meal).into()],
+            ImportFile::Many(recipes) => recipes.into_iter().map(Into::into).collect(),
+            ImportFile::One(recipe) => vec![recipe.into()],
+        }
+    }
+}
+
+/// Reads every recipe in one file, without touching the store.
+pub fn read_file(path: &Path) -> Result<Vec<MealDetail>, String> {
+    let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let is_yaml = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("yaml") || e.eq_ignore_ascii_case("yml"));
+    let file: ImportFile = if is_yaml {
+        serde_yaml::from_str(&text).map_err(|e| e.to_string())?
+    } else {
+        serde_json::from_str(&text).map_err(|e| e.to_string())?
+    };
+    Ok(file.into_meals())
+}
+
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    pub imported: usize,
+    /// Recipes whose title was already in the store.
+    pub duplicates: usize,
+    /// One line per file that couldn't be read.
+    pub errors: Vec<String>,
+}
+
+impl ImportReport {
+    pub fn summary(&self) -> String {
+        let mut summary = tr!("import-summary", count = self.imported);
+        if self.duplicates > 0 {
+            summary += &tr!("import-duplicates", count = self.duplicates);
+        }
+        if !self.errors.is_empty() {
+            summary += &tr!("import-failures", count = self.errors.len());
+        }
+        summary
+    }
+}
+
+/// Adds the recipes from every file to the store under fresh local IDs.
+/// The caller saves the store.
+pub fn import_files(store: &mut MyRecipes, paths: &[PathBuf]) -> ImportReport {
+    let mut report = ImportReport::default();
+    for path in paths {
+        let meals = match read_file(path) {
+            Ok(meals) => meals,
+            Err(e) => {
+                report.errors.push(format!("{}: {}", path.display(), e));
+                continue;
+            }
+        };
+        for mut meal in meals {
+            if meal.title.is_empty() {
+                continue;
+            }
+            if store.iter().any(|r| r.title.eq_ignore_ascii_case(&meal.title)) {
+                report.duplicates += 1;
+                continue;
+            }
+            meal.id.clear();
+            store.upsert(meal);
+            report.imported += 1;
+        }
+    }
+    report
+}
+
+pub fn pick_files() -> Vec<PathBuf> {
+    rfd::FileDialog::new()
+        .add_filter("Recipes", &["json", "yaml", "yml"])
+        .pick_files()
+        .unwrap_or_default()
+}
@@ -0,0 +1,41 @@
+use recipe_core::db::Database;
+
+/// A small user-maintained list of ingredient names, stored under a list name.
+#[derive(Default)]
+pub struct IngredientList {
+    items: Vec<String>,
+}
+
+impl IngredientList {
+    pub fn load(db: &Database, list: &str) -> rusqlite::Result<Self> {
+        Ok(Self {
+            items: db.load_list(list)?,
+        })
+    }
+
+    pub fn save(&self, db: &Database, list: &str) -> rusqlite::Result<()> {
+        db.save_list(list, &self.items)
+    }
+
+    /// Adds an item unless it is blank or already present. Returns whether it was added.
+    pub fn add(&mut self, item: &str) -> bool {
+        let item = item.trim();
+        if item.is_empty() || self.items.iter().any(|i| i.eq_ignore_ascii_case(item)) {
+            return false;
+        }
+        self.items.push(item.to_string());
+        true
+    }
+
+    pub fn remove(&mut self, item: &str) {
+        self.items.retain(|i| i != item);
+    }
+
+    pub fn items(&self) -> &[String] {
+        &self.items
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
@@ -0,0 +1,74 @@
+use chrono::NaiveDate;
+use recipe_core::db::Database;
+use recipe_core::matching::ingredient_matches;
+use recipe_core::MealDetail;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Leftover {
+    pub ingredient: String,
+    /// Free text, e.g. "half a tin" or "200g".
+    pub quantity: String,
+    /// Last day it is good for; `None` if it keeps.
+    pub expires: Option<NaiveDate>,
+}
+
+impl Leftover {
+    /// Days until `expires`, negative once it is past.
+    pub fn days_left(&self, today: NaiveDate) -> Option<i64> {
+        self.expires.map(|date| (date - today).num_days())
+    }
+}
+
+/// What's left over from earlier cooking, to be used up before it goes off.
+#[derive(Default)]
+pub struct Leftovers {
+    items: Vec<Leftover>,
+}
+
+impl Leftovers {
+    pub fn load(db: &Database) -> rusqlite::Result<Self> {
+        let items = db
+            .load_leftovers()?
+            .iter()
+            .filter_map(|json| serde_json::from_str(json).ok())
+            .collect();
+        Ok(Self { items })
+    }
+
+    pub fn save(&self, db: &Database) -> rusqlite::Result<()> {
+        let rows: Vec<String> = self.items.iter().map(|l| serde_json::to_string(l).unwrap()).collect();
+        db.save_leftovers(&rows)
+    }
+
+    /// Keeps the list ordered by expiry, soonest first.
+    pub fn add(&mut self, leftover: Leftover) {
+        self.items.push(leftover);
+        self.items.sort_by_key(|l| l.expires.unwrap_or(NaiveDate::MAX));
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        self.items.remove(index);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Leftover> {
+        self.items.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Ingredient names, soonest to expire first.
+    pub fn ingredients(&self) -> Vec<String> {
+        self.items.iter().map(|l| l.ingredient.clone()).collect()
+    }
+}
+
+/// How many of the meal's ingredients are among the leftovers.
+pub fn leftover_count(meal: &MealDetail, leftovers: &[String]) -> usize {
+    meal.ingredients
+        .iter()
+        .filter(|ing| leftovers.iter().any(|l| ingredient_matches(ing, l)))
+        .count()
+}
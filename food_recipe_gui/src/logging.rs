@@ -0,0 +1,92 @@
+// Tracing output goes to a daily log file in the cache directory and into a
+// small in-memory buffer that the log viewer panel reads.
+
+use std::collections::VecDeque;
+use std::io;
+use std::path::Path;
+use std::sync::Mutex;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::filter::{LevelFilter, Targets};
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::prelude::*;
+
+/// Lines the log viewer keeps; older ones drop off the top.
+const MAX_LINES: usize = 500;
+/// Daily files kept next to the current one.
+const MAX_LOG_FILES: usize = 7;
+
+static LINES: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// The most recent log lines, oldest first.
+pub fn recent_lines() -> Vec<String> {
+    LINES.lock().unwrap().iter().cloned().collect()
+}
+
+pub fn clear() {
+    LINES.lock().unwrap().clear();
+}
+
+// Each formatted event arrives as one write ending in a newline
+struct ViewerWriter;
+
+impl io::Write for ViewerWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let text = String::from_utf8_lossy(buf);
+        let mut lines = LINES.lock().unwrap();
+        for line in text.lines().filter(|l| !l.is_empty()) {
+            if lines.len() == MAX_LINES {
+                lines.pop_front();
+            }
+            lines.push_back(line.to_string());
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+struct Viewer;
+
+impl<'a> MakeWriter<'a> for Viewer {
+    type Writer = ViewerWriter;
+
+    fn make_writer(&'a self) -> ViewerWriter {
+        ViewerWriter
+    }
+}
+
+/// Starts logging for the whole process. Keep the guard alive until exit, or
+/// the last lines never reach the file. Without a log file, the viewer still works.
+pub fn init(dir: &Path) -> Option<WorkerGuard> {
+    // Our own crates at debug; libraries only when something goes wrong
+    let filter = Targets::new()
+        .with_default(LevelFilter::WARN)
+        .with_target("recipe_core", LevelFilter::DEBUG)
+        .with_target("food_recipe_finder_gui", LevelFilter::DEBUG);
+    let viewer = tracing_subscriber::fmt::layer()
+        .with_ansi(false)
+        .with_target(false)
+        .with_writer(Viewer);
+
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix("recipe_finder")
+        .filename_suffix("log")
+        .max_log_files(MAX_LOG_FILES)
+        .build(dir);
+    let (file, guard) = match appender {
+        Ok(appender) => {
+            let (writer, guard) = tracing_appender::non_blocking(appender);
+            (Some(tracing_subscriber::fmt::layer().with_ansi(false).with_writer(writer)), Some(guard))
+        }
+        Err(e) => {
+            eprintln!("Logging to {} is off: {}", dir.display(), e);
+            (None, None)
+        }
+    };
+    tracing_subscriber::registry().with(viewer).with(file).with(filter).init();
+    guard
+}
@@ -1,144 +1,784 @@
+// Declared first so `tr!` is visible in every module below
+#[macro_use]
+mod i18n;
+mod aisles;
+mod allergens;
+mod backup;
+mod batch;
+mod config;
+mod cooking;
+mod cost;
+mod diet;
+mod difficulty;
+mod export;
+mod favorites;
+mod filters;
+mod headless;
+mod images;
+mod import;
+mod ingredient_list;
+mod leftovers;
+mod logging;
+mod meal_plan;
+mod measure;
+mod my_recipes;
+mod notes;
+mod nutrition;
+mod pantry;
+mod prefetch;
+mod presets;
+mod profiles;
+mod quota;
+mod ratings;
+mod recent;
+mod result_diff;
+mod search;
+mod seasonal;
+mod session;
+mod shopping;
+#[cfg(feature = "speech")]
+mod speech;
+mod steps;
+mod text_index;
+mod theme;
+mod timers;
+mod tui;
+
+use allergens::Allergen;
+use backup::Backup;
+use batch::SearchQueue;
+use config::{AppConfig, CONFIG_FILE};
+use cooking::CookingMode;
+use cost::{Price, PriceUnit};
+use diet::Diet;
+use difficulty::Difficulty;
 use eframe::egui::{self, ScrollArea};
-use reqwest::blocking::Client;
-use serde::Deserialize;
+use favorites::Favorites;
+use filters::Allergies;
+use i18n::Language;
+use ingredient_list::IngredientList;
+use leftovers::{Leftover, Leftovers};
+use meal_plan::MealPlan;
+use measure::UnitSystem;
+use my_recipes::{MyRecipes, RecipeDraft};
+use notes::Notes;
+use pantry::{Pantry, PANTRY_LIST};
+use presets::{Preset, Presets};
+use profiles::Profiles;
+use quota::{DailyCount, RequestCounter};
+use ratings::Ratings;
+use recent::RecentlyViewed;
+use recipe_core::cache::{MealCache, DEFAULT_TTL};
+use recipe_core::db::{Database, DEFAULT_PROFILE};
+use recipe_core::matching::{self, ingredient_matches};
+use recipe_core::provider::http::RequestRecord;
+use recipe_core::provider::{http, themealdb, Edamam, FetchResult, RecipeProvider, Spoonacular, TheMealDb};
+use recipe_core::scoring::{Reason, ScoringConfig, TastePlace};
+use recipe_core::MealDetail;
+use reqwest::Client;
+use result_diff::{Change, ResultDiff};
+use seasonal::Region;
+use search::{SearchEvent, SearchProgress, SearchQuery, ScoringStrategy, SortOrder};
+use session::Session;
+use shopping::SHOPPING_LIST_FILE;
+#[cfg(feature = "speech")]
+use speech::ReadAloud;
 use std::collections::{HashMap, HashSet};
-use std::sync::{Arc, Mutex};
-use std::thread;
-use std::time::Duration;
-use std::convert::TryFrom;
-
-type SharedCache = Arc<Mutex<Vec<MealDetail>>>;
-const API_BASE: &str = "https://www.themealdb.com/api/json/v1/1";
-
-#[derive(Debug, Deserialize, Clone)]
-struct MealShort {
-    idMeal: String,
-    strMeal: String,
-}
+use std::sync::mpsc::{self, Receiver};
+use std::sync::{Arc, OnceLock};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use text_index::TextIndex;
+use theme::{ThemeConfig, ThemeMode};
+use timers::StepTimer;
+use tokio::runtime::Runtime;
+use tokio_util::sync::CancellationToken;
 
-#[derive(Debug, Deserialize, Clone)]
-struct MealsList {
-    meals: Option<Vec<MealShort>>,
+/// Category, area and ingredient names offered by one provider, loaded in the
+/// background; the ingredients feed the ingredient fields' autocomplete.
+#[derive(Debug, Clone, Default)]
+struct FilterLists {
+    categories: Vec<String>,
+    areas: Vec<String>,
+    ingredients: Vec<String>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
-struct MealFull {
-    idMeal: String,
-    strMeal: String,
-    strCategory: Option<String>,
-    strArea: Option<String>,
-    strInstructions: Option<String>,
-    #[serde(flatten)]
-    extra: HashMap<String, serde_json::Value>,
+struct RecipeApp {
+    /// Dish name for the name search, e.g. "carbonara".
+    name_query: String,
+    taste: String,
+    main_ingredients: String,
+    sub_ingredients: String,
+    /// Results of the current search, best first.
+    results: Vec<MealDetail>,
+    /// Zero-based page of `results` on screen.
+    page: usize,
+    sort_order: SortOrder,
+    /// Show results as image cards instead of list rows.
+    grid_view: bool,
+    selected_id: Option<String>,
+    /// Cached recipes most like a given one, by meal ID; worked out when first shown.
+    similar: HashMap<String, Vec<(MealDetail, f64)>>,
+    /// Recipes popped out into their own OS windows.
+    detail_windows: Vec<MealDetail>,
+    /// Instruction steps ticked off so far, by meal ID.
+    done_steps: HashMap<String, HashSet<usize>>,
+    timers: Vec<StepTimer>,
+    /// Recipe being cooked step by step; takes over the whole window while set.
+    cooking: Option<CookingMode>,
+    /// Instructions being read aloud, if any.
+    #[cfg(feature = "speech")]
+    read_aloud: Option<ReadAloud>,
+    /// Why reading aloud stopped, if the speech engine failed.
+    #[cfg(feature = "speech")]
+    read_aloud_status: Option<String>,
+    /// Servings picked in the detail view, by meal ID; ingredient amounts scale to match.
+    servings: HashMap<String, u32>,
+    profiles: Profiles,
+    /// Whose favorites, allergies, taste and diet are in use.
+    profile: String,
+    new_profile_name: String,
+    /// The "who's cooking" window shown at launch when there is more than one profile.
+    pick_profile: bool,
+    favorites: Favorites,
+    notes: Notes,
+    ratings: Ratings,
+    recently_viewed: RecentlyViewed,
+    pantry: Pantry,
+    pantry_input: String,
+    leftovers: Leftovers,
+    /// The leftover being typed in, with the days it keeps.
+    leftover_input: (String, String, u32),
+    presets: Presets,
+    meal_plan: MealPlan,
+    /// Result of the last meal plan export.
+    meal_plan_status: Option<String>,
+    /// Name typed for the next preset to save.
+    preset_name: String,
+    cook_from_pantry: bool,
+    /// Rank recipes higher for every ingredient that is in season.
+    seasonal_boost: bool,
+    /// Score recipes higher for every leftover they use up.
+    leftover_boost: bool,
+    /// Decides which produce counts as in season.
+    region: Region,
+    allergies: Allergies,
+    allergy_input: String,
+    exclude_ingredients: String,
+    hide_excluded: bool,
+    /// Only recipes fitting this diet are listed.
+    diet: Option<Diet>,
+    /// Only recipes estimated to cost at most this much are listed.
+    max_cost: Option<f64>,
+    /// Only recipes estimated at most this many kcal per serving are listed.
+    max_calories: Option<f64>,
+    /// Only recipes with at most this many ingredients are listed.
+    max_ingredients: Option<usize>,
+    /// Only recipes estimated to take at most this many minutes are listed.
+    max_minutes: Option<u64>,
+    /// Only recipes rated at most this hard are listed.
+    max_difficulty: Option<Difficulty>,
+    shopping_recipes: Vec<MealDetail>,
+    show_shopping_list: bool,
+    /// Up to `MAX_COMPARE` recipes picked for the comparison window.
+    compare: Vec<MealDetail>,
+    show_compare: bool,
+    my_recipes: MyRecipes,
+    show_my_recipes: bool,
+    /// Recipe open in the editor, if any.
+    recipe_draft: Option<RecipeDraft>,
+    import_report: Option<import::ImportReport>,
+    /// Outcome of the last backup export or restore.
+    backup_status: Option<String>,
+    /// No network calls at all; searches run over `local_pool` instead.
+    offline: bool,
+    /// How long ago each cache-served result was fetched, for the staleness hints.
+    cache_ages: HashMap<String, Duration>,
+    /// Results of the form search the current one refines, for the new,
+    /// dropped and rescored markers. Other kinds of search clear it.
+    result_diff: Option<ResultDiff>,
+    /// Result whose score breakdown window is open.
+    score_breakdown: Option<MealDetail>,
+    offline_query: String,
+    offline_status: Option<String>,
+    scoring: ScoringConfig,
+    scoring_strategy: ScoringStrategy,
+    prices: Vec<Price>,
+    currency: String,
+    /// Saved setting; the job itself only starts with the app.
+    prefetch_favorite_categories: bool,
+    /// Saved settings; the refresh job only starts with the app.
+    keep_favorites_offline: bool,
+    favorites_refresh_hours: u64,
+    /// Photos downloaded for offline use.
+    image_dir: PathBuf,
+    /// Saved setting; TheMealDB picks it up on the next start.
+    themealdb_api_key: String,
+    /// Whether TheMealDB was started with a paid key, offering the latest meals.
+    themealdb_premium: bool,
+    requests: RequestCounter,
+    daily_request_limit: Option<u64>,
+    units: UnitSystem,
+    theme: ThemeConfig,
+    /// What was last handed to egui, so the theme is only re-applied on change.
+    applied_theme: Option<ThemeConfig>,
+    language: Language,
+    show_settings: bool,
+    settings_status: Option<String>,
+    shopping_status: Option<String>,
+    export_status: Option<String>,
+    /// Outcome of the last CSV export of the results.
+    results_export_status: Option<String>,
+    /// Open an HTML export in the browser right after saving it.
+    open_html_export: bool,
+    /// The task manager's tasks.json that missing ingredients are added to.
+    tasks_file: PathBuf,
+    /// Where the daily log files are written, shown under the log viewer.
+    log_dir: PathBuf,
+    runtime: Runtime,
+    db: Arc<Database>,
+    providers: Vec<Arc<dyn RecipeProvider>>,
+    provider_index: usize,
+    /// One entry per provider, same order as `providers`.
+    filter_lists: Vec<Arc<OnceLock<FilterLists>>>,
+    /// Random pick fetched on launch, shown as a banner until dismissed.
+    meal_of_the_day: Arc<OnceLock<MealDetail>>,
+    show_meal_of_the_day: bool,
+    category: Option<String>,
+    area: Option<String>,
+    /// Only results carrying this tag are listed.
+    tag_filter: Option<String>,
+    /// Only results whose instructions mention this are listed, e.g. "oven".
+    instruction_filter: String,
+    /// Cancels the running search, if any.
+    search_cancel: CancellationToken,
+    search_events: Option<Receiver<SearchEvent>>,
+    progress: SearchProgress,
+    /// Searches waiting to run one after another in the background.
+    queue: SearchQueue,
+    show_queue: bool,
+    /// Query the current results were scored against, used to keep them sorted.
+    last_query: SearchQuery,
 }
 
-#[derive(Debug, Clone)]
-struct MealDetail {
-    id: String,
-    title: String,
-    category: String,
-    area: String,
-    instructions: String,
-    ingredients: Vec<String>,
-    score: i32,
-}
+impl RecipeApp {
+    fn new() -> Self {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .expect("failed to start tokio runtime");
+        let config = AppConfig::load(CONFIG_FILE).unwrap_or_default();
+        i18n::set_language(config.language);
+        let db = open_database(&config);
+        let providers = build_providers(&config, &db);
+
+        let filter_lists = providers
+            .iter()
+            .map(|provider| {
+                let lists = Arc::new(OnceLock::new());
+                let provider = Arc::clone(provider);
+                let target = Arc::clone(&lists);
+                runtime.spawn(async move {
+                    let or_empty = |list: FetchResult<Vec<String>>, what: &str| {
+                        list.unwrap_or_else(|e| {
+                            eprintln!("Failed to load {} from {}: {}", what, provider.name(), e);
+                            Vec::new()
+                        })
+                    };
+                    let categories = or_empty(provider.categories().await, "categories");
+                    let areas = or_empty(provider.areas().await, "areas");
+                    let ingredients = or_empty(provider.ingredient_names().await, "ingredients");
+                    let _ = target.set(FilterLists {
+                        categories,
+                        areas,
+                        ingredients,
+                    });
+                });
+                lists
+            })
+            .collect();
+
+        if config.prefetch_favorite_categories {
+            // TheMealDB is the provider with a meal cache
+            runtime.spawn(prefetch::run(Arc::clone(&providers[0]), Arc::clone(&db), config.prefetch_interval()));
+        }
+        if config.keep_favorites_offline {
+            runtime.spawn(prefetch::keep_favorites_offline(
+                providers.clone(),
+                Arc::clone(&db),
+                build_client(&config),
+                config.image_dir(),
+                config.favorites_refresh_interval(),
+            ));
+        }
+
+        let profiles = Profiles::load(&db).unwrap_or_default();
+        let pick_profile = profiles.len() > 1;
 
-fn extract_ingredients(full: &MealFull) -> Vec<String> {
-    let mut list = Vec::new();
-    for i in 1..=20 {
-        let key = format!("strIngredient{}", i);
-        if let Some(val) = full.extra.get(&key) {
-            if let Some(s) = val.as_str() {
-                let ing = s.trim();
-                if !ing.is_empty() {
-                    list.push(ing.to_string());
+        let meal_of_the_day = Arc::new(OnceLock::new());
+        {
+            let provider = Arc::clone(&providers[0]);
+            let target = Arc::clone(&meal_of_the_day);
+            runtime.spawn(async move {
+                match provider.random().await {
+                    Ok(Some(meal)) => {
+                        let _ = target.set(meal);
+                    }
+                    Ok(None) => {}
+                    Err(e) => eprintln!("Failed to load the meal of the day: {}", e),
                 }
-            }
+            });
+        }
+
+        Self {
+            name_query: String::new(),
+            taste: String::new(),
+            main_ingredients: String::new(),
+            sub_ingredients: String::new(),
+            results: Vec::new(),
+            page: 0,
+            sort_order: config.default_sort,
+            grid_view: false,
+            selected_id: None,
+            similar: HashMap::new(),
+            detail_windows: Vec::new(),
+            done_steps: HashMap::new(),
+            cooking: None,
+            #[cfg(feature = "speech")]
+            read_aloud: None,
+            #[cfg(feature = "speech")]
+            read_aloud_status: None,
+            timers: Vec::new(),
+            servings: HashMap::new(),
+            profiles,
+            profile: DEFAULT_PROFILE.to_string(),
+            new_profile_name: String::new(),
+            pick_profile,
+            favorites: Favorites::load(&db, DEFAULT_PROFILE).unwrap_or_default(),
+            notes: Notes::load(&db).unwrap_or_default(),
+            ratings: Ratings::load(&db).unwrap_or_default(),
+            recently_viewed: RecentlyViewed::load(&db).unwrap_or_default(),
+            pantry: Pantry::load(&db, PANTRY_LIST).unwrap_or_default(),
+            pantry_input: String::new(),
+            leftovers: Leftovers::load(&db).unwrap_or_default(),
+            leftover_input: (String::new(), String::new(), DEFAULT_LEFTOVER_DAYS),
+            presets: Presets::load(&db).unwrap_or_default(),
+            meal_plan: MealPlan::load(&db, chrono::Local::now().date_naive()).unwrap_or_default(),
+            meal_plan_status: None,
+            preset_name: String::new(),
+            cook_from_pantry: false,
+            seasonal_boost: false,
+            leftover_boost: false,
+            region: config.region,
+            allergies: Allergies::load(&db, &profiles::allergies_list(DEFAULT_PROFILE)).unwrap_or_default(),
+            allergy_input: String::new(),
+            exclude_ingredients: String::new(),
+            hide_excluded: true,
+            diet: None,
+            max_cost: None,
+            max_calories: None,
+            max_ingredients: None,
+            max_minutes: None,
+            max_difficulty: None,
+            shopping_recipes: Vec::new(),
+            show_shopping_list: false,
+            compare: Vec::new(),
+            show_compare: false,
+            my_recipes: MyRecipes::load(&db).unwrap_or_default(),
+            show_my_recipes: false,
+            recipe_draft: None,
+            import_report: None,
+            backup_status: None,
+            offline: false,
+            cache_ages: HashMap::new(),
+            result_diff: None,
+            score_breakdown: None,
+            offline_query: String::new(),
+            offline_status: None,
+            scoring: config.scoring.clone(),
+            scoring_strategy: config.scoring_strategy,
+            prices: config.prices.clone(),
+            currency: config.currency.clone(),
+            prefetch_favorite_categories: config.prefetch_favorite_categories,
+            keep_favorites_offline: config.keep_favorites_offline,
+            favorites_refresh_hours: config.favorites_refresh_hours,
+            image_dir: config.image_dir(),
+            themealdb_api_key: config.themealdb_api_key.clone(),
+            themealdb_premium: config.themealdb_premium(),
+            requests: RequestCounter::new(&DailyCount::default()),
+            daily_request_limit: config.daily_request_limit,
+            units: config.units,
+            theme: config.theme.clone(),
+            applied_theme: None,
+            language: config.language,
+            show_settings: false,
+            settings_status: None,
+            shopping_status: None,
+            export_status: None,
+            results_export_status: None,
+            open_html_export: true,
+            tasks_file: config.tasks_file.clone(),
+            log_dir: config.log_dir(),
+            runtime,
+            db,
+            providers,
+            provider_index: 0,
+            filter_lists,
+            meal_of_the_day,
+            show_meal_of_the_day: true,
+            category: None,
+            area: None,
+            tag_filter: None,
+            instruction_filter: String::new(),
+            search_cancel: CancellationToken::new(),
+            search_events: None,
+            progress: SearchProgress::default(),
+            queue: SearchQueue::default(),
+            show_queue: false,
+            last_query: SearchQuery::default(),
+        }
+    }
+
+    /// Puts back the inputs, theme and open recipe from the last run.
+    fn restore_session(&mut self, session: Session) {
+        if let Some(profile) = session.profile.filter(|p| self.profiles.get(p).is_some()) {
+            self.switch_profile(&profile);
+        }
+        self.name_query = session.name_query;
+        self.taste = session.taste;
+        self.main_ingredients = session.main_ingredients;
+        self.sub_ingredients = session.sub_ingredients;
+        self.exclude_ingredients = session.exclude_ingredients;
+        self.category = session.category;
+        self.area = session.area;
+        self.diet = session.diet;
+        self.max_cost = session.max_cost;
+        self.max_calories = session.max_calories;
+        self.max_ingredients = session.max_ingredients;
+        self.max_minutes = session.max_minutes;
+        self.max_difficulty = session.max_difficulty;
+        self.sort_order = session.sort_order;
+        self.grid_view = session.grid_view;
+        self.seasonal_boost = session.seasonal_boost;
+        self.leftover_boost = session.leftover_boost;
+        self.theme = session.theme;
+        self.selected_id = session.selected_id;
+        self.requests = RequestCounter::new(&session.requests);
+    }
+
+    fn session(&self) -> Session {
+        Session {
+            name_query: self.name_query.clone(),
+            taste: self.taste.clone(),
+            main_ingredients: self.main_ingredients.clone(),
+            sub_ingredients: self.sub_ingredients.clone(),
+            exclude_ingredients: self.exclude_ingredients.clone(),
+            category: self.category.clone(),
+            area: self.area.clone(),
+            diet: self.diet,
+            max_cost: self.max_cost,
+            max_calories: self.max_calories,
+            max_ingredients: self.max_ingredients,
+            max_minutes: self.max_minutes,
+            max_difficulty: self.max_difficulty,
+            sort_order: self.sort_order,
+            grid_view: self.grid_view,
+            seasonal_boost: self.seasonal_boost,
+            leftover_boost: self.leftover_boost,
+            theme: self.theme.clone(),
+            selected_id: self.selected_id.clone(),
+            profile: Some(self.profile.clone()),
+            requests: self.requests.saved(),
+        }
+    }
+
+    /// Loads the profile's favorites and allergies and fills in its taste and diet.
+    fn switch_profile(&mut self, name: &str) {
+        let Some(profile) = self.profiles.get(name) else {
+            return;
+        };
+        self.taste = profile.taste.clone();
+        self.diet = profile.diet;
+        self.profile = name.to_string();
+        self.favorites = Favorites::load(&self.db, name).unwrap_or_default();
+        self.allergies = Allergies::load(&self.db, &profiles::allergies_list(name)).unwrap_or_default();
+        self.allergy_input.clear();
+    }
+
+    fn save_profiles(&self) {
+        if let Err(e) = self.profiles.save(&self.db) {
+            eprintln!("Failed to save profiles: {}", e);
         }
     }
-    list
-}
 
-// Scoring: main ingredients higher priority
-fn score_meal(
-    detail: &MealDetail,
-    main_ing: &[String],
-    sub_ing: &[String],
-    taste: &Option<String>,
-) -> i32 {
-    let mut score = 0;
-    for ing in &detail.ingredients {
-        for want in main_ing {
-            if ing.to_lowercase().contains(&want.to_lowercase()) {
-                score += 4;
+    fn profile_ui(&mut self, ui: &mut egui::Ui) {
+        let mut switch_to = None;
+        egui::ComboBox::from_id_source("profile_select")
+            .selected_text(&self.profile)
+            .show_ui(ui, |ui| {
+                for profile in self.profiles.iter() {
+                    if ui.selectable_label(profile.name == self.profile, &profile.name).clicked() {
+                        switch_to = Some(profile.name.clone());
+                    }
+                }
+            });
+        if let Some(name) = switch_to {
+            self.switch_profile(&name);
+        }
+        ui.label(tr!("profile-allergens"));
+        let mut profile_changed = false;
+        if let Some(profile) = self.profiles.get_mut(&self.profile) {
+            ui.horizontal_wrapped(|ui| {
+                for allergen in Allergen::ALL {
+                    let mut enabled = profile.allergens.contains(&allergen);
+                    if ui.checkbox(&mut enabled, allergen.name()).changed() {
+                        profile.allergens.retain(|&a| a != allergen);
+                        if enabled {
+                            profile.allergens.push(allergen);
+                        }
+                        profile_changed = true;
+                    }
+                }
+            });
+        }
+        ui.label(tr!("profile-nutrition-goals"))
+            .on_hover_text(tr!("profile-nutrition-goals-hint"));
+        if let Some(profile) = self.profiles.get_mut(&self.profile) {
+            let goals = &mut profile.nutrition_goals;
+            ui.horizontal_wrapped(|ui| {
+                for (enabled, label) in [
+                    (&mut goals.high_protein, tr!("goal-high-protein")),
+                    (&mut goals.low_calorie, tr!("goal-low-calorie")),
+                    (&mut goals.low_sugar, tr!("goal-low-sugar")),
+                    (&mut goals.low_fat, tr!("goal-low-fat")),
+                ] {
+                    profile_changed |= ui.checkbox(enabled, label).changed();
+                }
+            });
+        }
+        if profile_changed {
+            self.save_profiles();
+            // Goals count for what's on screen right away
+            if let Some(profile) = self.profiles.get(&self.profile) {
+                self.last_query.nutrition_goals = profile.nutrition_goals;
             }
+            self.rescore_results();
         }
-        for want in sub_ing {
-            if ing.to_lowercase().contains(&want.to_lowercase()) {
-                score += 2;
+        if ui
+            .button(tr!("profile-keep-inputs"))
+            .on_hover_text(tr!("profile-keep-inputs-hint"))
+            .clicked()
+        {
+            if let Some(profile) = self.profiles.get_mut(&self.profile) {
+                profile.taste = self.taste.trim().to_string();
+                profile.diet = self.diet;
             }
+            self.save_profiles();
         }
-    }
-    if let Some(t) = taste {
-        if detail.title.to_lowercase().contains(&t.to_lowercase()) {
-            score += 3;
+        ui.horizontal(|ui| {
+            let edit = ui.add(egui::TextEdit::singleline(&mut self.new_profile_name).hint_text(tr!("profile-new-hint")));
+            let entered = edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if (ui.button(tr!("profile-add")).clicked() || entered) && self.profiles.add(&self.new_profile_name) {
+                self.save_profiles();
+                let name = self.new_profile_name.trim().to_string();
+                self.new_profile_name.clear();
+                self.switch_profile(&name);
+            }
+        });
+        if self.profile != DEFAULT_PROFILE && ui.button(tr!("profile-delete", name = self.profile.as_str())).clicked() {
+            if let Err(e) = self.profiles.remove(&self.db, &self.profile) {
+                eprintln!("Failed to delete profile data: {}", e);
+            }
+            self.save_profiles();
+            self.switch_profile(DEFAULT_PROFILE);
         }
     }
-    score
-}
 
-fn fetch_candidates_by_ingredients(client: &Client, ingredients: &[String]) -> HashSet<String> {
-    let mut ids = HashSet::new();
-    for ing in ingredients {
-        let url = format!("{}/filter.php", API_BASE);
-        if let Ok(resp) = client.get(&url).query(&[("i", ing)]).send() {
-            if let Ok(list) = resp.json::<MealsList>() {
-                if let Some(meals) = list.meals {
-                    for m in meals {
-                        ids.insert(m.idMeal);
+    // Shown once at launch; closing it keeps the profile from last time
+    fn profile_picker(&mut self, ctx: &egui::Context) {
+        if !self.pick_profile {
+            return;
+        }
+        let mut picked = None;
+        egui::Window::new(tr!("profile-picker-title"))
+            .id(egui::Id::new("profile_picker"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                for profile in self.profiles.iter() {
+                    if ui.button(&profile.name).clicked() {
+                        picked = Some(profile.name.clone());
                     }
                 }
+            });
+        if let Some(name) = picked {
+            self.switch_profile(&name);
+            self.pick_profile = false;
+        }
+    }
+
+    /// Allergies plus whatever was typed into the exclude box.
+    fn excluded_terms(&self) -> Vec<String> {
+        let mut terms: Vec<String> = self.allergies.items().to_vec();
+        terms.extend(
+            self.exclude_ingredients
+                .split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty()),
+        );
+        terms
+    }
+
+    fn provider(&self) -> Arc<dyn RecipeProvider> {
+        Arc::clone(&self.providers[self.provider_index])
+    }
+
+    fn is_searching(&self) -> bool {
+        self.search_events.is_some() && !self.progress.done
+    }
+
+    fn cancel_search(&mut self) {
+        self.search_cancel.cancel();
+    }
+
+    // Cancels the running search and gets ready to receive a new one
+    fn start_search(&mut self, query: SearchQuery) -> (mpsc::Sender<SearchEvent>, CancellationToken) {
+        self.cancel_search();
+        self.search_cancel = CancellationToken::new();
+        // Whatever earlier requests left behind isn't about this search
+        http::take_diagnostics();
+        let (tx, rx) = mpsc::channel();
+        self.search_events = Some(rx);
+        self.progress = SearchProgress::default();
+        self.results.clear();
+        self.page = 0;
+        self.cache_ages.clear();
+        self.result_diff = None;
+        self.offline_status = None;
+        self.last_query = query;
+        self.selected_id = None;
+        // The search will add to the cache
+        self.similar.clear();
+        (tx, self.search_cancel.clone())
+    }
+
+    // Applies whatever the background search has sent since the last frame
+    fn poll_search_events(&mut self) {
+        let Some(rx) = &self.search_events else {
+            return;
+        };
+        self.progress.requests.extend(http::take_diagnostics());
+        let mut got_meals = false;
+        let mut finished = false;
+        while let Ok(event) = rx.try_recv() {
+            self.progress.record(&event);
+            match event {
+                SearchEvent::MealLoaded(meal) => {
+                    self.results.push(*meal);
+                    got_meals = true;
+                }
+                SearchEvent::Done => finished = true,
+                _ => {}
+            }
+        }
+        let summary = self.last_query.describe();
+        if finished && !summary.is_empty() {
+            let provider = self.providers[self.provider_index].name();
+            let inputs = serde_json::to_string(&self.last_query).unwrap();
+            if let Err(e) = self.db.record_search(provider, &summary, self.results.len(), &inputs) {
+                eprintln!("Failed to record search: {}", e);
             }
         }
-        thread::sleep(Duration::from_millis(100));
+        if got_meals {
+            self.sort_results();
+        }
     }
-    ids
-}
 
-fn lookup_meal(client: &Client, id: &str) -> Option<MealFull> {
-    let url = format!("{}/lookup.php", API_BASE);
-    let res = client.get(&url).query(&[("i", id)]).send().ok()?;
-    res.json::<HashMap<String, serde_json::Value>>()
-        .ok()
-        .and_then(|mut map| {
-            map.remove("meals").and_then(|v| {
-                v.as_array().and_then(|arr| {
-                    arr.get(0)
-                        .and_then(|obj| serde_json::from_value(obj.clone()).ok())
-                })
-            })
-        })
-}
+    fn sort_results(&mut self) {
+        self.sort_order.apply(&self.last_query, &self.ratings, &mut self.results);
+    }
 
-fn pretty_print(meal: &MealDetail) {
-    println!("\n====== {} ======", meal.title);
-    println!("Category: {}", meal.category);
-    println!("Area: {}", meal.area);
-    println!("Ingredients: {:?}", meal.ingredients);
-    println!("Instructions: {}", meal.instructions);
-}
+    // Resets the results for a search answered from local data on this thread
+    fn start_local_search(&mut self, query: SearchQuery) {
+        self.cancel_search();
+        self.search_events = None;
+        self.progress = SearchProgress::default();
+        self.results.clear();
+        self.page = 0;
+        self.result_diff = None;
+        self.last_query = query;
+        self.selected_id = None;
+        self.tag_filter = None;
+        self.instruction_filter.clear();
+    }
 
-#[derive(Default)]
-struct RecipeApp {
-    taste: String,
-    main_ingredients: String,
-    sub_ingredients: String,
-    cache: SharedCache,
-    top_recipe_index: Option<usize>,
-}
+    /// Everything available without the network: own recipes, favorites and
+    /// the meal cache, each recipe once. Records cache ages as a side effect.
+    fn local_pool(&mut self) -> Vec<MealDetail> {
+        let cached = self.db.all_cached_meals().unwrap_or_default();
+        self.cache_ages.clear();
+        let mut seen = HashSet::new();
+        let mut pool: Vec<MealDetail> = self
+            .my_recipes
+            .iter()
+            .chain(self.favorites.iter())
+            .filter(|m| seen.insert(m.id.clone()))
+            .cloned()
+            .collect();
+        for (meal, age) in cached {
+            let meal = MealDetail::from(meal);
+            if seen.insert(meal.id.clone()) {
+                self.cache_ages.insert(meal.id.clone(), age);
+                pool.push(meal);
+            }
+        }
+        pool
+    }
 
-impl RecipeApp {
-    fn fetch_recipes(&mut self) {
+    // Searches cached, saved and user-written recipes without any network calls
+    fn offline_search(&mut self) {
+        self.start_local_search(SearchQuery::default());
+        let index = TextIndex::build(self.local_pool());
+        self.results = index.search(&self.offline_query);
+        self.sort_results();
+        self.offline_status = Some(tr!("offline-matches-among", count = self.results.len(), total = index.len()));
+    }
+
+    fn fetch_random(&mut self, ctx: &egui::Context) {
+        if self.offline {
+            self.start_local_search(SearchQuery::default());
+            let mut pool = self.local_pool();
+            if !pool.is_empty() {
+                // Good enough randomness for picking dinner
+                let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.subsec_nanos()).unwrap_or(0);
+                self.results.push(pool.swap_remove(nanos as usize % pool.len()));
+            }
+            return;
+        }
+        let (tx, cancel) = self.start_search(SearchQuery::default());
+        self.runtime.spawn(search::run_random(self.provider(), tx, cancel, ctx.clone()));
+    }
+
+    fn fetch_latest(&mut self, ctx: &egui::Context) {
+        let (tx, cancel) = self.start_search(SearchQuery::default());
+        self.runtime.spawn(search::run_latest(self.provider(), tx, cancel, ctx.clone()));
+    }
+
+    fn fetch_recipes(&mut self, ctx: &egui::Context) {
+        let query = self.form_query();
+        self.run_query(query, ctx);
+    }
+
+    /// Puts the search the form describes at the end of the background queue.
+    fn queue_search(&mut self) {
+        let query = self.with_form_filters(self.form_query());
+        self.queue.push(self.provider(), query);
+        self.show_queue = true;
+    }
+
+    // The ingredient search as typed into the form, before the shared filters
+    fn form_query(&self) -> SearchQuery {
         let taste_opt = if self.taste.trim().is_empty() {
             None
         } else {
@@ -159,131 +799,2713 @@ impl RecipeApp {
             .filter(|s| !s.is_empty())
             .collect();
 
-        let mut all_ing = main_ing.clone();
-        all_ing.extend(sub_ing.clone());
+        if self.cook_from_pantry {
+            SearchQuery {
+                main: self.pantry.items().to_vec(),
+                pantry: Some(self.pantry.items().to_vec()),
+                ..Default::default()
+            }
+        } else {
+            SearchQuery {
+                main: main_ing,
+                sub: sub_ing,
+                taste: taste_opt,
+                ..Default::default()
+            }
+        }
+    }
 
-        let cache_arc = Arc::new(Mutex::new(Vec::new()));
-        self.cache = Arc::clone(&cache_arc);
+    fn fetch_by_name(&mut self, ctx: &egui::Context) {
+        let name = self.name_query.trim();
+        if name.is_empty() {
+            return;
+        }
+        let query = SearchQuery {
+            name: Some(name.to_string()),
+            ..Default::default()
+        };
+        self.run_query(query, ctx);
+    }
 
-        let main_clone = main_ing.clone();
-        let sub_clone = sub_ing.clone();
-        let taste_clone = taste_opt.clone();
-        let all_clone = all_ing.clone();
+    fn browse_letter(&mut self, letter: char, ctx: &egui::Context) {
+        let query = SearchQuery {
+            first_letter: Some(letter),
+            ..Default::default()
+        };
+        self.run_query(query, ctx);
+    }
 
-        thread::spawn(move || {
-            let client = Client::new();
+    // Puts a past search back into the form and runs it again
+    fn rerun_search(&mut self, query: SearchQuery, ctx: &egui::Context) {
+        self.name_query = query.name.clone().unwrap_or_default();
+        self.main_ingredients = query.main.join(", ");
+        self.sub_ingredients = query.sub.join(", ");
+        self.taste = query.taste.clone().unwrap_or_default();
+        self.cook_from_pantry = query.pantry.is_some();
+        self.category = query.category.clone();
+        self.area = query.area.clone();
+        self.diet = query.diet;
+        self.max_cost = query.max_cost;
+        self.max_calories = query.max_calories;
+        self.max_ingredients = query.max_ingredients;
+        self.max_minutes = query.max_minutes;
+        self.max_difficulty = query.max_difficulty;
+        if query.pantry.is_some() {
+            // Search the pantry as it is now, not as it was back then
+            self.fetch_recipes(ctx);
+        } else {
+            self.run_query(query, ctx);
+        }
+    }
 
-            let ids_main = if !main_clone.is_empty() {
-                fetch_candidates_by_ingredients(&client, &main_clone)
-            } else {
-                HashSet::new()
-            };
+    // Fills the form from a preset and runs it
+    fn apply_preset(&mut self, preset: Preset, ctx: &egui::Context) {
+        self.sort_order = preset.sort;
+        self.exclude_ingredients = preset.exclude;
+        self.rerun_search(preset.query, ctx);
+    }
 
-            let ids_to_use: HashSet<String> = if !ids_main.is_empty() {
-                ids_main
-            } else {
-                fetch_candidates_by_ingredients(&client, &all_clone)
-            };
+    fn save_presets(&self) {
+        if let Err(e) = self.presets.save(&self.db) {
+            eprintln!("Failed to save search presets: {}", e);
+        }
+    }
 
-            for id in ids_to_use {
-                if let Some(full) = lookup_meal(&client, &id) {
-                    let meal = MealDetail {
-                        id: full.idMeal.clone(),
-                        title: full.strMeal.clone(),
-                        category: full.strCategory.clone().unwrap_or_default(),
-                        area: full.strArea.clone().unwrap_or_default(),
-                        instructions: full.strInstructions.clone().unwrap_or_default(),
-                        ingredients: extract_ingredients(&full),
-                        score: 0,
-                    };
-                    let mut lock = cache_arc.lock().unwrap();
-                    lock.push(meal);
+    fn presets_menu(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        if self.presets.is_empty() {
+            ui.weak(tr!("presets-empty"));
+        }
+        let mut chosen = None;
+        let mut removed = None;
+        for preset in self.presets.iter() {
+            ui.horizontal(|ui| {
+                if ui.button(&preset.name).on_hover_text(preset.query.describe()).clicked() {
+                    chosen = Some(preset.clone());
+                }
+                if ui.small_button("🗑").on_hover_text(tr!("presets-delete")).clicked() {
+                    removed = Some(preset.name.clone());
+                }
+            });
+        }
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.preset_name).hint_text(tr!("presets-name-hint")).desired_width(140.0));
+            if ui.button(tr!("presets-save")).on_hover_text(tr!("presets-save-hint")).clicked() {
+                let preset = Preset {
+                    name: self.preset_name.clone(),
+                    query: self.with_form_filters(self.form_query()),
+                    sort: self.sort_order,
+                    exclude: self.exclude_ingredients.clone(),
+                };
+                if self.presets.set(preset) {
+                    self.save_presets();
+                    self.preset_name.clear();
                 }
             }
+        });
+        if let Some(name) = removed {
+            self.presets.remove(&name);
+            self.save_presets();
+        }
+        if let Some(preset) = chosen {
+            ui.close_menu();
+            self.apply_preset(preset, ctx);
+        }
+    }
 
-            let mut lock = cache_arc.lock().unwrap();
-            for m in lock.iter_mut() {
-                m.score = score_meal(m, &main_clone, &sub_clone, &taste_clone);
+    fn history_menu(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        let history = match self.db.recent_searches(MAX_HISTORY) {
+            Ok(history) => history,
+            Err(e) => {
+                ui.label(tr!("history-load-failed", error = e.to_string()));
+                return;
             }
-            lock.sort_by(|a, b| b.score.cmp(&a.score));
-        });
+        };
+        if history.is_empty() {
+            ui.weak(tr!("history-empty"));
+        }
+        for record in history {
+            let label = tr!("history-entry", summary = record.summary.as_str(), count = record.result_count);
+            if ui.button(label).clicked() {
+                if let Ok(query) = serde_json::from_str(&record.inputs) {
+                    self.rerun_search(query, ctx);
+                }
+                ui.close_menu();
+            }
+        }
+    }
+
+    // Adds the filters and weights shared by every search form
+    fn with_form_filters(&self, mut query: SearchQuery) -> SearchQuery {
+        if self.hide_excluded {
+            query.excluded = self.excluded_terms();
+        }
+        query.category = self.category.clone();
+        query.area = self.area.clone();
+        query.diet = self.diet;
+        query.max_cost = self.max_cost;
+        query.max_calories = self.max_calories;
+        query.max_ingredients = self.max_ingredients;
+        query.max_minutes = self.max_minutes;
+        query.max_difficulty = self.max_difficulty;
+        query.prices = self.prices.clone();
+        query.on_hand = self.pantry.items().to_vec();
+        query.weights = self.scoring.clone();
+        query.strategy = self.scoring_strategy;
+        if let Some(profile) = self.profiles.get(&self.profile) {
+            query.nutrition_goals = profile.nutrition_goals;
+        }
+        if self.seasonal_boost {
+            query.seasonal = seasonal::in_season_now(self.region);
+        }
+        if self.leftover_boost {
+            query.leftovers = self.leftovers.ingredients();
+        }
+        query
+    }
+
+    // Runs the query with the form's filters, offline or against the current provider
+    fn run_query(&mut self, query: SearchQuery, ctx: &egui::Context) {
+        let query = self.with_form_filters(query);
+        let diff = (!self.results.is_empty()).then(|| ResultDiff::new(&self.results));
+        if self.offline {
+            self.start_local_search(query.clone());
+            self.result_diff = diff;
+            self.results = self
+                .local_pool()
+                .into_iter()
+                .filter(|m| query.selects(m))
+                .map(|mut m| {
+                    m.score = query.score(&m);
+                    m
+                })
+                .collect();
+            self.sort_results();
+            self.offline_status = Some(tr!("offline-matches", count = self.results.len()));
+            return;
+        }
+
+        // A new search supersedes whatever is still running
+        let (tx, cancel) = self.start_search(query.clone());
+        self.result_diff = diff;
+        self.tag_filter = None;
+        self.instruction_filter.clear();
+
+        // The user's own recipes don't need the network; rank them in right away
+        for recipe in self.my_recipes.iter().filter(|r| query.selects(r)) {
+            let mut recipe = recipe.clone();
+            recipe.score = query.score(&recipe);
+            self.results.push(recipe);
+        }
+        self.sort_results();
+
+        self.runtime.spawn(search::run_search(self.provider(), query, tx, cancel, ctx.clone()));
     }
 }
 
-// Helper function for safe number parsing
-fn parse_index(input: &str, max: usize) -> Option<usize> {
-    let n = input.trim().parse::<i32>().ok()?;
-    let idx = usize::try_from(n).ok()?;
-    if idx == 0 || idx > max {
-        None
-    } else {
-        Some(idx - 1)
+// Shared by the GUI and the headless mode
+fn open_database(config: &AppConfig) -> Arc<Database> {
+    let db_path = config.db_path();
+    Arc::new(Database::open(&db_path).unwrap_or_else(|e| {
+        eprintln!("Failed to open {}, nothing will be saved this session: {}", db_path.display(), e);
+        Database::in_memory().expect("failed to create in-memory database")
+    }))
+}
+
+/// The shared HTTP client; bad proxy settings fall back to a direct connection.
+fn build_client(config: &AppConfig) -> Client {
+    http::build_client(config.connect_timeout(), config.request_timeout(), &config.proxy).unwrap_or_else(|e| {
+        eprintln!("Invalid network settings, connecting directly: {}", e);
+        Client::new()
+    })
+}
+
+/// TheMealDB first, then every service the config has keys for.
+fn build_providers(config: &AppConfig, db: &Arc<Database>) -> Vec<Arc<dyn RecipeProvider>> {
+    let client = build_client(config);
+
+    let mut themealdb = TheMealDb::new(
+        client.clone(),
+        config.themealdb_base(),
+        MealCache::new(Arc::clone(db), DEFAULT_TTL),
+        config.requests_per_second,
+    )
+    .with_premium(config.themealdb_premium());
+    if let Some(fallback) = config.themealdb_fallback() {
+        themealdb = themealdb.with_fallback(fallback);
     }
+    let mut providers: Vec<Arc<dyn RecipeProvider>> = vec![Arc::new(themealdb)];
+    if let Some(key) = &config.spoonacular_api_key {
+        providers.push(Arc::new(Spoonacular::new(client.clone(), key, config.requests_per_second)));
+    }
+    if let (Some(id), Some(key)) = (&config.edamam_app_id, &config.edamam_app_key) {
+        providers.push(Arc::new(Edamam::new(
+            client.clone(),
+            id,
+            key,
+            config.edamam_health_labels.clone(),
+            config.requests_per_second,
+        )));
+    }
+    providers
 }
 
-impl eframe::App for RecipeApp {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        egui::CentralPanel::default().show(ctx, |ui| {
-            ui.heading("Food Recipe Finder (GUI)");
+const MAX_SUGGESTIONS: usize = 8;
+/// Past searches offered in the history menu.
+const MAX_HISTORY: usize = 20;
+/// Results listed per page.
+const PAGE_SIZE: usize = 10;
+/// Cards per row in the grid view.
+const GRID_COLUMNS: usize = 5;
+/// Width of a grid card and its square thumbnail.
+const CARD_WIDTH: f32 = 140.0;
+/// Budget the search form starts from when the limit is ticked.
+const DEFAULT_BUDGET: f64 = 15.0;
+/// Calories per serving the search form starts from when that limit is ticked.
+const DEFAULT_CALORIE_BUDGET: f64 = 700.0;
+/// Ingredient count the search form starts from when that limit is ticked.
+const DEFAULT_MAX_INGREDIENTS: usize = 8;
+/// Time limits offered in the search form, in minutes.
+const MAX_TIME_CHOICES: [u64; 2] = [30, 60];
+/// Suggestions under "Similar recipes".
+const MAX_SIMILAR: usize = 5;
+/// Side of an ingredient thumbnail in the detail view and shopping list.
+const INGREDIENT_IMAGE_SIZE: f32 = 20.0;
+/// Top results listed under a finished search in the queue window.
+const QUEUE_PREVIEW: usize = 3;
+/// Produce named in the "in season now" strip; the rest is in its tooltip.
+const MAX_SEASONAL_SUGGESTIONS: usize = 10;
+/// Days a new leftover is assumed to keep.
+const DEFAULT_LEFTOVER_DAYS: u32 = 3;
+/// Soft daily request limit offered when the limit is switched on; about a
+/// free Spoonacular key's allowance.
+const DEFAULT_REQUEST_LIMIT: u64 = 150;
+/// How days are shown in the meal plan: "Mon 12 Oct".
+const PLAN_DAY_FORMAT: &str = "%a %d %b";
 
-            ui.horizontal(|ui| {
-                ui.label("Taste:");
-                ui.text_edit_singleline(&mut self.taste);
-            });
-            ui.horizontal(|ui| {
-                ui.label("Main ingredients:");
-                ui.text_edit_singleline(&mut self.main_ingredients);
+/// Leftovers this close to their date are shown as a warning.
+const LEFTOVER_WARN_DAYS: i64 = 1;
+/// Text size of the step on screen in cooking mode.
+const COOKING_STEP_SIZE: f32 = 40.0;
+/// Text size of cooking mode's ingredient reminders, timers and buttons.
+const COOKING_DETAIL_SIZE: f32 = 26.0;
+
+// Comma separated ingredient field that suggests completions for the last entry
+// Returns true when the user pressed Enter in the field
+fn ingredient_input(ui: &mut egui::Ui, id: &str, text: &mut String, known: &[String]) -> bool {
+    let response = ui.text_edit_singleline(text);
+    let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+    let popup_id = ui.make_persistent_id(("ingredient_autocomplete", id));
+
+    let last = text.rsplit(',').next().unwrap_or("").trim().to_lowercase();
+    let mut suggestions: Vec<&String> = Vec::new();
+    if last.len() >= 2 {
+        // Prefix matches first, then anything containing the typed text
+        suggestions.extend(known.iter().filter(|k| k.to_lowercase().starts_with(&last)));
+        suggestions.extend(known.iter().filter(|k| {
+            let k = k.to_lowercase();
+            !k.starts_with(&last) && k.contains(&last)
+        }));
+        suggestions.retain(|k| !k.eq_ignore_ascii_case(&last));
+        suggestions.truncate(MAX_SUGGESTIONS);
+    }
+
+    if response.has_focus() && !suggestions.is_empty() {
+        ui.memory_mut(|m| m.open_popup(popup_id));
+    }
+    let mut chosen = None;
+    egui::popup_below_widget(
+        ui,
+        popup_id,
+        &response,
+        egui::PopupCloseBehavior::CloseOnClick,
+        |ui| {
+            for suggestion in &suggestions {
+                if ui.selectable_label(false, suggestion.as_str()).clicked() {
+                    chosen = Some(suggestion.to_string());
+                }
+            }
+        },
+    );
+    if let Some(choice) = chosen {
+        let prefix = match text.rfind(',') {
+            Some(pos) => format!("{}, ", text[..pos].trim_end()),
+            None => String::new(),
+        };
+        *text = format!("{}{}", prefix, choice);
+    }
+    submitted
+}
+
+// "Any" plus every option; keeps `selected` as None for "Any"
+fn filter_combo(ui: &mut egui::Ui, label: &str, options: &[String], selected: &mut Option<String>) {
+    ui.label(label);
+    ui.add_enabled_ui(!options.is_empty(), |ui| {
+        egui::ComboBox::from_id_source(label)
+            .selected_text(selected.clone().unwrap_or_else(|| tr!("filter-any")))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(selected, None, tr!("filter-any"));
+                for option in options {
+                    ui.selectable_value(selected, Some(option.clone()), option);
+                }
             });
+    });
+}
+
+fn favorite_button(ui: &mut egui::Ui, favorites: &mut Favorites, db: &Database, meal: &MealDetail) {
+    let is_favorite = favorites.contains(&meal.id);
+    let (icon, hover) = if is_favorite {
+        ("★", tr!("favorite-remove"))
+    } else {
+        ("☆", tr!("favorite-add"))
+    };
+    if ui.button(icon).on_hover_text(hover).clicked() {
+        favorites.toggle(meal);
+        if let Err(e) = favorites.save(db) {
+            eprintln!("Failed to save favorites: {}", e);
+        }
+    }
+}
+
+impl RecipeApp {
+    fn my_recipes_ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if ui.button(tr!("my-recipes-new")).clicked() {
+                self.recipe_draft = Some(RecipeDraft::new());
+            }
+            if ui.button(tr!("my-recipes-import"))
+                .on_hover_text(tr!("my-recipes-import-hint"))
+                .clicked()
+            {
+                let paths = import::pick_files();
+                if !paths.is_empty() {
+                    let report = import::import_files(&mut self.my_recipes, &paths);
+                    if let Err(e) = self.my_recipes.save(&self.db) {
+                        eprintln!("Failed to save my recipes: {}", e);
+                    }
+                    self.import_report = Some(report);
+                }
+            }
+        });
+        if let Some(report) = &self.import_report {
+            let label = ui.label(report.summary());
+            if !report.errors.is_empty() {
+                label.on_hover_text(report.errors.join("\n"));
+            }
+        }
+        if self.my_recipes.is_empty() {
+            ui.label(tr!("my-recipes-empty"));
+        }
+        let mut edit = None;
+        let mut delete = None;
+        ScrollArea::vertical().id_source("my_recipes_list").max_height(200.0).show(ui, |ui| {
+            for recipe in self.my_recipes.iter() {
+                ui.horizontal(|ui| {
+                    let selected = self.selected_id.as_ref() == Some(&recipe.id);
+                    if ui.selectable_label(selected, &recipe.title).clicked() {
+                        self.selected_id = Some(recipe.id.clone());
+                    }
+                    if ui.small_button("✏").on_hover_text(tr!("edit")).clicked() {
+                        edit = Some(RecipeDraft::from_meal(recipe));
+                    }
+                    if ui.small_button("🗑").on_hover_text(tr!("delete")).clicked() {
+                        delete = Some(recipe.id.clone());
+                    }
+                });
+            }
+        });
+        if let Some(draft) = edit {
+            self.recipe_draft = Some(draft);
+        }
+        if let Some(id) = delete {
+            self.my_recipes.remove(&id);
+            self.results.retain(|m| m.id != id);
+            if let Err(e) = self.my_recipes.save(&self.db) {
+                eprintln!("Failed to save my recipes: {}", e);
+            }
+        }
+
+        let Some(draft) = &mut self.recipe_draft else {
+            return;
+        };
+        ui.separator();
+        ui.heading(if draft.is_new() { tr!("recipe-form-new") } else { tr!("recipe-form-edit") });
+        egui::Grid::new("recipe_form").num_columns(2).show(ui, |ui| {
+            ui.label(tr!("field-title"));
+            ui.text_edit_singleline(&mut draft.title);
+            ui.end_row();
+            ui.label(tr!("field-category"));
+            ui.text_edit_singleline(&mut draft.category);
+            ui.end_row();
+            ui.label(tr!("field-area"));
+            ui.text_edit_singleline(&mut draft.area);
+            ui.end_row();
+        });
+        ui.label(tr!("recipe-form-ingredients"));
+        let mut remove_row = None;
+        for (i, (ingredient, measure)) in draft.ingredients.iter_mut().enumerate() {
             ui.horizontal(|ui| {
-                ui.label("Sub ingredients:");
-                ui.text_edit_singleline(&mut self.sub_ingredients);
+                ui.add(egui::TextEdit::singleline(ingredient).hint_text(tr!("hint-ingredient")).desired_width(160.0));
+                ui.add(egui::TextEdit::singleline(measure).hint_text(tr!("hint-measure")).desired_width(100.0));
+                if ui.small_button("✖").clicked() {
+                    remove_row = Some(i);
+                }
             });
+        }
+        if let Some(i) = remove_row {
+            draft.ingredients.remove(i);
+        }
+        if ui.button(tr!("recipe-form-add-ingredient")).clicked() {
+            draft.ingredients.push((String::new(), String::new()));
+        }
+        ui.label(tr!("instructions-label"));
+        ui.text_edit_multiline(&mut draft.instructions);
+
+        let mut close = false;
+        ui.horizontal(|ui| {
+            if ui.add_enabled(draft.is_valid(), egui::Button::new(tr!("save"))).clicked() {
+                let recipe = draft.to_meal();
+                let id = self.my_recipes.upsert(recipe.clone());
+                // Keep an edited recipe that's on screen in step with the store
+                if let Some(shown) = self.results.iter_mut().find(|m| m.id == id) {
+                    *shown = MealDetail { score: shown.score, ..recipe };
+                }
+                if let Err(e) = self.my_recipes.save(&self.db) {
+                    eprintln!("Failed to save my recipes: {}", e);
+                }
+                self.selected_id = Some(id);
+                close = true;
+            }
+            if ui.button(tr!("cancel")).clicked() {
+                close = true;
+            }
+        });
+        if close {
+            self.recipe_draft = None;
+        }
+    }
 
-            if ui.button("Fetch Recipes").clicked() {
-                self.fetch_recipes();
+    fn record_viewed(&mut self, meal: &MealDetail) {
+        if self.recently_viewed.record(meal) {
+            if let Err(e) = self.recently_viewed.save(&self.db) {
+                eprintln!("Failed to save recently viewed: {}", e);
             }
+        }
+    }
 
-            let cache_lock = self.cache.lock().unwrap();
-            if !cache_lock.is_empty() {
-                ui.separator();
-                ui.label("Top recipes:");
-                for (i, meal) in cache_lock.iter().enumerate().take(10) {
-                    if ui.button(format!("{}: {} (Score {})", i + 1, meal.title, meal.score)).clicked() {
-                        self.top_recipe_index = Some(i);
+    /// Shows `meal` in the detail pane, results or not.
+    fn show_meal(&mut self, meal: &MealDetail) {
+        // Recently viewed is where the detail pane finds meals that aren't results
+        self.record_viewed(meal);
+        self.selected_id = Some(meal.id.clone());
+    }
+
+    fn open_detail_window(&mut self, meal: &MealDetail) {
+        self.record_viewed(meal);
+        if !self.detail_windows.iter().any(|m| m.id == meal.id) {
+            self.detail_windows.push(meal.clone());
+        }
+    }
+
+    fn detail_windows_ui(&mut self, ctx: &egui::Context) {
+        let mut closed = Vec::new();
+        for meal in self.detail_windows.clone() {
+            let viewport_id = egui::ViewportId::from_hash_of(("recipe_window", &meal.id));
+            let builder = egui::ViewportBuilder::default()
+                .with_title(&meal.title)
+                .with_inner_size([480.0, 640.0]);
+            ctx.show_viewport_immediate(viewport_id, builder, |ctx, class| {
+                if class == egui::ViewportClass::Embedded {
+                    // Backends without multi-window support get an ordinary egui window
+                    let mut open = true;
+                    egui::Window::new(&meal.title)
+                        .id(egui::Id::new(viewport_id))
+                        .open(&mut open)
+                        .show(ctx, |ui| {
+                            ScrollArea::vertical().show(ui, |ui| self.recipe_detail_ui(ui, &meal));
+                        });
+                    if !open {
+                        closed.push(meal.id.clone());
+                    }
+                } else {
+                    egui::CentralPanel::default().show(ctx, |ui| {
+                        ScrollArea::vertical().show(ui, |ui| self.recipe_detail_ui(ui, &meal));
+                    });
+                    if ctx.input(|i| i.viewport().close_requested()) {
+                        closed.push(meal.id.clone());
                     }
                 }
-            }
+            });
+        }
+        self.detail_windows.retain(|m| !closed.contains(&m.id));
+    }
 
-            if let Some(index) = self.top_recipe_index {
-                if let Some(meal) = cache_lock.get(index) {
-                    ui.separator();
-                    ui.label("Recipe Details:");
-                    ScrollArea::vertical()
-                        .max_height(300.0)
-                        .show(ui, |ui| {
-                            ui.heading(&meal.title);
-                            ui.label(format!("Category: {}", meal.category));
-                            ui.label(format!("Area: {}", meal.area));
-                            ui.separator();
-                            ui.label("Ingredients:");
-                            for ing in &meal.ingredients {
-                                ui.label(format!("- {}", ing));
+    // Everything about one recipe; shared by the detail pane and the pop-out windows
+    fn recipe_detail_ui(&mut self, ui: &mut egui::Ui, meal: &MealDetail) {
+        let excluded = self.excluded_terms();
+        ui.horizontal(|ui| {
+            ui.heading(&meal.title);
+            favorite_button(ui, &mut self.favorites, &self.db, meal);
+            ui.menu_button(tr!("plan-add"), |ui| {
+                for day in meal_plan::plan_days(chrono::Local::now().date_naive()) {
+                    if ui.button(day.format(PLAN_DAY_FORMAT).to_string()).clicked() {
+                        self.meal_plan.add(day, meal);
+                        if let Err(e) = self.meal_plan.save(&self.db) {
+                            eprintln!("Failed to save meal plan: {}", e);
+                        }
+                        ui.close_menu();
+                    }
+                }
+            });
+            if self.my_recipes.get(&meal.id).is_some() && ui.button(tr!("detail-edit")).clicked() {
+                self.recipe_draft = Some(RecipeDraft::from_meal(meal));
+                self.show_my_recipes = true;
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr!("rating-label"));
+            let current = self.ratings.get(&meal.id);
+            for stars in 1..=ratings::MAX_STARS {
+                let star = if stars <= current { "★" } else { "☆" };
+                if ui.small_button(star).on_hover_text(tr!("rating-star-hint", stars = stars, max = ratings::MAX_STARS)).clicked() {
+                    // Clicking the current rating again clears it
+                    let new = if stars == current { 0 } else { stars };
+                    if let Err(e) = self.ratings.set(&self.db, &meal.id, new) {
+                        eprintln!("Failed to save rating: {}", e);
+                    }
+                }
+            }
+        });
+        ui.horizontal(|ui| {
+            if !meal.youtube.is_empty() && ui.button(tr!("watch-video")).clicked() {
+                ui.ctx().open_url(egui::OpenUrl::new_tab(&meal.youtube));
+            }
+            if !meal.source.is_empty() && ui.button(tr!("open-source")).clicked() {
+                ui.ctx().open_url(egui::OpenUrl::new_tab(&meal.source));
+            }
+        });
+        ui.horizontal(|ui| {
+            if ui.button(tr!("export-markdown")).clicked() {
+                if let Some(path) = export::save_dialog(&meal.title, "md", "Markdown") {
+                    self.export_status = Some(match export::markdown::export(&path, &measure::convert_meal(meal, self.units)) {
+                        Ok(()) => tr!("saved-path", path = path.display().to_string()),
+                        Err(e) => tr!("export-failed", error = e.to_string()),
+                    });
+                }
+            }
+            if ui.button(tr!("export-pdf")).clicked() {
+                if let Some(path) = export::save_dialog(&meal.title, "pdf", "PDF") {
+                    self.export_status = Some(match export::pdf::export(&path, &measure::convert_meal(meal, self.units)) {
+                        Ok(()) => tr!("saved-path", path = path.display().to_string()),
+                        Err(e) => tr!("export-failed", error = e.to_string()),
+                    });
+                }
+            }
+            if ui.button(tr!("export-html")).clicked() {
+                if let Some(path) = export::save_dialog(&meal.title, "html", "HTML") {
+                    self.export_status = Some(match export::html::export(&path, &measure::convert_meal(meal, self.units)) {
+                        Ok(()) => {
+                            if self.open_html_export {
+                                // Percent-encoded, so paths with spaces or '#' still open
+                                if let Ok(url) = url::Url::from_file_path(&path) {
+                                    ui.ctx().open_url(egui::OpenUrl::new_tab(url));
+                                }
                             }
-                            ui.separator();
-                            ui.label("Instructions:");
-                            ui.label(&meal.instructions);
-                        });
+                            tr!("saved-path", path = path.display().to_string())
+                        }
+                        Err(e) => tr!("export-failed", error = e.to_string()),
+                    });
                 }
             }
+            ui.checkbox(&mut self.open_html_export, tr!("export-html-open"));
+            if ui.button(tr!("export-tasks")).on_hover_text(tr!("export-tasks-hint")).clicked() {
+                let converted = measure::convert_meal(meal, self.units);
+                let (_, need) = pantry::coverage(&converted, self.pantry.items());
+                let mut items = shopping::build([&converted]);
+                items.retain(|item| need.contains(&item.ingredient));
+                let path = self.tasks_file.display().to_string();
+                self.export_status = Some(match export::tasks::append(&self.tasks_file, &items) {
+                    Ok(count) => tr!("tasks-added", count = count, path = path),
+                    Err(e) => tr!("export-failed", error = e.to_string()),
+                });
+            }
+            if let Some(status) = &self.export_status {
+                ui.label(status);
+            }
         });
-    }
-}
-
+        if let Some(uri) = images::photo_uri(&meal.thumb, false, self.offline.then_some(self.image_dir.as_path())) {
+            ui.add(egui::Image::new(uri).max_width(240.0).rounding(6.0));
+        }
+        if let Some(age) = self.cache_ages.get(&meal.id) {
+            cache_age_label(ui, *age);
+        }
+        ui.label(tr!("detail-category", category = meal.category.as_str()));
+        ui.label(tr!("detail-area", area = meal.area.as_str()));
+        if !meal.tags.is_empty() {
+            ui.horizontal_wrapped(|ui| {
+                for tag in &meal.tags {
+                    let active = self.tag_filter.as_ref() == Some(tag);
+                    if ui
+                        .selectable_label(active, format!("#{}", tag))
+                        .on_hover_text(tr!("tag-filter-hint"))
+                        .clicked()
+                    {
+                        self.tag_filter = if active { None } else { Some(tag.clone()) };
+                    }
+                }
+            });
+        }
+        ui.separator();
+        let base_servings = meal.servings.filter(|&s| s > 0).unwrap_or(nutrition::DEFAULT_SERVINGS);
+        let servings = self.servings.entry(meal.id.clone()).or_insert(base_servings);
+        ui.horizontal(|ui| {
+            ui.label(tr!("servings-before"));
+            ui.add(egui::DragValue::new(servings).range(1..=50));
+            ui.label(tr!("servings-after"));
+            egui::ComboBox::from_id_source(("units", &meal.id))
+                .selected_text(self.units.label())
+                .show_ui(ui, |ui| {
+                    for system in UnitSystem::ALL {
+                        ui.selectable_value(&mut self.units, system, system.label());
+                    }
+                });
+            let reset = ui.small_button("↺").on_hover_text(tr!("servings-reset"));
+            if *servings != base_servings && reset.clicked() {
+                *servings = base_servings;
+            }
+        });
+        let factor = f64::from(*servings) / f64::from(base_servings);
+        let form = self.form_query();
+        let wanted: Vec<String> = form.main.into_iter().chain(form.sub).collect();
+        let mut on_hand = self.pantry.items().to_vec();
+        on_hand.extend(self.leftovers.ingredients());
+        let mut lines = Vec::new();
+        for (i, ing) in meal.ingredients.iter().enumerate() {
+            let line = match meal.measures.get(i).filter(|m| !m.is_empty()) {
+                Some(measure) => {
+                    let shown = measure::convert(&measure::scale(measure, factor), self.units);
+                    format!("{} {}", shown, ing)
+                }
+                None => ing.clone(),
+            };
+            let text = highlighted_ingredient(ui, ing, format!("- {}", line), &wanted, &on_hand);
+            ingredient_row(ui, ing, text, !self.offline);
+            lines.push((ing.clone(), line));
+        }
+        if !wanted.is_empty() || !on_hand.is_empty() {
+            ui.label(egui::RichText::new(tr!("ingredient-highlight-legend")).small().weak());
+        }
+        let hits = filters::excluded_matches(meal, &excluded);
+        if !hits.is_empty() {
+            ui.colored_label(
+                ui.visuals().warn_fg_color,
+                tr!("excluded-warning", items = hits.join(", ")),
+            );
+        }
+        ui.horizontal_wrapped(|ui| allergen_badges(ui, meal, active_allergens(&self.profiles, &self.profile)));
+        if !self.pantry.is_empty() {
+            let (have, need) = pantry::coverage(meal, self.pantry.items());
+            ui.label(tr!("pantry-have", items = join_or_dash(&have)));
+            ui.label(tr!("pantry-need", items = join_or_dash(&need)));
+        }
+        nutrition_panel(ui, meal);
+        cost_panel(ui, meal, &self.prices, &self.currency);
+        self.similar_ui(ui, meal);
+        ui.separator();
+        ui.label(tr!("notes-label"));
+        let note = egui::TextEdit::multiline(self.notes.entry(&meal.id))
+            .hint_text(tr!("notes-hint"))
+            .desired_rows(2);
+        if ui.add(note).changed() {
+            if let Err(e) = self.notes.save(&self.db, &meal.id) {
+                eprintln!("Failed to save note: {}", e);
+            }
+        }
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.label(tr!("instructions-label"));
+            if !meal.instructions.trim().is_empty()
+                && ui.button(tr!("cooking-start")).on_hover_text(tr!("cooking-start-hint")).clicked()
+            {
+                self.cooking = Some(CookingMode::new(meal, lines));
+                ui.ctx().send_viewport_cmd_to(egui::ViewportId::ROOT, egui::ViewportCommand::Fullscreen(true));
+            }
+            #[cfg(feature = "speech")]
+            if !meal.instructions.trim().is_empty() {
+                self.read_aloud_ui(ui, meal);
+            }
+        });
+        let reading = self.step_being_read(meal);
+        let done = self.done_steps.entry(meal.id.clone()).or_default();
+        for (i, step) in steps::parse_steps(&meal.instructions).iter().enumerate() {
+            let mut checked = done.contains(&i);
+            let mut text = egui::RichText::new(format!("{}. {}", i + 1, step.text));
+            if checked {
+                text = text.strikethrough().weak();
+            }
+            if reading == Some(i) {
+                text = text.strong();
+            }
+            ui.horizontal_wrapped(|ui| {
+                if ui.checkbox(&mut checked, text).changed() {
+                    if checked {
+                        done.insert(i);
+                    } else {
+                        done.remove(&i);
+                    }
+                }
+                if let Some(duration) = step.duration {
+                    let label = format!("⏱ {}", timers::format_duration(duration));
+                    if ui.small_button(label).on_hover_text(tr!("timer-start-hint")).clicked() {
+                        let name = tr!("timer-name", title = meal.title.as_str(), step = i + 1);
+                        self.timers.push(StepTimer::start(name, duration));
+                    }
+                }
+            });
+        }
+    }
+
+    fn meal_of_the_day_ui(&mut self, ui: &mut egui::Ui) {
+        if !self.show_meal_of_the_day {
+            return;
+        }
+        let Some(meal) = self.meal_of_the_day.get().cloned() else {
+            return;
+        };
+        egui::Frame::group(ui.style()).show(ui, |ui| {
+            ui.horizontal(|ui| {
+                if let Some(uri) = images::photo_uri(&meal.thumb, false, self.offline.then_some(self.image_dir.as_path())) {
+                    ui.add(egui::Image::new(uri).max_width(64.0).rounding(4.0));
+                }
+                ui.vertical(|ui| {
+                    ui.weak(tr!("meal-of-the-day"));
+                    ui.strong(&meal.title);
+                    let place: Vec<&str> = [meal.category.as_str(), meal.area.as_str()]
+                        .into_iter()
+                        .filter(|s| !s.is_empty())
+                        .collect();
+                    ui.label(place.join(" · "));
+                    ui.horizontal(|ui| {
+                        if ui.button(tr!("meal-of-the-day-show")).clicked() {
+                            self.show_meal(&meal);
+                        }
+                        if ui.button(tr!("dismiss")).clicked() {
+                            self.show_meal_of_the_day = false;
+                        }
+                    });
+                });
+            });
+        });
+    }
+
+    // "In season now" line above the search form; clicking produce adds it to the sub ingredients
+    fn seasonal_strip(&mut self, ui: &mut egui::Ui) {
+        let produce = seasonal::in_season_now(self.region);
+        if produce.is_empty() {
+            return;
+        }
+        ui.horizontal_wrapped(|ui| {
+            ui.label(tr!("seasonal-now"));
+            for item in produce.iter().take(MAX_SEASONAL_SUGGESTIONS) {
+                if ui.small_button(item).on_hover_text(tr!("seasonal-add-hint")).clicked() {
+                    if !self.sub_ingredients.trim().is_empty() {
+                        self.sub_ingredients.push_str(", ");
+                    }
+                    self.sub_ingredients.push_str(item);
+                }
+            }
+            if produce.len() > MAX_SEASONAL_SUGGESTIONS {
+                ui.weak("…").on_hover_text(produce[MAX_SEASONAL_SUGGESTIONS..].join(", "));
+            }
+        });
+    }
+
+    fn leftovers_ui(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+        let (ingredient, quantity, days) = &mut self.leftover_input;
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(ingredient).hint_text(tr!("leftover-ingredient-hint")).desired_width(100.0));
+            ui.add(egui::TextEdit::singleline(quantity).hint_text(tr!("leftover-quantity-hint")).desired_width(60.0));
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr!("leftover-keeps"));
+            ui.add(egui::DragValue::new(days).range(0..=30));
+            ui.label(tr!("leftover-days"));
+            if ui.add_enabled(!ingredient.trim().is_empty(), egui::Button::new(tr!("add"))).clicked() {
+                let today = chrono::Local::now().date_naive();
+                self.leftovers.add(Leftover {
+                    ingredient: ingredient.trim().to_string(),
+                    quantity: quantity.trim().to_string(),
+                    expires: Some(today + chrono::Days::new(u64::from(*days))),
+                });
+                ingredient.clear();
+                quantity.clear();
+                *days = DEFAULT_LEFTOVER_DAYS;
+                changed = true;
+            }
+        });
+        let today = chrono::Local::now().date_naive();
+        let mut remove = None;
+        for (i, leftover) in self.leftovers.iter().enumerate() {
+            ui.horizontal(|ui| {
+                if ui.small_button("✖").on_hover_text(tr!("leftover-used")).clicked() {
+                    remove = Some(i);
+                }
+                if leftover.quantity.is_empty() {
+                    ui.label(&leftover.ingredient);
+                } else {
+                    ui.label(format!("{} ({})", leftover.ingredient, leftover.quantity));
+                }
+                match leftover.days_left(today) {
+                    Some(days) if days < 0 => {
+                        ui.colored_label(ui.visuals().error_fg_color, tr!("leftover-expired"));
+                    }
+                    Some(0) => {
+                        ui.colored_label(ui.visuals().warn_fg_color, tr!("leftover-today"));
+                    }
+                    Some(days) if days <= LEFTOVER_WARN_DAYS => {
+                        ui.colored_label(ui.visuals().warn_fg_color, tr!("leftover-days-left", days = days));
+                    }
+                    Some(days) => {
+                        ui.weak(tr!("leftover-days-left", days = days));
+                    }
+                    None => {}
+                }
+            });
+        }
+        if let Some(i) = remove {
+            self.leftovers.remove(i);
+            changed = true;
+        }
+        if changed {
+            if let Err(e) = self.leftovers.save(&self.db) {
+                eprintln!("Failed to save leftovers: {}", e);
+            }
+        }
+    }
+
+    fn meal_plan_ui(&mut self, ui: &mut egui::Ui) {
+        if self.meal_plan.is_empty() {
+            ui.weak(tr!("plan-empty"));
+            return;
+        }
+        let mut remove = None;
+        for (i, planned) in self.meal_plan.items().iter().enumerate() {
+            ui.horizontal(|ui| {
+                if ui.small_button("✖").on_hover_text(tr!("plan-remove")).clicked() {
+                    remove = Some(i);
+                }
+                ui.label(planned.day.format(PLAN_DAY_FORMAT).to_string());
+                ui.label(&planned.title);
+            });
+        }
+        if let Some(i) = remove {
+            self.meal_plan.remove(i);
+            if let Err(e) = self.meal_plan.save(&self.db) {
+                eprintln!("Failed to save meal plan: {}", e);
+            }
+        }
+        if ui.button(tr!("plan-export")).on_hover_text(tr!("plan-export-hint")).clicked() {
+            if let Some(path) = export::save_dialog("meal_plan", "ics", "iCalendar") {
+                self.meal_plan_status = Some(match export::ics::export(&path, self.meal_plan.items()) {
+                    Ok(()) => tr!("saved-path", path = path.display().to_string()),
+                    Err(e) => tr!("export-failed", error = e.to_string()),
+                });
+            }
+        }
+        if let Some(status) = &self.meal_plan_status {
+            ui.label(status);
+        }
+    }
+
+    /// Searches with the leftovers as sub ingredients, soonest to expire
+    /// first, and ranks recipes by how many of them they use up.
+    fn use_up_leftovers(&mut self, ctx: &egui::Context) {
+        self.main_ingredients.clear();
+        self.sub_ingredients = self.leftovers.ingredients().join(", ");
+        self.cook_from_pantry = false;
+        self.leftover_boost = true;
+        self.fetch_recipes(ctx);
+    }
+
+    fn similar_ui(&mut self, ui: &mut egui::Ui, meal: &MealDetail) {
+        egui::CollapsingHeader::new(tr!("similar-title"))
+            .id_source(("similar", &meal.id))
+            .show(ui, |ui| {
+                let db = &self.db;
+                let similar = self.similar.entry(meal.id.clone()).or_insert_with(|| {
+                    let cached: Vec<MealDetail> = db
+                        .all_cached_meals()
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|(full, _)| full.into())
+                        .collect();
+                    matching::most_similar(meal, &cached, MAX_SIMILAR)
+                        .into_iter()
+                        .map(|(m, similarity)| (m.clone(), similarity))
+                        .collect()
+                });
+                if similar.is_empty() {
+                    ui.weak(tr!("similar-empty"));
+                }
+                let mut open = None;
+                for (other, similarity) in similar.iter() {
+                    ui.horizontal(|ui| {
+                        ui.weak(format!("{:.0}%", similarity * 100.0));
+                        if ui.link(&other.title).clicked() {
+                            open = Some(other.clone());
+                        }
+                    });
+                }
+                if let Some(other) = open {
+                    self.show_meal(&other);
+                }
+            });
+    }
+
+    fn recently_viewed_ui(&mut self, ui: &mut egui::Ui) {
+        if self.recently_viewed.is_empty() {
+            ui.weak(tr!("recent-empty"));
+            return;
+        }
+        ScrollArea::vertical().id_source("recently_viewed").max_height(200.0).show(ui, |ui| {
+            for meal in self.recently_viewed.iter() {
+                if ui.selectable_label(self.selected_id.as_ref() == Some(&meal.id), &meal.title).clicked() {
+                    self.selected_id = Some(meal.id.clone());
+                }
+            }
+        });
+        if ui.small_button(tr!("clear")).clicked() {
+            self.recently_viewed.clear();
+            if let Err(e) = self.recently_viewed.save(&self.db) {
+                eprintln!("Failed to save recently viewed: {}", e);
+            }
+        }
+    }
+
+    fn compare_ui(&mut self, ui: &mut egui::Ui) {
+        if self.compare.len() < MAX_COMPARE {
+            ui.label(tr!("compare-empty"));
+            return;
+        }
+        let (left, right) = (&self.compare[0], &self.compare[1]);
+        let shared = |ing: &str, other: &MealDetail| {
+            other
+                .ingredients
+                .iter()
+                .any(|o| ingredient_matches(o, ing) || ingredient_matches(ing, o))
+        };
+        ui.horizontal(|ui| {
+            ui.label(tr!("compare-legend-shared"));
+            ui.colored_label(ui.visuals().warn_fg_color, tr!("compare-legend-unique"));
+        });
+        ScrollArea::vertical().max_height(500.0).show(ui, |ui| {
+            ui.columns(2, |columns| {
+                for (ui, (meal, other)) in columns.iter_mut().zip([(left, right), (right, left)]) {
+                    ui.heading(&meal.title);
+                    ui.label(format!("{} · {}", meal.category, meal.area));
+                    let unique = meal.ingredients.iter().filter(|ing| !shared(ing, other)).count();
+                    ui.strong(tr!(
+                        "compare-ingredients",
+                        count = meal.ingredients.len(),
+                        shared = meal.ingredients.len() - unique
+                    ));
+                    for (i, ing) in meal.ingredients.iter().enumerate() {
+                        let text = match meal.measures.get(i).filter(|m| !m.is_empty()) {
+                            Some(measure) => format!("- {} {}", measure, ing),
+                            None => format!("- {}", ing),
+                        };
+                        if shared(ing, other) {
+                            ui.label(text);
+                        } else {
+                            ui.colored_label(ui.visuals().warn_fg_color, text);
+                        }
+                    }
+                    ui.separator();
+                    ui.strong(tr!("compare-instructions"));
+                    for (i, step) in steps::parse_steps(&meal.instructions).iter().enumerate() {
+                        ui.label(format!("{}. {}", i + 1, step.text));
+                    }
+                }
+            });
+        });
+        if ui.button(tr!("compare-clear")).clicked() {
+            self.compare.clear();
+        }
+    }
+
+    /// Results currently listed, in display order (the tag and instruction filters apply).
+    fn visible_results(&self) -> Vec<&MealDetail> {
+        self.results
+            .iter()
+            .filter(|m| self.tag_filter.as_ref().is_none_or(|t| m.tags.contains(t)))
+            .filter(|m| filters::instructions_mention(m, &self.instruction_filter))
+            .collect()
+    }
+
+    // ↑/↓ move through the results, F toggles the favorite, Esc closes the
+    // details. Ignored while a text field has focus so typing isn't hijacked.
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+        let (up, down, favorite, close) = ctx.input(|i| {
+            (
+                i.key_pressed(egui::Key::ArrowUp),
+                i.key_pressed(egui::Key::ArrowDown),
+                i.key_pressed(egui::Key::F),
+                i.key_pressed(egui::Key::Escape),
+            )
+        });
+        if up || down {
+            let visible = self.visible_results();
+            if !visible.is_empty() {
+                let current = self
+                    .selected_id
+                    .as_ref()
+                    .and_then(|id| visible.iter().position(|m| &m.id == id));
+                let next = match (current, down) {
+                    (None, _) => 0,
+                    (Some(i), true) => (i + 1).min(visible.len() - 1),
+                    (Some(i), false) => i.saturating_sub(1),
+                };
+                self.selected_id = Some(visible[next].id.clone());
+                self.page = next / PAGE_SIZE;
+            }
+        }
+        if favorite {
+            let selected = self.selected_id.as_ref().and_then(|id| {
+                self.results
+                    .iter()
+                    .find(|m| &m.id == id)
+                    .or_else(|| self.favorites.get(id))
+                    .cloned()
+            });
+            if let Some(meal) = selected {
+                self.favorites.toggle(&meal);
+                if let Err(e) = self.favorites.save(&self.db) {
+                    eprintln!("Failed to save favorites: {}", e);
+                }
+            }
+        }
+        if close {
+            self.selected_id = None;
+        }
+    }
+
+    fn timers_ui(&mut self, ui: &mut egui::Ui) {
+        if self.timers.is_empty() {
+            ui.weak(tr!("timers-empty"));
+        }
+        let mut stopped = None;
+        for (i, timer) in self.timers.iter().enumerate() {
+            ui.horizontal(|ui| {
+                if timer.is_finished() {
+                    ui.colored_label(ui.visuals().warn_fg_color, tr!("timer-done"));
+                } else {
+                    ui.monospace(timers::format_duration(timer.remaining()));
+                }
+                ui.label(&timer.label);
+                if ui.small_button("✖").on_hover_text(tr!("timer-stop")).clicked() {
+                    stopped = Some(i);
+                }
+            });
+        }
+        if let Some(i) = stopped {
+            self.timers.remove(i);
+        }
+    }
+
+    /// The step of `meal` being read aloud right now, if any.
+    #[cfg(feature = "speech")]
+    fn step_being_read(&self, meal: &MealDetail) -> Option<usize> {
+        self.read_aloud
+            .as_ref()
+            .filter(|reader| reader.meal_id == meal.id)
+            .map(|reader| reader.current)
+    }
+
+    #[cfg(not(feature = "speech"))]
+    fn step_being_read(&self, _: &MealDetail) -> Option<usize> {
+        None
+    }
+
+    // "Read aloud" for this recipe, or pause/skip/stop while it is the one being read
+    #[cfg(feature = "speech")]
+    fn read_aloud_ui(&mut self, ui: &mut egui::Ui, meal: &MealDetail) {
+        let result = match self.read_aloud.as_mut().filter(|reader| reader.meal_id == meal.id) {
+            Some(reader) => {
+                let step = (reader.current + 1).min(reader.step_count());
+                ui.label(tr!("speech-progress", step = step, count = reader.step_count()));
+                let mut result = Ok(());
+                if ui.small_button("⏮").on_hover_text(tr!("speech-back")).clicked() {
+                    result = reader.skip(false);
+                }
+                if reader.paused {
+                    if ui.small_button("▶").on_hover_text(tr!("speech-resume")).clicked() {
+                        result = reader.resume();
+                    }
+                } else if ui.small_button("⏸").on_hover_text(tr!("speech-pause")).clicked() {
+                    result = reader.pause();
+                }
+                if ui.small_button("⏭").on_hover_text(tr!("speech-skip")).clicked() {
+                    result = reader.skip(true);
+                }
+                if ui.small_button("⏹").on_hover_text(tr!("speech-stop")).clicked() {
+                    result = reader.stop();
+                }
+                result
+            }
+            None => {
+                if !ui.button(tr!("speech-start")).on_hover_text(tr!("speech-start-hint")).clicked() {
+                    if let Some(status) = &self.read_aloud_status {
+                        ui.colored_label(ui.visuals().warn_fg_color, status);
+                    }
+                    return;
+                }
+                // Only one recipe is read at a time
+                if let Some(mut previous) = self.read_aloud.take() {
+                    if let Err(e) = previous.stop() {
+                        eprintln!("Failed to stop reading aloud: {}", e);
+                    }
+                }
+                let ctx = ui.ctx().clone();
+                ReadAloud::start(meal, move || ctx.request_repaint()).map(|reader| self.read_aloud = Some(reader))
+            }
+        };
+        match result {
+            Ok(()) => self.read_aloud_status = None,
+            Err(e) => {
+                self.read_aloud_status = Some(tr!("speech-failed", error = e.to_string()));
+                self.read_aloud = None;
+            }
+        }
+    }
+
+    // Moves reading aloud on to the next step, and lets go of it after the last
+    #[cfg(feature = "speech")]
+    fn poll_read_aloud(&mut self) {
+        let Some(reader) = &mut self.read_aloud else {
+            return;
+        };
+        if let Err(e) = reader.poll() {
+            self.read_aloud_status = Some(tr!("speech-failed", error = e.to_string()));
+            self.read_aloud = None;
+        } else if !reader.is_active() {
+            self.read_aloud = None;
+        }
+    }
+
+    // The whole window while cooking: the current step in very large text,
+    // what it needs, running timers, and Back/Next. Arrow keys and space step
+    // through too, Esc leaves.
+    fn cooking_mode_ui(&mut self, ctx: &egui::Context) {
+        let Some(cooking) = &mut self.cooking else {
+            return;
+        };
+        let (mut next, mut back, mut exit) = ctx.input(|i| {
+            (
+                i.key_pressed(egui::Key::ArrowRight) || i.key_pressed(egui::Key::Space),
+                i.key_pressed(egui::Key::ArrowLeft),
+                i.key_pressed(egui::Key::Escape),
+            )
+        });
+        let big = |text: String| egui::RichText::new(text).size(COOKING_DETAIL_SIZE);
+        let mut started = None;
+        egui::TopBottomPanel::bottom("cooking_controls").show(ctx, |ui| {
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                back |= ui.add_enabled(!cooking.is_first(), egui::Button::new(big(tr!("cooking-back")))).clicked();
+                let label = if cooking.is_last() { tr!("cooking-finish") } else { tr!("cooking-next") };
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.button(big(label)).clicked() {
+                        next = true;
+                    }
+                });
+            });
+            ui.add_space(8.0);
+        });
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.heading(&cooking.meal.title);
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    exit |= ui.button(big(tr!("cooking-exit"))).clicked();
+                    ui.label(big(tr!("cooking-progress", step = cooking.current + 1, count = cooking.steps.len())));
+                });
+            });
+            ui.separator();
+            for timer in &self.timers {
+                let text = if timer.is_finished() {
+                    tr!("timer-done")
+                } else {
+                    timers::format_duration(timer.remaining())
+                };
+                ui.label(big(format!("⏱ {} · {}", text, timer.label)).monospace());
+            }
+            ScrollArea::vertical().show(ui, |ui| {
+                let Some(step) = cooking.step() else {
+                    return;
+                };
+                ui.add_space(16.0);
+                ui.label(egui::RichText::new(&step.text).size(COOKING_STEP_SIZE).strong());
+                ui.add_space(16.0);
+                if let Some(duration) = step.duration {
+                    let label = big(format!("⏱ {}", timers::format_duration(duration)));
+                    if ui.button(label).on_hover_text(tr!("timer-start-hint")).clicked() {
+                        started = Some(StepTimer::start(
+                            tr!("timer-name", title = cooking.meal.title.as_str(), step = cooking.current + 1),
+                            duration,
+                        ));
+                    }
+                }
+                let reminders = cooking.reminders();
+                if !reminders.is_empty() {
+                    ui.add_space(16.0);
+                    ui.label(big(tr!("cooking-reminders")).weak());
+                    for line in reminders {
+                        ui.label(big(format!("• {}", line)));
+                    }
+                }
+            });
+        });
+        self.timers.extend(started);
+        if next {
+            self.done_steps.entry(cooking.meal.id.clone()).or_default().insert(cooking.current);
+            if cooking.is_last() {
+                exit = true;
+            } else {
+                cooking.next();
+            }
+        }
+        if back {
+            cooking.back();
+        }
+        if exit {
+            self.cooking = None;
+            ctx.send_viewport_cmd_to(egui::ViewportId::ROOT, egui::ViewportCommand::Fullscreen(false));
+        }
+    }
+
+    fn log_viewer_ui(&self, ui: &mut egui::Ui, lines: &[String]) {
+        ui.horizontal(|ui| {
+            if ui.button(tr!("log-clear")).clicked() {
+                logging::clear();
+            }
+            ui.weak(tr!("log-file-hint", dir = self.log_dir.display().to_string()));
+        });
+        if lines.is_empty() {
+            ui.weak(tr!("log-empty"));
+        }
+        ScrollArea::vertical().max_height(180.0).stick_to_bottom(true).show(ui, |ui| {
+            for line in lines {
+                ui.label(egui::RichText::new(line).monospace().small());
+            }
+        });
+    }
+
+    // Pops up for every finished timer until it is dismissed
+    fn timer_alerts(&mut self, ctx: &egui::Context) {
+        for (i, timer) in self.timers.iter_mut().enumerate() {
+            if !timer.is_finished() || timer.dismissed {
+                continue;
+            }
+            egui::Window::new(tr!("timer-finished-title"))
+                .id(egui::Id::new(("timer_alert", i)))
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label(&timer.label);
+                    if ui.button(tr!("dismiss")).clicked() {
+                        timer.dismissed = true;
+                    }
+                });
+        }
+        self.timers.retain(|t| !t.dismissed);
+        if self.timers.iter().any(|t| !t.is_finished()) {
+            // Keep the countdowns ticking without user input
+            ctx.request_repaint_after(Duration::from_secs(1));
+        }
+    }
+
+    fn settings_ui(&mut self, ui: &mut egui::Ui) {
+        ui.heading(tr!("settings-language"));
+        egui::ComboBox::from_id_source("language")
+            .selected_text(self.language.native_name())
+            .show_ui(ui, |ui| {
+                for language in Language::ALL {
+                    if ui.selectable_value(&mut self.language, language, language.native_name()).changed() {
+                        i18n::set_language(language);
+                    }
+                }
+            });
+        ui.separator();
+        ui.heading(tr!("settings-appearance"));
+        egui::Grid::new("appearance").show(ui, |ui| {
+            ui.label(tr!("settings-mode"));
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.theme.mode, ThemeMode::Dark, tr!("theme-dark"));
+                ui.radio_value(&mut self.theme.mode, ThemeMode::Light, tr!("theme-light"));
+            });
+            ui.end_row();
+            ui.label(tr!("settings-accent"));
+            egui::color_picker::color_edit_button_srgb(ui, &mut self.theme.accent);
+            ui.end_row();
+            ui.label(tr!("settings-spacing"));
+            ui.add(egui::Slider::new(&mut self.theme.spacing, 0.5..=2.0));
+            ui.end_row();
+        });
+        ui.separator();
+        ui.heading(tr!("settings-accessibility"));
+        egui::Grid::new("accessibility").show(ui, |ui| {
+            ui.label(tr!("settings-text-size"));
+            ui.add(egui::Slider::new(&mut self.theme.text_scale, 0.8..=2.5).suffix("×"));
+            ui.end_row();
+            ui.label(tr!("settings-high-contrast"));
+            ui.checkbox(&mut self.theme.high_contrast, tr!("settings-high-contrast-detail"));
+            ui.end_row();
+        });
+        ui.separator();
+        ui.heading(tr!("settings-units"));
+        ui.horizontal(|ui| {
+            for system in UnitSystem::ALL {
+                ui.radio_value(&mut self.units, system, system.label());
+            }
+        });
+        ui.separator();
+        ui.heading(tr!("settings-region"));
+        ui.horizontal(|ui| {
+            for region in Region::ALL {
+                ui.radio_value(&mut self.region, region, region.label());
+            }
+        });
+        ui.separator();
+        ui.heading(tr!("settings-themealdb"));
+        let key_from_env = std::env::var_os("THEMEALDB_API_KEY").is_some();
+        ui.horizontal(|ui| {
+            ui.label(tr!("settings-api-key"));
+            let field = egui::TextEdit::singleline(&mut self.themealdb_api_key).password(true).desired_width(160.0);
+            ui.add_enabled(!key_from_env, field).on_disabled_hover_text(tr!("settings-api-key-env"));
+        });
+        ui.label(egui::RichText::new(tr!("settings-api-key-hint")).small().weak());
+        ui.horizontal(|ui| {
+            let mut limited = self.daily_request_limit.is_some();
+            if ui.checkbox(&mut limited, tr!("settings-request-limit")).changed() {
+                self.daily_request_limit = limited.then_some(DEFAULT_REQUEST_LIMIT);
+            }
+            if let Some(limit) = &mut self.daily_request_limit {
+                ui.add(egui::DragValue::new(limit).range(1..=100_000));
+            }
+        });
+        ui.label(egui::RichText::new(tr!("settings-request-limit-hint")).small().weak());
+        ui.separator();
+        ui.heading(tr!("settings-backup"));
+        ui.horizontal(|ui| {
+            if ui.button(tr!("backup-export")).on_hover_text(tr!("backup-export-hint")).clicked() {
+                self.export_backup();
+            }
+            if ui.button(tr!("backup-import")).on_hover_text(tr!("backup-import-hint")).clicked() {
+                self.import_backup();
+            }
+        });
+        if let Some(status) = &self.backup_status {
+            ui.label(status);
+        }
+        ui.separator();
+        ui.heading(tr!("settings-background"));
+        ui.checkbox(&mut self.prefetch_favorite_categories, tr!("settings-prefetch"))
+            .on_hover_text(tr!("settings-prefetch-hint"));
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.keep_favorites_offline, tr!("settings-favorites-offline"))
+                .on_hover_text(tr!("settings-favorites-offline-hint"));
+            ui.add_enabled(
+                self.keep_favorites_offline,
+                egui::DragValue::new(&mut self.favorites_refresh_hours).range(0..=24 * 7),
+            );
+            ui.label(tr!("settings-favorites-refresh-hours"));
+        });
+        ui.separator();
+        ui.heading(tr!("settings-prices"));
+        ui.horizontal(|ui| {
+            ui.label(tr!("settings-currency"));
+            ui.add(egui::TextEdit::singleline(&mut self.currency).desired_width(40.0));
+        });
+        let mut remove_price = None;
+        egui::Grid::new("prices").striped(true).show(ui, |ui| {
+            for (i, price) in self.prices.iter_mut().enumerate() {
+                ui.add(egui::TextEdit::singleline(&mut price.ingredient).desired_width(140.0));
+                ui.add(egui::DragValue::new(&mut price.amount).range(0.0..=1000.0).speed(0.05).prefix(&self.currency));
+                egui::ComboBox::from_id_source(("price_unit", i))
+                    .selected_text(price.unit.label())
+                    .show_ui(ui, |ui| {
+                        for unit in PriceUnit::ALL {
+                            ui.selectable_value(&mut price.unit, unit, unit.label());
+                        }
+                    });
+                if ui.small_button("✖").clicked() {
+                    remove_price = Some(i);
+                }
+                ui.end_row();
+            }
+        });
+        if let Some(i) = remove_price {
+            self.prices.remove(i);
+        }
+        if ui.button(tr!("price-add")).clicked() {
+            self.prices.push(Price {
+                ingredient: String::new(),
+                unit: PriceUnit::default(),
+                amount: 1.0,
+            });
+        }
+        ui.separator();
+        ui.heading(tr!("settings-scoring"));
+        let before = (self.scoring.clone(), self.scoring_strategy);
+        egui::Grid::new("scoring_weights").show(ui, |ui| {
+            ui.label(tr!("scoring-strategy"));
+            egui::ComboBox::from_id_source("scoring_strategy")
+                .selected_text(self.scoring_strategy.label())
+                .show_ui(ui, |ui| {
+                    for strategy in ScoringStrategy::ALL {
+                        ui.selectable_value(&mut self.scoring_strategy, strategy, strategy.label());
+                    }
+                });
+            ui.end_row();
+            ui.label(tr!("weight-main"));
+            ui.add(egui::DragValue::new(&mut self.scoring.main_ingredient).range(0..=20));
+            ui.end_row();
+            ui.label(tr!("weight-sub"));
+            ui.add(egui::DragValue::new(&mut self.scoring.sub_ingredient).range(0..=20));
+            ui.end_row();
+            let taste_weights = [
+                (tr!("weight-taste-title"), &mut self.scoring.taste),
+                (tr!("weight-taste-tags"), &mut self.scoring.taste_in_tags),
+                (tr!("weight-taste-category"), &mut self.scoring.taste_in_category),
+                (tr!("weight-taste-area"), &mut self.scoring.taste_in_area),
+                (tr!("weight-taste-instructions"), &mut self.scoring.taste_in_instructions),
+                (tr!("weight-taste-ingredients"), &mut self.scoring.taste_in_ingredients),
+            ];
+            for (label, weight) in taste_weights {
+                ui.label(label);
+                ui.add(egui::DragValue::new(weight).range(0..=20));
+                ui.end_row();
+            }
+            ui.label(tr!("weight-unmatched"))
+                .on_hover_text(tr!("weight-unmatched-hint"));
+            ui.add(egui::DragValue::new(&mut self.scoring.unmatched_ingredient).range(0..=20));
+            ui.end_row();
+            ui.label(tr!("weight-seasonal"));
+            ui.add(egui::DragValue::new(&mut self.scoring.seasonal_ingredient).range(0..=20));
+            ui.end_row();
+            ui.label(tr!("weight-leftover"));
+            ui.add(egui::DragValue::new(&mut self.scoring.leftover_ingredient).range(0..=20));
+            ui.end_row();
+        });
+        let words: Vec<&str> = self.scoring.taste_words.iter().map(|w| w.word.as_str()).collect();
+        ui.weak(tr!("taste-words", words = words.join(", ")))
+            .on_hover_text(tr!("taste-words-hint", file = CONFIG_FILE));
+        ui.horizontal(|ui| {
+            if ui.button(tr!("reset-defaults")).clicked() {
+                self.scoring = ScoringConfig::default();
+                self.scoring_strategy = ScoringStrategy::default();
+                self.theme = ThemeConfig::default();
+            }
+            if ui.button(tr!("save")).clicked() {
+                // Start from the file so env-provided API keys aren't written out
+                let result = AppConfig::load_file(CONFIG_FILE).and_then(|mut config| {
+                    config.scoring = self.scoring.clone();
+                    config.scoring_strategy = self.scoring_strategy;
+                    config.prices = self.prices.clone();
+                    config.currency = self.currency.clone();
+                    config.prefetch_favorite_categories = self.prefetch_favorite_categories;
+                    config.keep_favorites_offline = self.keep_favorites_offline;
+                    config.favorites_refresh_hours = self.favorites_refresh_hours;
+                    config.daily_request_limit = self.daily_request_limit;
+                    if !key_from_env {
+                        config.themealdb_api_key = self.themealdb_api_key.trim().to_string();
+                    }
+                    config.units = self.units;
+                    config.region = self.region;
+                    config.theme = self.theme.clone();
+                    config.language = self.language;
+                    config.save(CONFIG_FILE)
+                });
+                self.settings_status = Some(match result {
+                    Ok(()) => tr!("saved-to", path = CONFIG_FILE),
+                    Err(e) => tr!("save-failed", error = e.to_string()),
+                });
+            }
+        });
+        if let Some(status) = &self.settings_status {
+            ui.label(status);
+        }
+
+        // Re-rank what's already on screen so the effect is visible right away
+        if (self.scoring.clone(), self.scoring_strategy) != before {
+            self.rescore_results();
+        }
+    }
+
+    /// "🌐 12 today · 3 this run", coloured as today's count nears the soft limit.
+    fn request_counter_ui(&self, ui: &mut egui::Ui) {
+        let text = tr!("quota-counter", today = self.requests.today(), session = self.requests.session());
+        let color = match self.requests.level(self.daily_request_limit) {
+            quota::Level::Fine => ui.visuals().weak_text_color(),
+            quota::Level::Near => ui.visuals().warn_fg_color,
+            quota::Level::Over => ui.visuals().error_fg_color,
+        };
+        let hint = match self.daily_request_limit {
+            Some(limit) => tr!("quota-limit-hint", limit = limit),
+            None => tr!("quota-no-limit-hint"),
+        };
+        ui.label(egui::RichText::new(text).small().color(color)).on_hover_text(hint);
+    }
+
+    /// Scores the results again with the current weights and strategy.
+    fn rescore_results(&mut self) {
+        self.last_query.weights = self.scoring.clone();
+        self.last_query.strategy = self.scoring_strategy;
+        for meal in &mut self.results {
+            meal.score = self.last_query.score(meal);
+        }
+        self.sort_results();
+    }
+
+    // How the results differ from the search before, with the recipes that dropped out
+    fn result_diff_ui(&mut self, ui: &mut egui::Ui) {
+        let Some(diff) = &self.result_diff else {
+            return;
+        };
+        let mut new = 0;
+        let mut rescored = 0;
+        for meal in &self.results {
+            match diff.change(meal) {
+                Change::New => new += 1,
+                Change::Score(_) => rescored += 1,
+                Change::Same => {}
+            }
+        }
+        let dropped = diff.dropped(&self.results);
+        ui.weak(tr!("diff-summary", new = new, dropped = dropped.len(), rescored = rescored));
+        if dropped.is_empty() {
+            return;
+        }
+        egui::CollapsingHeader::new(tr!("diff-dropped", count = dropped.len()))
+            .id_source("dropped_results")
+            .show(ui, |ui| {
+                for meal in dropped {
+                    let title = egui::RichText::new(tr!("diff-dropped-title", title = meal.title.as_str(), score = meal.score))
+                        .strikethrough();
+                    let is_selected = self.selected_id.as_ref() == Some(&meal.id);
+                    if ui.selectable_label(is_selected, title).on_hover_text(tr!("diff-dropped-hint")).clicked() {
+                        self.selected_id = Some(meal.id.clone());
+                    }
+                }
+            });
+    }
+
+    fn export_backup(&mut self) {
+        let Some(path) = backup::save_dialog() else {
+            return;
+        };
+        let result = Backup::collect(&self.db, &self.profiles)
+            .map_err(|e| e.to_string())
+            .and_then(|backup| backup.write(&path).map(|()| backup));
+        self.backup_status = Some(match result {
+            Ok(backup) => backup.summary(),
+            Err(e) => tr!("save-failed", error = e),
+        });
+    }
+
+    fn import_backup(&mut self) {
+        let Some(path) = backup::open_dialog() else {
+            return;
+        };
+        let result = Backup::read(&path)
+            .and_then(|backup| backup.restore(&self.db, &mut self.profiles).map_err(|e| e.to_string()));
+        self.backup_status = Some(match result {
+            Ok(report) => report.summary(),
+            Err(e) => tr!("backup-import-failed", error = e),
+        });
+        // Show what the file brought in
+        self.favorites = Favorites::load(&self.db, &self.profile).unwrap_or_default();
+        self.ratings = Ratings::load(&self.db).unwrap_or_default();
+        self.notes = Notes::load(&self.db).unwrap_or_default();
+    }
+
+    fn queue_ui(&mut self, ui: &mut egui::Ui) {
+        if self.queue.is_empty() {
+            ui.label(tr!("queue-empty"));
+            return;
+        }
+        ui.horizontal(|ui| {
+            if self.queue.is_paused() {
+                if ui.button(tr!("queue-resume")).clicked() {
+                    self.queue.resume();
+                }
+            } else if ui.button(tr!("queue-pause")).clicked() {
+                self.queue.pause();
+            }
+            if ui.button(tr!("queue-clear-finished")).clicked() {
+                self.queue.clear_finished();
+            }
+        });
+        let mut remove = None;
+        let mut show = None;
+        ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+            for (i, queued) in self.queue.iter().enumerate() {
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.strong(format!("{}. {}", i + 1, queued.query.describe()));
+                    ui.weak(queued.provider_name());
+                    if ui.small_button("✖").on_hover_text(tr!("queue-remove")).clicked() {
+                        remove = Some(i);
+                    }
+                });
+                let progress = &queued.progress;
+                if queued.is_waiting() {
+                    ui.weak(tr!("queue-waiting"));
+                } else if progress.cancelled {
+                    ui.weak(tr!("queue-paused"));
+                } else if !progress.done {
+                    ui.add(egui::ProgressBar::new(progress.fraction()).show_percentage());
+                } else {
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("queue-found", count = queued.results.len()));
+                        if !progress.errors.is_empty() {
+                            ui.colored_label(ui.visuals().error_fg_color, tr!("queue-errors", count = progress.errors.len()))
+                                .on_hover_text(progress.errors.join("\n"));
+                        }
+                        if !queued.results.is_empty() && ui.button(tr!("queue-show")).clicked() {
+                            show = Some(i);
+                        }
+                    });
+                    for meal in queued.results.iter().take(QUEUE_PREVIEW) {
+                        ui.label(tr!("queue-result", score = meal.score, title = meal.title.as_str()));
+                    }
+                }
+            }
+        });
+        if let Some(queued) = show.and_then(|i| self.queue.get(i)) {
+            let (query, results) = (queued.query.clone(), queued.results.clone());
+            self.start_local_search(query);
+            self.results = results;
+            self.sort_results();
+        }
+        if let Some(i) = remove {
+            self.queue.remove(i);
+        }
+    }
+
+    fn shopping_list_ui(&mut self, ui: &mut egui::Ui) {
+        if self.shopping_recipes.is_empty() {
+            ui.label(tr!("shopping-empty"));
+            return;
+        }
+        ui.label(tr!("shopping-recipes"));
+        let mut remove = None;
+        for meal in &self.shopping_recipes {
+            ui.horizontal(|ui| {
+                if ui.small_button("✖").clicked() {
+                    remove = Some(meal.id.clone());
+                }
+                ui.label(&meal.title);
+            });
+        }
+        if let Some(id) = remove {
+            self.shopping_recipes.retain(|m| m.id != id);
+        }
+
+        ui.separator();
+        let recipes: Vec<MealDetail> = self
+            .shopping_recipes
+            .iter()
+            .map(|m| measure::convert_meal(m, self.units))
+            .collect();
+        let items = shopping::build(&recipes);
+        let sections = shopping::by_aisle(&items, &self.prices);
+        ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+            for section in &sections {
+                let heading = ui.strong(shopping::section_heading(section, &self.currency));
+                if section.unpriced > 0 {
+                    heading.on_hover_text(tr!("aisle-unpriced", count = section.unpriced));
+                }
+                for item in &section.items {
+                    ingredient_row(ui, &item.ingredient, format!("• {}", shopping::item_line(item)), !self.offline)
+                        .on_hover_text(item.recipes.join(", "));
+                }
+            }
+        });
+        let total: f64 = sections.iter().map(|s| s.subtotal).sum();
+        ui.label(tr!("shopping-total", total = cost::format_cost(total, &self.currency)));
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            if ui.button(tr!("shopping-export")).clicked() {
+                self.shopping_status = Some(match shopping::export(SHOPPING_LIST_FILE, &items, &self.prices, &self.currency) {
+                    Ok(()) => tr!("saved-to", path = SHOPPING_LIST_FILE),
+                    Err(e) => tr!("export-failed", error = e.to_string()),
+                });
+            }
+            if ui.button(tr!("clear")).clicked() {
+                self.shopping_recipes.clear();
+                self.shopping_status = None;
+            }
+        });
+        if let Some(status) = &self.shopping_status {
+            ui.label(status);
+        }
+    }
+}
+
+// Editor for an ingredient list kept in the database; saves it under `name` on every change
+fn ingredient_list_ui(ui: &mut egui::Ui, list: &mut IngredientList, input: &mut String, db: &Database, name: &str) {
+    let mut changed = false;
+    ui.horizontal(|ui| {
+        let response = ui.text_edit_singleline(input);
+        let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+        if ui.button(tr!("add")).clicked() || submitted {
+            changed |= list.add(input);
+            input.clear();
+        }
+    });
+    let mut remove = None;
+    for item in list.items() {
+        ui.horizontal(|ui| {
+            if ui.small_button("✖").clicked() {
+                remove = Some(item.clone());
+            }
+            ui.label(item);
+        });
+    }
+    if let Some(item) = remove {
+        list.remove(&item);
+        changed = true;
+    }
+    if changed {
+        if let Err(e) = list.save(db, name) {
+            eprintln!("Failed to save {}: {}", name, e);
+        }
+    }
+}
+
+/// Recipes side by side in the comparison window.
+const MAX_COMPARE: usize = 2;
+
+fn compare_checkbox(ui: &mut egui::Ui, selected: &mut Vec<MealDetail>, meal: &MealDetail) {
+    let mut checked = selected.iter().any(|m| m.id == meal.id);
+    if ui
+        .checkbox(&mut checked, "⚖")
+        .on_hover_text(tr!("compare-pick-hint"))
+        .changed()
+    {
+        if checked {
+            // Picking a third recipe replaces the oldest pick
+            if selected.len() == MAX_COMPARE {
+                selected.remove(0);
+            }
+            selected.push(meal.clone());
+        } else {
+            selected.retain(|m| m.id != meal.id);
+        }
+    }
+}
+
+fn shopping_checkbox(ui: &mut egui::Ui, selected: &mut Vec<MealDetail>, meal: &MealDetail) {
+    let mut checked = selected.iter().any(|m| m.id == meal.id);
+    if ui
+        .checkbox(&mut checked, "🛒")
+        .on_hover_text(tr!("shopping-include-hint"))
+        .changed()
+    {
+        if checked {
+            selected.push(meal.clone());
+        } else {
+            selected.retain(|m| m.id != meal.id);
+        }
+    }
+}
+
+/// The score, clickable to open its breakdown; returns whether it was clicked.
+fn score_button(ui: &mut egui::Ui, score: i32) -> bool {
+    let text = egui::RichText::new(tr!("score", score = score)).weak();
+    ui.add(egui::Label::new(text).sense(egui::Sense::click()))
+        .on_hover_cursor(egui::CursorIcon::PointingHand)
+        .on_hover_text(tr!("score-hint"))
+        .clicked()
+}
+
+/// "⏱ 45 min" from the instructions; nothing if there are none.
+fn time_label(ui: &mut egui::Ui, meal: &MealDetail) {
+    if let Some(minutes) = steps::estimated_minutes(&meal.instructions) {
+        ui.weak(tr!("time-estimate", minutes = minutes)).on_hover_text(tr!("time-estimate-hint"));
+    }
+}
+
+/// Easy, Medium or Hard, with what the rating is based on in the tooltip.
+fn difficulty_badge(ui: &mut egui::Ui, meal: &MealDetail) {
+    let assessment = difficulty::assess(meal);
+    let color = match assessment.difficulty {
+        Difficulty::Easy => ui.visuals().weak_text_color(),
+        Difficulty::Medium => ui.visuals().text_color(),
+        Difficulty::Hard => ui.visuals().warn_fg_color,
+    };
+    ui.label(egui::RichText::new(assessment.difficulty.label()).small().color(color))
+        .on_hover_text(assessment.summary());
+}
+
+fn reason_label(reason: &Reason) -> String {
+    match reason {
+        Reason::MainIngredient { ingredient, wanted } => {
+            tr!("breakdown-main", ingredient = ingredient.as_str(), wanted = wanted.as_str())
+        }
+        Reason::SubIngredient { ingredient, wanted } => {
+            tr!("breakdown-sub", ingredient = ingredient.as_str(), wanted = wanted.as_str())
+        }
+        Reason::Unmatched(ingredient) => tr!("breakdown-unmatched", ingredient = ingredient.as_str()),
+        Reason::Taste(TastePlace::Title) => tr!("breakdown-taste-title"),
+        Reason::Taste(TastePlace::Tags) => tr!("breakdown-taste-tags"),
+        Reason::Taste(TastePlace::Category) => tr!("breakdown-taste-category"),
+        Reason::Taste(TastePlace::Area) => tr!("breakdown-taste-area"),
+        Reason::Taste(TastePlace::Instructions) => tr!("breakdown-taste-instructions"),
+        Reason::Taste(TastePlace::Ingredients) => tr!("breakdown-taste-ingredients"),
+        Reason::Have(ingredient) => tr!("breakdown-have", ingredient = ingredient.as_str()),
+        Reason::ToBuy(ingredient) => tr!("breakdown-to-buy", ingredient = ingredient.as_str()),
+        Reason::Other(text) => text.clone(),
+    }
+}
+
+fn points_label(ui: &mut egui::Ui, points: i32) {
+    let color = if points > 0 { ui.visuals().strong_text_color() } else { ui.visuals().warn_fg_color };
+    ui.colored_label(color, format!("{:+}", points));
+}
+
+/// Every line of a result's score, gains first, and the total. Anything
+/// the lines don't cover, e.g. from weights changed since, shows as unexplained.
+fn score_breakdown_ui(ui: &mut egui::Ui, query: &SearchQuery, meal: &MealDetail) {
+    let mut lines = query.explain(meal);
+    lines.sort_by_key(|line| std::cmp::Reverse(line.points));
+    if lines.is_empty() {
+        ui.label(tr!("breakdown-empty"));
+    }
+    egui::Grid::new("score_breakdown").striped(true).show(ui, |ui| {
+        for line in &lines {
+            ui.label(reason_label(&line.reason));
+            points_label(ui, line.points);
+            ui.end_row();
+        }
+        let rest = meal.score - lines.iter().map(|line| line.points).sum::<i32>();
+        if rest != 0 {
+            ui.weak(tr!("breakdown-unexplained"));
+            points_label(ui, rest);
+            ui.end_row();
+        }
+        ui.strong(tr!("breakdown-total"));
+        ui.strong(meal.score.to_string());
+        ui.end_row();
+    });
+}
+
+// Marks a result as new since the last search, or by how much its score moved
+fn change_badge(ui: &mut egui::Ui, change: Change) {
+    let (text, color) = match change {
+        Change::New => (tr!("diff-new"), ui.visuals().hyperlink_color),
+        Change::Score(delta) if delta > 0 => (format!("▲ +{}", delta), ui.visuals().strong_text_color()),
+        Change::Score(delta) => (format!("▼ {}", delta), ui.visuals().warn_fg_color),
+        Change::Same => return,
+    };
+    ui.label(egui::RichText::new(text).small().color(color))
+        .on_hover_text(tr!("diff-badge-hint"));
+}
+
+// "cached 3 days ago", highlighted once the entry is past the cache TTL
+fn cache_age_label(ui: &mut egui::Ui, age: Duration) {
+    let days = age.as_secs() / (24 * 60 * 60);
+    let text = match days {
+        0 => tr!("cached-today"),
+        1 => tr!("cached-yesterday"),
+        n => tr!("cached-days", days = n),
+    };
+    if age > DEFAULT_TTL {
+        ui.colored_label(ui.visuals().warn_fg_color, format!("⏳ {}", text))
+            .on_hover_text(tr!("cache-stale-hint"));
+    } else {
+        ui.weak(text);
+    }
+}
+
+fn join_or_dash(items: &[String]) -> String {
+    if items.is_empty() {
+        "-".to_string()
+    } else {
+        items.join(", ")
+    }
+}
+
+// One row per failed or slow attempt: what came back, how long it took, and where it went
+fn request_diagnostics(ui: &mut egui::Ui, requests: &[RequestRecord]) {
+    egui::Grid::new("request_diagnostics").striped(true).show(ui, |ui| {
+        ui.strong(tr!("diagnostics-attempt"));
+        ui.strong(tr!("diagnostics-result"));
+        ui.strong(tr!("diagnostics-time"));
+        ui.strong(tr!("diagnostics-url"));
+        ui.end_row();
+        for request in requests {
+            ui.label(request.attempt.to_string());
+            let result = match (&request.error, request.status) {
+                (Some(error), _) => error.clone(),
+                (None, Some(status)) => tr!("diagnostics-slow", status = status.as_u16()),
+                (None, None) => tr!("diagnostics-slow", status = "-"),
+            };
+            if request.error.is_some() {
+                ui.colored_label(ui.visuals().error_fg_color, result);
+            } else {
+                ui.colored_label(ui.visuals().warn_fg_color, result);
+            }
+            ui.label(format!("{} ms", request.elapsed.as_millis()));
+            ui.label(egui::RichText::new(&request.url).monospace().small());
+            ui.end_row();
+        }
+    });
+}
+
+/// The active profile's allergens, if any.
+fn active_allergens<'a>(profiles: &'a Profiles, profile: &str) -> &'a [Allergen] {
+    profiles.get(profile).map(|p| p.allergens.as_slice()).unwrap_or_default()
+}
+
+// One small badge per allergen found; hovering lists the ingredients that triggered it
+fn allergen_badges(ui: &mut egui::Ui, meal: &MealDetail, enabled: &[Allergen]) {
+    for (allergen, ingredients) in allergens::find(meal, enabled) {
+        let badge = egui::RichText::new(format!("⚠ {}", allergen.name()))
+            .small()
+            .color(ui.visuals().error_fg_color);
+        ui.label(badge).on_hover_text(tr!("allergen-hint", items = ingredients.join(", ")));
+    }
+}
+
+fn cost_panel(ui: &mut egui::Ui, meal: &MealDetail, prices: &[Price], currency: &str) {
+    let estimate = cost::estimate(meal, prices);
+    egui::CollapsingHeader::new(tr!("cost-title"))
+        .id_source(("cost", &meal.id))
+        .show(ui, |ui| {
+            ui.label(tr!(
+                "cost-estimate",
+                total = cost::format_cost(estimate.total, currency),
+                per_serving = cost::format_cost(estimate.per_serving(), currency),
+                servings = estimate.servings
+            ));
+            if !estimate.unpriced.is_empty() {
+                ui.weak(tr!("cost-not-counted", items = estimate.unpriced.join(", ")));
+            }
+        });
+}
+
+/// Bold for ingredients matching the main or sub inputs, plain for what is
+/// at home, greyed out for the rest. Plain throughout if nothing was entered.
+fn highlighted_ingredient(
+    ui: &egui::Ui,
+    ingredient: &str,
+    text: String,
+    wanted: &[String],
+    on_hand: &[String],
+) -> egui::RichText {
+    let text = egui::RichText::new(text);
+    if wanted.is_empty() && on_hand.is_empty() {
+        text
+    } else if wanted.iter().any(|w| ingredient_matches(ingredient, w)) {
+        text.strong().color(ui.visuals().strong_text_color())
+    } else if on_hand.iter().any(|h| ingredient_matches(ingredient, h)) {
+        text
+    } else {
+        text.weak()
+    }
+}
+
+// One ingredient line, with TheMealDB's thumbnail in front when images may be loaded
+fn ingredient_row(
+    ui: &mut egui::Ui,
+    ingredient: &str,
+    text: impl Into<egui::WidgetText>,
+    show_image: bool,
+) -> egui::Response {
+    ui.horizontal(|ui| {
+        if show_image {
+            ui.add(
+                egui::Image::new(themealdb::ingredient_image_url(ingredient))
+                    .fit_to_exact_size(egui::vec2(INGREDIENT_IMAGE_SIZE, INGREDIENT_IMAGE_SIZE)),
+            );
+        }
+        ui.label(text)
+    })
+    .inner
+}
+
+fn nutrition_panel(ui: &mut egui::Ui, meal: &MealDetail) {
+    let estimate = nutrition::estimate(meal);
+    let per_serving = estimate.per_serving();
+    egui::CollapsingHeader::new(tr!("nutrition-title"))
+        .id_source(("nutrition", &meal.id))
+        .show(ui, |ui| {
+            egui::Grid::new(("nutrition_grid", &meal.id))
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.strong("");
+                    ui.strong(tr!("nutrition-total"));
+                    ui.strong(tr!("nutrition-per-serving", servings = estimate.servings));
+                    ui.end_row();
+                    let rows = [
+                        (tr!("nutrition-calories"), estimate.total.calories, per_serving.calories),
+                        (tr!("nutrition-protein"), estimate.total.protein, per_serving.protein),
+                        (tr!("nutrition-fat"), estimate.total.fat, per_serving.fat),
+                        (tr!("nutrition-carbs"), estimate.total.carbs, per_serving.carbs),
+                        (tr!("nutrition-sugar"), estimate.total.sugar, per_serving.sugar),
+                    ];
+                    for (name, total, serving) in rows {
+                        ui.label(name);
+                        ui.label(format!("{:.0}", total));
+                        ui.label(format!("{:.0}", serving));
+                        ui.end_row();
+                    }
+                });
+            if !estimate.unmatched.is_empty() {
+                ui.weak(tr!("nutrition-not-counted", items = estimate.unmatched.join(", ")));
+            }
+        });
+}
+
+impl eframe::App for RecipeApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.poll_search_events();
+        self.queue.poll(&self.runtime, ctx);
+        #[cfg(feature = "speech")]
+        self.poll_read_aloud();
+        self.requests.roll_over();
+        if self.applied_theme.as_ref() != Some(&self.theme) {
+            self.theme.apply(ctx);
+            self.applied_theme = Some(self.theme.clone());
+        }
+        if self.cooking.is_some() {
+            self.cooking_mode_ui(ctx);
+            self.timer_alerts(ctx);
+            return;
+        }
+        self.handle_shortcuts(ctx);
+
+        let allergies_list = profiles::allergies_list(&self.profile);
+        egui::SidePanel::right("favorites_panel").show(ctx, |ui| {
+            egui::CollapsingHeader::new(tr!("profile-title", name = self.profile.as_str()))
+                .id_source("profile")
+                .show(ui, |ui| self.profile_ui(ui));
+            ui.heading(tr!("favorites-title"));
+            if self.favorites.is_empty() {
+                ui.label(tr!("favorites-empty"));
+            }
+            ScrollArea::vertical().max_height(250.0).show(ui, |ui| {
+                for meal in self.favorites.iter() {
+                    if ui.selectable_label(self.selected_id.as_ref() == Some(&meal.id), &meal.title).clicked() {
+                        self.selected_id = Some(meal.id.clone());
+                    }
+                }
+            });
+
+            ui.separator();
+            egui::CollapsingHeader::new(tr!("pantry-title"))
+                .id_source("pantry")
+                .default_open(true)
+                .show(ui, |ui| {
+                    ingredient_list_ui(ui, &mut self.pantry, &mut self.pantry_input, &self.db, PANTRY_LIST)
+                });
+            egui::CollapsingHeader::new(tr!("leftovers-title"))
+                .id_source("leftovers")
+                .show(ui, |ui| self.leftovers_ui(ui));
+            egui::CollapsingHeader::new(tr!("plan-title"))
+                .id_source("meal_plan")
+                .show(ui, |ui| self.meal_plan_ui(ui));
+            egui::CollapsingHeader::new(tr!("allergies-title"))
+                .id_source("allergies")
+                .show(ui, |ui| {
+                    ingredient_list_ui(ui, &mut self.allergies, &mut self.allergy_input, &self.db, &allergies_list)
+                });
+            egui::CollapsingHeader::new(tr!("recent-title"))
+                .id_source("recently_viewed_header")
+                .show(ui, |ui| self.recently_viewed_ui(ui));
+            egui::CollapsingHeader::new(tr!("timers-title", count = self.timers.len()))
+                .id_source("timers")
+                .default_open(true)
+                .show(ui, |ui| self.timers_ui(ui));
+        });
+
+        let mut show_shopping_list = self.show_shopping_list;
+        egui::Window::new(tr!("window-shopping"))
+            .id(egui::Id::new("shopping_window"))
+            .open(&mut show_shopping_list)
+            .show(ctx, |ui| self.shopping_list_ui(ui));
+        self.show_shopping_list = show_shopping_list;
+
+        let mut show_compare = self.show_compare;
+        egui::Window::new(tr!("window-compare"))
+            .id(egui::Id::new("compare_window"))
+            .open(&mut show_compare)
+            .default_width(700.0)
+            .show(ctx, |ui| self.compare_ui(ui));
+        self.show_compare = show_compare;
+
+        let mut show_my_recipes = self.show_my_recipes;
+        egui::Window::new(tr!("window-my-recipes"))
+            .id(egui::Id::new("my_recipes_window"))
+            .open(&mut show_my_recipes)
+            .show(ctx, |ui| self.my_recipes_ui(ui));
+        self.show_my_recipes = show_my_recipes;
+
+        let mut show_queue = self.show_queue;
+        egui::Window::new(tr!("window-queue"))
+            .id(egui::Id::new("queue_window"))
+            .open(&mut show_queue)
+            .show(ctx, |ui| self.queue_ui(ui));
+        self.show_queue = show_queue;
+
+        let mut show_settings = self.show_settings;
+        egui::Window::new(tr!("window-settings"))
+            .id(egui::Id::new("settings_window"))
+            .open(&mut show_settings)
+            .show(ctx, |ui| self.settings_ui(ui));
+        self.show_settings = show_settings;
+
+        if let Some(meal) = &self.score_breakdown {
+            let mut open = true;
+            egui::Window::new(tr!("window-score-breakdown", title = meal.title.as_str()))
+                .id(egui::Id::new("score_breakdown_window"))
+                .open(&mut open)
+                .show(ctx, |ui| score_breakdown_ui(ui, &self.last_query, meal));
+            if !open {
+                self.score_breakdown = None;
+            }
+        }
+
+        egui::TopBottomPanel::bottom("log_panel").show(ctx, |ui| {
+            let lines = logging::recent_lines();
+            egui::CollapsingHeader::new(tr!("log-title", count = lines.len()))
+                .id_source("log_viewer")
+                .show(ui, |ui| self.log_viewer_ui(ui, &lines));
+        });
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.heading(tr!("app-heading"));
+            self.meal_of_the_day_ui(ui);
+            self.seasonal_strip(ui);
+            ui.horizontal(|ui| {
+                ui.label(tr!("source-label"));
+                ui.add_enabled_ui(!self.offline, |ui| {
+                    egui::ComboBox::from_id_source("provider_select")
+                        .selected_text(self.providers[self.provider_index].name())
+                        .show_ui(ui, |ui| {
+                            for (i, provider) in self.providers.iter().enumerate() {
+                                if ui.selectable_value(&mut self.provider_index, i, provider.name()).changed() {
+                                    self.category = None;
+                                    self.area = None;
+                                }
+                            }
+                        });
+                });
+                if ui.checkbox(&mut self.offline, tr!("offline-mode")).changed() && self.offline {
+                    self.cancel_search();
+                }
+                self.request_counter_ui(ui);
+            });
+            if self.offline {
+                ui.colored_label(
+                    ui.visuals().warn_fg_color,
+                    tr!("offline-warning"),
+                );
+            }
+            if let (Some(limit), quota::Level::Over) = (self.daily_request_limit, self.requests.level(self.daily_request_limit)) {
+                ui.colored_label(ui.visuals().error_fg_color, tr!("quota-over", limit = limit));
+            }
+
+            let lists_handle = Arc::clone(&self.filter_lists[self.provider_index]);
+            let no_lists = FilterLists::default();
+            let lists = lists_handle.get().unwrap_or(&no_lists);
+            ui.horizontal(|ui| {
+                filter_combo(ui, &tr!("field-category"), &lists.categories, &mut self.category);
+                filter_combo(ui, &tr!("field-area"), &lists.areas, &mut self.area);
+            });
+
+            ui.horizontal(|ui| {
+                ui.label(tr!("name-label"));
+                let response = ui.add(egui::TextEdit::singleline(&mut self.name_query).hint_text(tr!("name-hint")));
+                let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                if ui.button(tr!("name-search")).clicked() || submitted {
+                    self.fetch_by_name(ctx);
+                }
+            });
+            ui.horizontal_wrapped(|ui| {
+                ui.label(tr!("browse-letters"));
+                for letter in 'A'..='Z' {
+                    let browsing = self.last_query.first_letter == Some(letter);
+                    if ui.selectable_label(browsing, letter.to_string()).clicked() {
+                        self.browse_letter(letter, ctx);
+                    }
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.add_enabled_ui(!self.pantry.is_empty(), |ui| {
+                    ui.checkbox(&mut self.cook_from_pantry, tr!("cook-from-pantry"));
+                });
+                ui.checkbox(&mut self.seasonal_boost, tr!("seasonal-boost"))
+                    .on_hover_text(tr!("seasonal-boost-hint"));
+                if !self.leftovers.is_empty() {
+                    ui.checkbox(&mut self.leftover_boost, tr!("leftover-boost"))
+                        .on_hover_text(tr!("leftover-boost-hint"));
+                    if ui.button(tr!("leftovers-use-up")).on_hover_text(tr!("leftovers-use-up-hint")).clicked() {
+                        self.use_up_leftovers(ctx);
+                    }
+                }
+            });
+            if self.pantry.is_empty() {
+                self.cook_from_pantry = false;
+            }
+
+            // Enter in any of the search fields runs the search
+            let mut submitted = false;
+            ui.add_enabled_ui(!self.cook_from_pantry, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr!("taste-label"));
+                    let response = ui.text_edit_singleline(&mut self.taste);
+                    submitted |= response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr!("main-label"));
+                    submitted |= ingredient_input(ui, "main", &mut self.main_ingredients, &lists.ingredients);
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr!("sub-label"));
+                    submitted |= ingredient_input(ui, "sub", &mut self.sub_ingredients, &lists.ingredients);
+                });
+            });
+            ui.horizontal(|ui| {
+                ui.label(tr!("exclude-label"));
+                submitted |= ingredient_input(ui, "exclude", &mut self.exclude_ingredients, &lists.ingredients);
+                ui.checkbox(&mut self.hide_excluded, tr!("hide-excluded"))
+                    .on_hover_text(tr!("hide-excluded-hint"));
+            });
+            ui.horizontal(|ui| {
+                ui.label(tr!("diet-label"));
+                let mut vegetarian = self.diet.is_some();
+                let mut vegan = self.diet == Some(Diet::Vegan);
+                // Vegan implies vegetarian, so unticking vegan leaves vegetarian on
+                if ui.checkbox(&mut vegetarian, tr!("diet-vegetarian")).changed() {
+                    self.diet = vegetarian.then_some(Diet::Vegetarian);
+                }
+                if ui.checkbox(&mut vegan, tr!("diet-vegan")).changed() {
+                    self.diet = Some(if vegan { Diet::Vegan } else { Diet::Vegetarian });
+                }
+            })
+            .response
+            .on_hover_text(tr!("diet-hint"));
+            ui.horizontal(|ui| {
+                let mut limited = self.max_cost.is_some();
+                if ui.checkbox(&mut limited, tr!("budget-label")).changed() {
+                    self.max_cost = limited.then_some(DEFAULT_BUDGET);
+                }
+                if let Some(max) = &mut self.max_cost {
+                    ui.add(egui::DragValue::new(max).range(0.0..=1000.0).speed(0.5).prefix(&self.currency));
+                }
+            })
+            .response
+            .on_hover_text(tr!("budget-hint"));
+            ui.horizontal(|ui| {
+                let mut limited = self.max_calories.is_some();
+                if ui.checkbox(&mut limited, tr!("calorie-budget-label")).changed() {
+                    self.max_calories = limited.then_some(DEFAULT_CALORIE_BUDGET);
+                }
+                if let Some(max) = &mut self.max_calories {
+                    ui.add(egui::Slider::new(max, 100.0..=2000.0).step_by(50.0).suffix(" kcal"));
+                }
+            })
+            .response
+            .on_hover_text(tr!("calorie-budget-hint"));
+            ui.horizontal(|ui| {
+                let mut limited = self.max_ingredients.is_some();
+                if ui.checkbox(&mut limited, tr!("max-ingredients-label")).changed() {
+                    self.max_ingredients = limited.then_some(DEFAULT_MAX_INGREDIENTS);
+                }
+                if let Some(max) = &mut self.max_ingredients {
+                    ui.add(egui::Slider::new(max, 3..=20));
+                }
+            })
+            .response
+            .on_hover_text(tr!("max-ingredients-hint"));
+            ui.horizontal(|ui| {
+                ui.label(tr!("max-time-label"));
+                ui.selectable_value(&mut self.max_minutes, None, tr!("max-time-any"));
+                for minutes in MAX_TIME_CHOICES {
+                    ui.selectable_value(&mut self.max_minutes, Some(minutes), tr!("max-time-minutes", minutes = minutes));
+                }
+            })
+            .response
+            .on_hover_text(tr!("max-time-hint"));
+            ui.horizontal(|ui| {
+                ui.label(tr!("max-difficulty-label"));
+                ui.selectable_value(&mut self.max_difficulty, None, tr!("max-difficulty-any"));
+                ui.selectable_value(&mut self.max_difficulty, Some(Difficulty::Easy), Difficulty::Easy.label());
+                ui.selectable_value(&mut self.max_difficulty, Some(Difficulty::Medium), tr!("max-difficulty-medium"));
+            })
+            .response
+            .on_hover_text(tr!("max-difficulty-hint"));
+
+            ui.horizontal(|ui| {
+                if ui.button(tr!("fetch-recipes")).on_hover_text("Enter").clicked() || submitted {
+                    self.fetch_recipes(ctx);
+                }
+                if ui
+                    .add_enabled(!self.offline, egui::Button::new(tr!("queue-add")))
+                    .on_hover_text(tr!("queue-add-hint"))
+                    .clicked()
+                {
+                    self.queue_search();
+                }
+                if ui.button(tr!("random-recipe")).clicked() {
+                    self.fetch_random(ctx);
+                }
+                // Only TheMealDB has the latest meals, and only for paid keys
+                if self.themealdb_premium
+                    && self.provider_index == 0
+                    && ui
+                        .add_enabled(!self.offline, egui::Button::new(tr!("latest-recipes")))
+                        .on_hover_text(tr!("latest-recipes-hint"))
+                        .clicked()
+                {
+                    self.fetch_latest(ctx);
+                }
+                let shopping_label = tr!("shopping-toggle", count = self.shopping_recipes.len());
+                ui.toggle_value(&mut self.show_shopping_list, shopping_label);
+                ui.menu_button(tr!("history-menu"), |ui| self.history_menu(ui, ctx));
+                ui.menu_button(tr!("presets-menu"), |ui| self.presets_menu(ui, ctx));
+                let compare_label = tr!("compare-toggle", count = self.compare.len(), max = MAX_COMPARE);
+                ui.toggle_value(&mut self.show_compare, compare_label);
+                ui.toggle_value(&mut self.show_my_recipes, tr!("my-recipes-toggle"));
+                ui.toggle_value(&mut self.show_queue, tr!("queue-toggle", count = self.queue.pending()));
+                ui.toggle_value(&mut self.show_settings, tr!("settings-toggle"));
+                if self.is_searching() {
+                    ui.spinner();
+                    if ui.button(tr!("cancel")).clicked() {
+                        self.cancel_search();
+                    }
+                }
+            });
+            if self.progress.cancelled {
+                ui.weak(tr!("search-cancelled"));
+            }
+            ui.horizontal(|ui| {
+                ui.label(tr!("offline-search-label"));
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut self.offline_query)
+                        .hint_text(tr!("offline-search-hint")),
+                );
+                let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                if ui.button(tr!("offline-search-button")).clicked() || submitted {
+                    self.offline_search();
+                }
+                if let Some(status) = &self.offline_status {
+                    ui.weak(status);
+                }
+            });
+            if self.is_searching() {
+                let text = match self.progress.total {
+                    Some(total) => tr!("progress-loaded", done = self.progress.processed, total = total),
+                    None => tr!("progress-candidates"),
+                };
+                ui.add(egui::ProgressBar::new(self.progress.fraction()).text(text));
+            }
+            if self.progress.done && !self.progress.cancelled && self.results.is_empty() {
+                // Tell "nothing matched" apart from "couldn't reach the service"
+                if self.progress.errors.is_empty() {
+                    ui.label(tr!("no-results"));
+                } else {
+                    ui.colored_label(ui.visuals().error_fg_color, tr!("search-failed-see-below"));
+                }
+            }
+            if !self.progress.errors.is_empty() || !self.progress.requests.is_empty() {
+                let header = tr!(
+                    "details-header",
+                    failed = self.progress.errors.len(),
+                    slow = self.progress.slow_requests()
+                );
+                egui::CollapsingHeader::new(header)
+                    .id_source("error_report")
+                    .show(ui, |ui| {
+                        ScrollArea::vertical().max_height(160.0).show(ui, |ui| {
+                            for error in &self.progress.errors {
+                                ui.label(error);
+                            }
+                            if !self.progress.requests.is_empty() {
+                                request_diagnostics(ui, &self.progress.requests);
+                            }
+                        });
+                    });
+            }
+
+            let excluded = self.excluded_terms();
+            let allergens = active_allergens(&self.profiles, &self.profile).to_vec();
+            // Until the search is done, recipes still to come would count as dropped
+            if self.search_events.is_none() || self.progress.done {
+                self.result_diff_ui(ui);
+            }
+            if !self.results.is_empty() {
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label(tr!("results-heading"))
+                        .on_hover_text(tr!("results-keyboard-hint"));
+                    let before = self.sort_order;
+                    egui::ComboBox::from_id_source("sort_order")
+                        .selected_text(tr!("sort-selected", order = self.sort_order.label()))
+                        .show_ui(ui, |ui| {
+                            for order in SortOrder::ALL {
+                                ui.selectable_value(&mut self.sort_order, order, order.label());
+                            }
+                        });
+                    if self.sort_order != before {
+                        self.sort_results();
+                        self.page = 0;
+                    }
+                    let before = self.scoring_strategy;
+                    egui::ComboBox::from_id_source("results_scoring_strategy")
+                        .selected_text(tr!("scored-by", strategy = self.scoring_strategy.label()))
+                        .show_ui(ui, |ui| {
+                            for strategy in ScoringStrategy::ALL {
+                                ui.selectable_value(&mut self.scoring_strategy, strategy, strategy.label());
+                            }
+                        });
+                    if self.scoring_strategy != before {
+                        self.rescore_results();
+                        self.page = 0;
+                    }
+                    ui.selectable_value(&mut self.grid_view, false, tr!("view-list"));
+                    ui.selectable_value(&mut self.grid_view, true, tr!("view-grid"));
+                    if ui.button(tr!("export-results")).on_hover_text(tr!("export-results-hint")).clicked() {
+                        if let Some(path) = export::save_dialog("search_results", "csv", "CSV") {
+                            self.results_export_status = Some(match export::csv::export(&path, &self.results) {
+                                Ok(()) => tr!("saved-path", path = path.display().to_string()),
+                                Err(e) => tr!("export-failed", error = e.to_string()),
+                            });
+                        }
+                    }
+                    if let Some(status) = &self.results_export_status {
+                        ui.weak(status);
+                    }
+                    if let Some(tag) = &self.tag_filter {
+                        ui.label(tr!("tag-filter-active", tag = tag.as_str()));
+                        if ui.small_button("✖").on_hover_text(tr!("tag-filter-clear")).clicked() {
+                            self.tag_filter = None;
+                        }
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr!("instruction-filter-label"));
+                    let field = egui::TextEdit::singleline(&mut self.instruction_filter)
+                        .hint_text(tr!("instruction-filter-hint"))
+                        .desired_width(160.0);
+                    if ui.add(field).changed() {
+                        self.page = 0;
+                    }
+                    if !self.instruction_filter.is_empty() && ui.small_button("✖").clicked() {
+                        self.instruction_filter.clear();
+                    }
+                });
+                let tag_filter = self.tag_filter.clone();
+                let instruction_filter = self.instruction_filter.clone();
+                let visible: Vec<&MealDetail> = self
+                    .results
+                    .iter()
+                    .filter(|m| tag_filter.as_ref().is_none_or(|t| m.tags.contains(t)))
+                    .filter(|m| filters::instructions_mention(m, &instruction_filter))
+                    .collect();
+                let pages = visible.len().div_ceil(PAGE_SIZE).max(1);
+                // The tag and instruction filters can shrink the list under the current page
+                self.page = self.page.min(pages - 1);
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(self.page > 0, egui::Button::new(tr!("page-prev"))).clicked() {
+                        self.page -= 1;
+                    }
+                    ui.label(tr!("page-status", page = self.page + 1, pages = pages, count = visible.len()));
+                    if ui.add_enabled(self.page + 1 < pages, egui::Button::new(tr!("page-next"))).clicked() {
+                        self.page += 1;
+                    }
+                });
+                let mut pop_out = None;
+                let first = self.page * PAGE_SIZE;
+                let page: Vec<&MealDetail> = visible.into_iter().skip(first).take(PAGE_SIZE).collect();
+                // Looked up before the closures below, which borrow the rest of `self`
+                let offline_dir = self.offline.then(|| self.image_dir.clone());
+                if self.grid_view {
+                    egui::Grid::new("result_grid").spacing([8.0, 8.0]).show(ui, |ui| {
+                        for (i, meal) in page.into_iter().enumerate() {
+                            ui.group(|ui| {
+                                ui.set_width(CARD_WIDTH);
+                                ui.vertical(|ui| {
+                                    if let Some(uri) = images::photo_uri(&meal.thumb, true, offline_dir.as_deref()) {
+                                        let size = egui::vec2(CARD_WIDTH, CARD_WIDTH);
+                                        if images::thumbnail(ui, &uri, size, 6.0).clicked() {
+                                            self.selected_id = Some(meal.id.clone());
+                                        }
+                                    }
+                                    let title = egui::Button::new(&meal.title).wrap();
+                                    if ui.add(title).clicked() {
+                                        self.selected_id = Some(meal.id.clone());
+                                    }
+                                    ui.horizontal(|ui| {
+                                        if score_button(ui, meal.score) {
+                                            self.score_breakdown = Some(meal.clone());
+                                        }
+                                        time_label(ui, meal);
+                                        difficulty_badge(ui, meal);
+                                        if let Some(diff) = &self.result_diff {
+                                            change_badge(ui, diff.change(meal));
+                                        }
+                                        let stars = self.ratings.get(&meal.id);
+                                        if stars > 0 {
+                                            ui.label(ratings::stars_text(stars));
+                                        }
+                                        favorite_button(ui, &mut self.favorites, &self.db, meal);
+                                        shopping_checkbox(ui, &mut self.shopping_recipes, meal);
+                                        compare_checkbox(ui, &mut self.compare, meal);
+                                    });
+                                    let hits = filters::excluded_matches(meal, &excluded);
+                                    if !hits.is_empty() {
+                                        ui.colored_label(ui.visuals().warn_fg_color, format!("⚠ {}", hits.join(", ")))
+                                            .on_hover_text(tr!("excluded-hint"));
+                                    }
+                                    ui.horizontal_wrapped(|ui| allergen_badges(ui, meal, &allergens));
+                                    if let Some(age) = self.cache_ages.get(&meal.id) {
+                                        cache_age_label(ui, *age);
+                                    }
+                                });
+                            });
+                            if (i + 1) % GRID_COLUMNS == 0 {
+                                ui.end_row();
+                            }
+                        }
+                    });
+                } else {
+                    for (i, meal) in page.into_iter().enumerate() {
+                        let i = first + i;
+                        ui.horizontal(|ui| {
+                            if let Some(uri) = images::photo_uri(&meal.thumb, true, offline_dir.as_deref()) {
+                                images::thumbnail(ui, &uri, egui::vec2(48.0, 48.0), 4.0);
+                            }
+                            let is_selected = self.selected_id.as_ref() == Some(&meal.id);
+                            let title = tr!("result-title", rank = i + 1, title = meal.title.as_str());
+                            if ui.selectable_label(is_selected, title).clicked() {
+                                self.selected_id = Some(meal.id.clone());
+                            }
+                            if score_button(ui, meal.score) {
+                                self.score_breakdown = Some(meal.clone());
+                            }
+                            time_label(ui, meal);
+                            difficulty_badge(ui, meal);
+                            if let Some(diff) = &self.result_diff {
+                                change_badge(ui, diff.change(meal));
+                            }
+                            if ui.small_button("🗗").on_hover_text(tr!("open-window-hint")).clicked() {
+                                pop_out = Some(meal.clone());
+                            }
+                            favorite_button(ui, &mut self.favorites, &self.db, meal);
+                            shopping_checkbox(ui, &mut self.shopping_recipes, meal);
+                            compare_checkbox(ui, &mut self.compare, meal);
+                            let stars = self.ratings.get(&meal.id);
+                            if stars > 0 {
+                                ui.label(ratings::stars_text(stars)).on_hover_text(tr!("your-rating"));
+                            }
+                            let hits = filters::excluded_matches(meal, &excluded);
+                            if !hits.is_empty() {
+                                ui.colored_label(ui.visuals().warn_fg_color, format!("⚠ {}", hits.join(", ")))
+                                    .on_hover_text(tr!("excluded-hint"));
+                            }
+                            allergen_badges(ui, meal, &allergens);
+                            if let Some(age) = self.cache_ages.get(&meal.id) {
+                                cache_age_label(ui, *age);
+                            }
+                            if self.cook_from_pantry {
+                                let missing = pantry::missing_count(meal, self.pantry.items());
+                                ui.weak(tr!("to-buy", count = missing));
+                            }
+                        });
+                    }
+                }
+                if let Some(meal) = pop_out {
+                    self.open_detail_window(&meal);
+                }
+            }
+
+            // Favorites and recent recipes stay viewable even when they aren't part of the current results
+            let selected = self.selected_id.as_ref().and_then(|id| {
+                self.results
+                    .iter()
+                    .find(|m| &m.id == id)
+                    .or_else(|| self.my_recipes.get(id))
+                    .or_else(|| self.favorites.get(id))
+                    .or_else(|| self.recently_viewed.get(id))
+                    .or_else(|| self.result_diff.as_ref().and_then(|d| d.get(id)))
+                    .cloned()
+            });
+
+            if let Some(meal) = selected {
+                self.record_viewed(&meal);
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label(tr!("details-heading"));
+                    if ui.small_button(tr!("open-in-window")).clicked() {
+                        self.open_detail_window(&meal);
+                    }
+                });
+                ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        self.recipe_detail_ui(ui, &meal);
+                    });
+            }
+        });
+
+        self.detail_windows_ui(ctx);
+        self.timer_alerts(ctx);
+        self.profile_picker(ctx);
+    }
+
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, session::STORAGE_KEY, &self.session());
+    }
+}
+
 fn main() -> eframe::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let log_guard = logging::init(&AppConfig::load(CONFIG_FILE).unwrap_or_default().log_dir());
+    if args.first().is_some_and(|a| a == headless::FLAG) {
+        let code = headless::run(&args[1..]);
+        // exit() skips destructors; flush the log file first
+        drop(log_guard);
+        std::process::exit(code);
+    }
+    if args.first().is_some_and(|a| a == tui::FLAG) {
+        if let Err(e) = tui::run() {
+            eprintln!("Terminal interface failed: {}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
     let options = eframe::NativeOptions::default();
     eframe::run_native(
         "Food Recipe Finder GUI",
         options,
-        Box::new(|_cc| Ok(Box::new(RecipeApp::default()))),
+        Box::new(|cc| {
+            egui_extras::install_image_loaders(&cc.egui_ctx);
+            images::ThreadedImageLoader::install(&cc.egui_ctx);
+            let mut app = RecipeApp::new();
+            if let Some(session) = cc.storage.and_then(|s| eframe::get_value(s, session::STORAGE_KEY)) {
+                app.restore_session(session);
+            }
+            Ok(Box::new(app))
+        }),
     )
 }
@@ -0,0 +1,70 @@
+// The week ahead: recipes put on days, so dinners can be planned and the
+// plan taken along in a calendar.
+
+use chrono::NaiveDate;
+use recipe_core::db::Database;
+use recipe_core::MealDetail;
+use serde::{Deserialize, Serialize};
+
+/// Days offered when planning: today and the six after it.
+pub const PLAN_DAYS: u64 = 7;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlannedMeal {
+    pub day: NaiveDate,
+    pub meal_id: String,
+    pub title: String,
+}
+
+#[derive(Default)]
+pub struct MealPlan {
+    items: Vec<PlannedMeal>,
+}
+
+impl MealPlan {
+    /// Days before `today` are dropped; the plan only looks ahead.
+    pub fn load(db: &Database, today: NaiveDate) -> rusqlite::Result<Self> {
+        let items = db
+            .load_meal_plan()?
+            .iter()
+            .filter_map(|json| serde_json::from_str::<PlannedMeal>(json).ok())
+            .filter(|planned| planned.day >= today)
+            .collect();
+        Ok(Self { items })
+    }
+
+    pub fn save(&self, db: &Database) -> rusqlite::Result<()> {
+        let rows: Vec<String> = self.items.iter().map(|p| serde_json::to_string(p).unwrap()).collect();
+        db.save_meal_plan(&rows)
+    }
+
+    /// Keeps the plan in date order. The same recipe twice on one day is kept once.
+    pub fn add(&mut self, day: NaiveDate, meal: &MealDetail) {
+        if self.items.iter().any(|p| p.day == day && p.meal_id == meal.id) {
+            return;
+        }
+        self.items.push(PlannedMeal {
+            day,
+            meal_id: meal.id.clone(),
+            title: meal.title.clone(),
+        });
+        self.items.sort_by_key(|p| p.day);
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        self.items.remove(index);
+    }
+
+    pub fn items(&self) -> &[PlannedMeal] {
+        &self.items
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+/// The days a meal can be planned for, starting with `today`.
+pub fn plan_days(today: NaiveDate) -> impl Iterator<Item = NaiveDate> {
+    (0..PLAN_DAYS).map(move |offset| today + chrono::Days::new(offset))
+}
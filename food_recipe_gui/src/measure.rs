@@ -0,0 +1,121 @@
+// Helpers for the free-text measures TheMealDB and friends hand out
+// ("1 1/2 cups", "200g", "½ tsp", "2 cloves").
+
+use recipe_core::quantity::{format_amount, unit_size, Dimension, Quantity};
+use recipe_core::MealDetail;
+use serde::{Deserialize, Serialize};
+
+/// Approximate grams for one of the given unit. Volumes assume the density of water.
+pub fn unit_grams(unit: &str) -> Option<f64> {
+    let word = unit
+        .split_whitespace()
+        .next()
+        .unwrap_or("")
+        .trim_end_matches('.')
+        .to_lowercase();
+    if let Some(size) = unit_size(&word) {
+        return Some(size.base);
+    }
+    // Units without an exact size, at their usual weight
+    let grams = match word.as_str() {
+        "mg" => 0.001,
+        "tsp" | "tsps" | "teaspoon" | "teaspoons" => 5.0,
+        "tbsp" | "tbsps" | "tbs" | "tblsp" | "tablespoon" | "tablespoons" => 15.0,
+        "pinch" | "dash" | "sprinkling" => 0.5,
+        "clove" | "cloves" => 5.0,
+        "can" | "cans" | "tin" | "tins" => 400.0,
+        "handful" | "handfuls" => 30.0,
+        "slice" | "slices" => 30.0,
+        _ => return None,
+    };
+    Some(grams)
+}
+
+/// Best-effort weight of a measure. Bare counts ("2") and unknown units are
+/// multiplied by `piece_grams`, the weight of one typical item.
+pub fn estimate_grams(measure: &str, piece_grams: f64) -> f64 {
+    let quantity = Quantity::parse(measure);
+    let amount = quantity.value.unwrap_or(1.0);
+    match unit_grams(&quantity.unit) {
+        Some(g) => amount * g,
+        None => amount * piece_grams,
+    }
+}
+
+/// Multiplies the leading amount of a measure: "200g" by 2 is "400g",
+/// "1 1/2 cups" by 2 is "3 cups". Measures without an amount are left alone.
+pub fn scale(measure: &str, factor: f64) -> String {
+    let quantity = Quantity::parse(measure);
+    if quantity.value.is_none() {
+        return measure.to_string();
+    }
+    quantity.scaled(factor).to_string()
+}
+
+/// Which units measures are shown in. `AsWritten` leaves them untouched.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UnitSystem {
+    #[default]
+    AsWritten,
+    Metric,
+    Imperial,
+}
+
+impl UnitSystem {
+    pub const ALL: [UnitSystem; 3] = [UnitSystem::AsWritten, UnitSystem::Metric, UnitSystem::Imperial];
+
+    pub fn label(self) -> String {
+        match self {
+            UnitSystem::AsWritten => tr!("units-as-written"),
+            UnitSystem::Metric => tr!("units-metric"),
+            UnitSystem::Imperial => tr!("units-imperial"),
+        }
+    }
+}
+
+// Picks a unit that keeps the number readable
+fn to_system(quantity: Dimension, base: f64, system: UnitSystem) -> (f64, &'static str) {
+    match (system, quantity) {
+        (UnitSystem::Metric, Dimension::Mass) if base >= 1000.0 => ((base / 100.0).round() / 10.0, "kg"),
+        (UnitSystem::Metric, Dimension::Mass) => (base.round(), "g"),
+        (UnitSystem::Metric, Dimension::Volume) if base >= 1000.0 => ((base / 100.0).round() / 10.0, "l"),
+        (UnitSystem::Metric, Dimension::Volume) => (base.round(), "ml"),
+        (_, Dimension::Mass) if base >= 453.6 => (quarters(base / 453.6), "lb"),
+        (_, Dimension::Mass) => (quarters(base / 28.35), "oz"),
+        (_, Dimension::Volume) if base < 15.0 => (quarters(base / 5.0), "tsp"),
+        (_, Dimension::Volume) if base < 60.0 => (quarters(base / 15.0), "tbsp"),
+        (_, Dimension::Volume) => (quarters(base / 240.0), "cups"),
+    }
+}
+
+// Imperial cooks measure in quarters; anything smaller shows as an eighth
+fn quarters(amount: f64) -> f64 {
+    ((amount * 4.0).round() / 4.0).max(0.125)
+}
+
+/// Rewrites a measure in the given unit system: "8 oz" becomes "227 g",
+/// "500 ml milk" becomes "2 cups milk". Unknown or missing units and ranges
+/// are left alone.
+pub fn convert(measure: &str, system: UnitSystem) -> String {
+    if system == UnitSystem::AsWritten {
+        return measure.to_string();
+    }
+    let quantity = Quantity::parse(measure);
+    let (Some(amount), Some(size), None) = (quantity.value, quantity.size(), quantity.upper) else {
+        return measure.to_string();
+    };
+    let from = if size.metric { UnitSystem::Metric } else { UnitSystem::Imperial };
+    if from == system {
+        return measure.to_string();
+    }
+    let (value, unit) = to_system(size.dimension, amount * size.base, system);
+    format!("{} {} {}", format_amount(value), unit, quantity.unit_rest()).trim_end().to_string()
+}
+
+/// Copy of a recipe with every measure converted, for exports and the shopping list.
+pub fn convert_meal(meal: &MealDetail, system: UnitSystem) -> MealDetail {
+    MealDetail {
+        measures: meal.measures.iter().map(|m| convert(m, system)).collect(),
+        ..meal.clone()
+    }
+}
@@ -0,0 +1,130 @@
+use recipe_core::MealDetail;
+use recipe_core::db::{Database, MealTable};
+
+/// IDs of user-written recipes start with this so they never clash with API IDs.
+pub const LOCAL_ID_PREFIX: &str = "local-";
+
+/// Recipes written by the user, searched alongside whatever provider is active.
+#[derive(Default)]
+pub struct MyRecipes {
+    recipes: Vec<MealDetail>,
+}
+
+impl MyRecipes {
+    pub fn load(db: &Database) -> rusqlite::Result<Self> {
+        Ok(Self {
+            recipes: db.load_meals(MealTable::MyRecipes)?,
+        })
+    }
+
+    pub fn save(&self, db: &Database) -> rusqlite::Result<()> {
+        db.save_meals(MealTable::MyRecipes, &self.recipes)
+    }
+
+    fn next_id(&self) -> String {
+        let highest = self
+            .recipes
+            .iter()
+            .filter_map(|r| r.id.strip_prefix(LOCAL_ID_PREFIX)?.parse::<u64>().ok())
+            .max()
+            .unwrap_or(0);
+        format!("{}{}", LOCAL_ID_PREFIX, highest + 1)
+    }
+
+    /// Replaces the recipe with the same ID, or adds it under a fresh ID if it
+    /// has none yet. Returns the ID it was stored under.
+    pub fn upsert(&mut self, mut recipe: MealDetail) -> String {
+        if recipe.id.is_empty() {
+            recipe.id = self.next_id();
+        }
+        let id = recipe.id.clone();
+        match self.recipes.iter_mut().find(|r| r.id == id) {
+            Some(existing) => *existing = recipe,
+            None => self.recipes.push(recipe),
+        }
+        id
+    }
+
+    pub fn remove(&mut self, id: &str) {
+        self.recipes.retain(|r| r.id != id);
+    }
+
+    pub fn get(&self, id: &str) -> Option<&MealDetail> {
+        self.recipes.iter().find(|r| r.id == id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &MealDetail> {
+        self.recipes.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.recipes.is_empty()
+    }
+}
+
+/// Editable copy of a recipe for the "My Recipes" form.
+#[derive(Default, Clone)]
+pub struct RecipeDraft {
+    /// The recipe being edited, so fields the form doesn't show survive a save.
+    /// Its ID is empty for a recipe that hasn't been saved yet.
+    base: MealDetail,
+    pub title: String,
+    pub category: String,
+    pub area: String,
+    /// (ingredient, measure) rows.
+    pub ingredients: Vec<(String, String)>,
+    pub instructions: String,
+}
+
+impl RecipeDraft {
+    pub fn new() -> Self {
+        Self {
+            ingredients: vec![(String::new(), String::new())],
+            ..Default::default()
+        }
+    }
+
+    pub fn from_meal(meal: &MealDetail) -> Self {
+        let ingredients = meal
+            .ingredients
+            .iter()
+            .enumerate()
+            .map(|(i, ing)| (ing.clone(), meal.measures.get(i).cloned().unwrap_or_default()))
+            .collect();
+        Self {
+            base: meal.clone(),
+            title: meal.title.clone(),
+            category: meal.category.clone(),
+            area: meal.area.clone(),
+            ingredients,
+            instructions: meal.instructions.clone(),
+        }
+    }
+
+    pub fn is_new(&self) -> bool {
+        self.base.id.is_empty()
+    }
+
+    pub fn is_valid(&self) -> bool {
+        !self.title.trim().is_empty() && self.ingredients.iter().any(|(ing, _)| !ing.trim().is_empty())
+    }
+
+    /// Rows without an ingredient name are dropped.
+    pub fn to_meal(&self) -> MealDetail {
+        let (ingredients, measures) = self
+            .ingredients
+            .iter()
+            .filter(|(ing, _)| !ing.trim().is_empty())
+            .map(|(ing, measure)| (ing.trim().to_string(), measure.trim().to_string()))
+            .unzip();
+        MealDetail {
+            title: self.title.trim().to_string(),
+            category: self.category.trim().to_string(),
+            area: self.area.trim().to_string(),
+            instructions: self.instructions.trim().to_string(),
+            ingredients,
+            measures,
+            ..self.base.clone()
+        }
+    }
+}
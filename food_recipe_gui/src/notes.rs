@@ -0,0 +1,27 @@
+use recipe_core::db::Database;
+use std::collections::HashMap;
+
+/// The user's own notes per meal ID ("less salt next time"), kept in memory
+/// and written through to the database one meal at a time.
+#[derive(Default)]
+pub struct Notes {
+    by_meal: HashMap<String, String>,
+}
+
+impl Notes {
+    pub fn load(db: &Database) -> rusqlite::Result<Self> {
+        Ok(Self {
+            by_meal: db.load_notes()?,
+        })
+    }
+
+    /// Editable note for a meal, empty if there is none yet.
+    pub fn entry(&mut self, meal_id: &str) -> &mut String {
+        self.by_meal.entry(meal_id.to_string()).or_default()
+    }
+
+    pub fn save(&self, db: &Database, meal_id: &str) -> rusqlite::Result<()> {
+        let body = self.by_meal.get(meal_id).map(String::as_str).unwrap_or("");
+        db.save_note(meal_id, body)
+    }
+}
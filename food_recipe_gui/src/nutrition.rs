@@ -0,0 +1,298 @@
+use crate::diet;
+use crate::measure::estimate_grams;
+use recipe_core::scoring::{Contribution, IngredientOverlap, Reason, RecipeScorer, ScoreInput};
+use recipe_core::MealDetail;
+use serde::{Deserialize, Serialize};
+
+/// Servings assumed when the provider doesn't say (TheMealDB never does).
+pub const DEFAULT_SERVINGS: u32 = 4;
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Nutrition {
+    pub calories: f64,
+    pub protein: f64,
+    pub fat: f64,
+    pub carbs: f64,
+    /// Part of `carbs`.
+    pub sugar: f64,
+}
+
+impl Nutrition {
+    fn add_scaled(&mut self, per_100g: &Nutrition, grams: f64) {
+        let factor = grams / 100.0;
+        self.calories += per_100g.calories * factor;
+        self.protein += per_100g.protein * factor;
+        self.fat += per_100g.fat * factor;
+        self.carbs += per_100g.carbs * factor;
+        self.sugar += per_100g.sugar * factor;
+    }
+
+    pub fn divided(&self, n: u32) -> Nutrition {
+        let n = n.max(1) as f64;
+        Nutrition {
+            calories: self.calories / n,
+            protein: self.protein / n,
+            fat: self.fat / n,
+            carbs: self.carbs / n,
+            sugar: self.sugar / n,
+        }
+    }
+}
+
+pub struct NutritionEstimate {
+    pub total: Nutrition,
+    pub servings: u32,
+    /// Ingredients with no entry in the table; they contribute nothing.
+    pub unmatched: Vec<String>,
+}
+
+impl NutritionEstimate {
+    pub fn per_serving(&self) -> Nutrition {
+        self.total.divided(self.servings)
+    }
+}
+
+// name, kcal, protein g, fat g, carbs g, of which sugar g (all per 100 g), grams per typical piece
+const NUTRITION_TABLE: &[(&str, f64, f64, f64, f64, f64, f64)] = &[
+    ("chicken breast", 165.0, 31.0, 3.6, 0.0, 0.0, 170.0),
+    ("chicken thigh", 209.0, 26.0, 10.9, 0.0, 0.0, 110.0),
+    ("chicken stock", 7.0, 1.0, 0.2, 0.5, 0.2, 240.0),
+    ("chicken", 215.0, 18.6, 15.0, 0.0, 0.0, 1000.0),
+    ("minced beef", 254.0, 17.0, 20.0, 0.0, 0.0, 250.0),
+    ("ground beef", 254.0, 17.0, 20.0, 0.0, 0.0, 250.0),
+    ("beef stock", 7.0, 1.0, 0.2, 0.5, 0.2, 240.0),
+    ("beef", 250.0, 26.0, 15.0, 0.0, 0.0, 250.0),
+    ("pork", 242.0, 27.0, 14.0, 0.0, 0.0, 250.0),
+    ("lamb", 294.0, 25.0, 21.0, 0.0, 0.0, 250.0),
+    ("bacon", 541.0, 37.0, 42.0, 1.4, 0.0, 12.0),
+    ("sausage", 301.0, 12.0, 27.0, 2.0, 1.0, 75.0),
+    ("salmon", 208.0, 20.0, 13.0, 0.0, 0.0, 150.0),
+    ("tuna", 132.0, 28.0, 1.0, 0.0, 0.0, 150.0),
+    ("prawn", 99.0, 24.0, 0.3, 0.2, 0.0, 15.0),
+    ("shrimp", 99.0, 24.0, 0.3, 0.2, 0.0, 15.0),
+    ("cod", 82.0, 18.0, 0.7, 0.0, 0.0, 150.0),
+    ("white fish", 82.0, 18.0, 0.7, 0.0, 0.0, 150.0),
+    ("eggplant", 25.0, 1.0, 0.2, 6.0, 3.5, 300.0),
+    ("aubergine", 25.0, 1.0, 0.2, 6.0, 3.5, 300.0),
+    ("egg", 155.0, 13.0, 11.0, 1.1, 1.1, 50.0),
+    ("coconut milk", 230.0, 2.3, 24.0, 6.0, 3.3, 400.0),
+    ("milk", 42.0, 3.4, 1.0, 5.0, 5.0, 240.0),
+    ("peanut butter", 588.0, 25.0, 50.0, 20.0, 9.0, 16.0),
+    ("butter", 717.0, 0.9, 81.0, 0.1, 0.1, 14.0),
+    ("parmesan", 431.0, 38.0, 29.0, 4.0, 0.9, 20.0),
+    ("cheese", 402.0, 25.0, 33.0, 1.3, 0.5, 30.0),
+    ("double cream", 340.0, 2.0, 36.0, 3.0, 3.0, 240.0),
+    ("cream", 340.0, 2.0, 36.0, 3.0, 3.0, 240.0),
+    ("yogurt", 59.0, 10.0, 0.4, 3.6, 3.2, 150.0),
+    ("flour", 364.0, 10.0, 1.0, 76.0, 0.3, 125.0),
+    ("brown sugar", 380.0, 0.1, 0.0, 98.0, 97.0, 12.0),
+    ("sugar", 387.0, 0.0, 0.0, 100.0, 100.0, 12.0),
+    ("honey", 304.0, 0.3, 0.0, 82.0, 82.0, 21.0),
+    ("vinegar", 18.0, 0.0, 0.0, 0.04, 0.4, 15.0),
+    ("rice", 365.0, 7.0, 0.7, 80.0, 0.1, 185.0),
+    ("spaghetti", 371.0, 13.0, 1.5, 75.0, 2.7, 100.0),
+    ("pasta", 371.0, 13.0, 1.5, 75.0, 2.7, 100.0),
+    ("noodles", 138.0, 4.5, 2.0, 25.0, 0.5, 100.0),
+    ("breadcrumbs", 395.0, 13.0, 5.0, 72.0, 6.0, 30.0),
+    ("bread", 265.0, 9.0, 3.2, 49.0, 5.0, 30.0),
+    ("sweet potato", 86.0, 1.6, 0.1, 20.0, 4.2, 130.0),
+    ("potato", 77.0, 2.0, 0.1, 17.0, 0.8, 170.0),
+    ("onion", 40.0, 1.1, 0.1, 9.0, 4.2, 110.0),
+    ("garlic", 149.0, 6.4, 0.5, 33.0, 1.0, 5.0),
+    ("tomato puree", 82.0, 4.3, 0.5, 19.0, 12.0, 16.0),
+    ("tomato paste", 82.0, 4.3, 0.5, 19.0, 12.0, 16.0),
+    ("chopped tomatoes", 32.0, 1.6, 0.3, 7.0, 4.0, 400.0),
+    ("tomato", 18.0, 0.9, 0.2, 3.9, 2.6, 120.0),
+    ("carrot", 41.0, 0.9, 0.2, 10.0, 4.7, 60.0),
+    ("celery", 16.0, 0.7, 0.2, 3.0, 1.3, 40.0),
+    ("black pepper", 251.0, 10.0, 3.3, 64.0, 0.6, 2.0),
+    ("red pepper", 31.0, 1.0, 0.3, 6.0, 4.2, 120.0),
+    ("green pepper", 20.0, 0.9, 0.2, 4.6, 2.4, 120.0),
+    ("bell pepper", 31.0, 1.0, 0.3, 6.0, 4.2, 120.0),
+    ("mushroom", 22.0, 3.1, 0.3, 3.3, 2.0, 18.0),
+    ("spinach", 23.0, 2.9, 0.4, 3.6, 0.4, 30.0),
+    ("broccoli", 34.0, 2.8, 0.4, 7.0, 1.7, 150.0),
+    ("peas", 81.0, 5.4, 0.4, 14.0, 5.7, 145.0),
+    ("lemon juice", 22.0, 0.4, 0.2, 7.0, 2.5, 15.0),
+    ("lemon", 29.0, 1.1, 0.3, 9.0, 2.5, 60.0),
+    ("lime", 30.0, 0.7, 0.2, 10.0, 1.7, 45.0),
+    ("olive oil", 884.0, 0.0, 100.0, 0.0, 0.0, 14.0),
+    ("oil", 884.0, 0.0, 100.0, 0.0, 0.0, 14.0),
+    ("chickpeas", 164.0, 9.0, 2.6, 27.0, 4.8, 240.0),
+    ("lentils", 116.0, 9.0, 0.4, 20.0, 1.8, 200.0),
+    ("beans", 127.0, 8.7, 0.5, 23.0, 0.3, 240.0),
+    ("avocado", 160.0, 2.0, 15.0, 9.0, 0.7, 150.0),
+    ("apple", 52.0, 0.3, 0.2, 14.0, 10.0, 180.0),
+    ("banana", 89.0, 1.1, 0.3, 23.0, 12.0, 120.0),
+    ("ginger", 80.0, 1.8, 0.8, 18.0, 1.7, 5.0),
+    ("soy sauce", 53.0, 8.0, 0.6, 4.9, 0.4, 16.0),
+    ("stock", 7.0, 1.0, 0.2, 0.5, 0.2, 240.0),
+    ("oats", 389.0, 17.0, 7.0, 66.0, 1.0, 40.0),
+    ("almonds", 579.0, 21.0, 50.0, 22.0, 4.4, 1.2),
+    ("cocoa", 228.0, 20.0, 14.0, 58.0, 1.8, 5.0),
+    ("chocolate", 546.0, 4.9, 31.0, 61.0, 48.0, 10.0),
+    ("water", 0.0, 0.0, 0.0, 0.0, 0.0, 240.0),
+    ("salt", 0.0, 0.0, 0.0, 0.0, 0.0, 6.0),
+];
+
+// Longest matching table name wins, so "olive oil" beats "oil" and "chicken stock" beats "chicken".
+// Whole words only, so "butternut squash" isn't butter
+fn lookup(ingredient: &str) -> Option<(Nutrition, f64)> {
+    let words = diet::words(ingredient);
+    NUTRITION_TABLE
+        .iter()
+        .filter(|(name, ..)| diet::in_list(&words, &[name]))
+        .max_by_key(|(name, ..)| name.len())
+        .map(|&(_, calories, protein, fat, carbs, sugar, piece)| {
+            (
+                Nutrition {
+                    calories,
+                    protein,
+                    fat,
+                    carbs,
+                    sugar,
+                },
+                piece,
+            )
+        })
+}
+
+/// Weight of one typical piece of the ingredient, if the table knows it.
+pub fn piece_grams(ingredient: &str) -> Option<f64> {
+    lookup(ingredient).map(|(_, piece)| piece)
+}
+
+pub fn estimate(meal: &MealDetail) -> NutritionEstimate {
+    let mut total = Nutrition::default();
+    let mut unmatched = Vec::new();
+    for (i, ing) in meal.ingredients.iter().enumerate() {
+        let measure = meal.measures.get(i).map(String::as_str).unwrap_or("");
+        match lookup(ing) {
+            Some((per_100g, piece)) => total.add_scaled(&per_100g, estimate_grams(measure, piece)),
+            None => unmatched.push(ing.clone()),
+        }
+    }
+    NutritionEstimate {
+        total,
+        servings: meal.servings.filter(|&s| s > 0).unwrap_or(DEFAULT_SERVINGS),
+        unmatched,
+    }
+}
+
+/// What a profile wants more or less of, for `NutritionAware`. The default
+/// is lean and filling: high protein, not too many calories.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NutritionGoals {
+    pub high_protein: bool,
+    pub low_calorie: bool,
+    pub low_sugar: bool,
+    pub low_fat: bool,
+}
+
+impl Default for NutritionGoals {
+    fn default() -> Self {
+        Self {
+            high_protein: true,
+            low_calorie: true,
+            low_sugar: false,
+            low_fat: false,
+        }
+    }
+}
+
+impl NutritionGoals {
+    /// Points for one serving, each goal worth at most 5 either way: a point
+    /// per 10 g of protein, minus one per 100 kcal over 700, per 5 g of sugar
+    /// over 10 g and per 5 g of fat over 20 g.
+    pub fn points(&self, per_serving: &Nutrition) -> f64 {
+        let over = |value: f64, limit: f64, step: f64| ((value - limit) / step).clamp(0.0, 5.0);
+        let mut points = 0.0;
+        if self.high_protein {
+            points += (per_serving.protein / 10.0).min(5.0);
+        }
+        if self.low_calorie {
+            points -= over(per_serving.calories, 700.0, 100.0);
+        }
+        if self.low_sugar {
+            points -= over(per_serving.sugar, 10.0, 5.0);
+        }
+        if self.low_fat {
+            points -= over(per_serving.fat, 20.0, 5.0);
+        }
+        points
+    }
+}
+
+/// Ingredient overlap nudged towards the active profile's nutrition goals.
+pub struct NutritionAware {
+    pub goals: NutritionGoals,
+}
+
+impl RecipeScorer for NutritionAware {
+    fn score(&self, meal: &MealDetail, input: &ScoreInput) -> i32 {
+        let points = self.goals.points(&estimate(meal).per_serving());
+        IngredientOverlap.score(meal, input) + points.round() as i32
+    }
+
+    fn explain(&self, meal: &MealDetail, input: &ScoreInput) -> Vec<Contribution> {
+        let mut lines = IngredientOverlap.explain(meal, input);
+        let points = self.goals.points(&estimate(meal).per_serving()).round() as i32;
+        if points != 0 {
+            lines.push(Contribution::new(Reason::Other(tr!("breakdown-nutrition")), points));
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn calories(ingredient: &str) -> Option<f64> {
+        lookup(ingredient).map(|(per_100g, _)| per_100g.calories)
+    }
+
+    #[test]
+    fn looks_up_whole_words_only() {
+        assert_eq!(calories("Butter"), Some(717.0));
+        assert_eq!(calories("Butternut Squash"), None);
+        assert_eq!(calories("Eggplant"), Some(25.0));
+        assert_eq!(calories("Eggs"), Some(155.0));
+        assert_eq!(calories("Codfish"), None);
+    }
+
+    #[test]
+    fn prefers_the_longest_name() {
+        assert_eq!(calories("Extra Virgin Olive Oil"), Some(884.0));
+        assert_eq!(calories("Chicken Stock"), Some(7.0));
+        assert_eq!(calories("Peanut Butter"), Some(588.0));
+        assert_eq!(piece_grams("Chicken Breasts"), Some(170.0));
+    }
+
+    #[test]
+    fn estimates_per_serving() {
+        let meal = MealDetail {
+            ingredients: vec!["Flour".into(), "Eggs".into(), "Butternut Squash".into()],
+            measures: vec!["200g".into(), "2".into(), "1".into()],
+            servings: Some(2),
+            ..Default::default()
+        };
+        let estimate = estimate(&meal);
+        // 200 g flour and two 50 g eggs
+        assert!((estimate.total.calories - (2.0 * 364.0 + 155.0)).abs() < 1e-6);
+        assert!((estimate.per_serving().protein - (2.0 * 10.0 + 13.0) / 2.0).abs() < 1e-6);
+        assert_eq!(estimate.unmatched, ["Butternut Squash"]);
+    }
+
+    #[test]
+    fn assumes_default_servings_when_unknown_or_zero() {
+        for servings in [None, Some(0)] {
+            let meal = MealDetail {
+                servings,
+                ..Default::default()
+            };
+            assert_eq!(estimate(&meal).servings, DEFAULT_SERVINGS);
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use crate::ingredient_list::IngredientList;
+use recipe_core::MealDetail;
+use recipe_core::matching::ingredient_matches;
+
+/// Name of the pantry in the database.
+pub const PANTRY_LIST: &str = "pantry";
+
+/// Ingredients the user currently has at home.
+pub type Pantry = IngredientList;
+
+fn covered(ingredient: &str, pantry: &[String]) -> bool {
+    pantry.iter().any(|p| ingredient_matches(ingredient, p))
+}
+
+/// Splits a meal's ingredients into those the pantry covers and those still to buy.
+pub fn coverage(meal: &MealDetail, pantry: &[String]) -> (Vec<String>, Vec<String>) {
+    meal.ingredients
+        .iter()
+        .cloned()
+        .partition(|ing| covered(ing, pantry))
+}
+
+/// Score for "cook from what I have": the number of pantry-covered ingredients.
+pub fn score(meal: &MealDetail, pantry: &[String]) -> i32 {
+    meal.ingredients.iter().filter(|ing| covered(ing, pantry)).count() as i32
+}
+
+pub fn missing_count(meal: &MealDetail, pantry: &[String]) -> usize {
+    meal.ingredients.iter().filter(|ing| !covered(ing, pantry)).count()
+}
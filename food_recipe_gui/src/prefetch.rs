@@ -0,0 +1,129 @@
+// Optional background job: keeps every meal in the categories of the user's
+// favorites in the meal cache, so browsing them is instant and works offline.
+
+use crate::favorites::Favorites;
+use crate::images;
+use crate::my_recipes::LOCAL_ID_PREFIX;
+use crate::profiles::Profiles;
+use recipe_core::db::Database;
+use recipe_core::provider::RecipeProvider;
+use recipe_core::MealDetail;
+use reqwest::Client;
+use std::collections::{BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Categories of every profile's favorites, each once.
+fn favorite_categories(db: &Database) -> BTreeSet<String> {
+    let mut categories = BTreeSet::new();
+    for profile in Profiles::load(db).unwrap_or_default().iter() {
+        let favorites = Favorites::load(db, &profile.name).unwrap_or_default();
+        categories.extend(favorites.iter().map(|m| m.category.trim().to_string()).filter(|c| !c.is_empty()));
+    }
+    categories
+}
+
+// Lookups fill the cache as a side effect; meals already cached cost nothing
+async fn prefetch_once(provider: &dyn RecipeProvider, db: &Database) {
+    for category in favorite_categories(db) {
+        let ids = match provider.search_by_category(&category).await {
+            Ok(ids) => ids,
+            Err(e) => {
+                tracing::warn!(category = category.as_str(), "prefetch failed: {}", e);
+                continue;
+            }
+        };
+        for id in ids {
+            if let Err(e) = provider.lookup(&id).await {
+                tracing::warn!(id = id.as_str(), "prefetch of meal failed: {}", e);
+            }
+        }
+    }
+}
+
+/// Prefetches right away and then every `interval`, for as long as the runtime lives.
+/// Favorites are re-read each round, so new ones are picked up.
+pub async fn run(provider: Arc<dyn RecipeProvider>, db: Arc<Database>, interval: Duration) {
+    let mut ticks = tokio::time::interval(interval);
+    loop {
+        ticks.tick().await;
+        prefetch_once(provider.as_ref(), &db).await;
+    }
+}
+
+// Every profile's favorites, each once
+fn all_favorites(db: &Database) -> Vec<MealDetail> {
+    let mut seen = HashSet::new();
+    let mut meals = Vec::new();
+    for profile in Profiles::load(db).unwrap_or_default().iter() {
+        let favorites = Favorites::load(db, &profile.name).unwrap_or_default();
+        meals.extend(favorites.iter().filter(|m| seen.insert(m.id.clone())).cloned());
+    }
+    meals
+}
+
+async fn download(client: &Client, url: &str, path: &Path) -> Result<(), String> {
+    let resp = client.get(url).send().await.map_err(|e| e.to_string())?;
+    if !resp.status().is_success() {
+        return Err(format!("server returned {}", resp.status()));
+    }
+    let bytes = resp.bytes().await.map_err(|e| e.to_string())?;
+    std::fs::write(path, bytes).map_err(|e| e.to_string())
+}
+
+// Whichever provider the favorite came from answers its ID; the others
+// don't know it. Photos already on disk aren't fetched again.
+async fn refresh_favorites_once(providers: &[Arc<dyn RecipeProvider>], db: &Database, client: &Client, image_dir: &Path) {
+    if let Err(e) = std::fs::create_dir_all(image_dir) {
+        eprintln!("Failed to create {}: {}", image_dir.display(), e);
+        return;
+    }
+    for favorite in all_favorites(db) {
+        let mut meal = favorite;
+        if !meal.id.starts_with(LOCAL_ID_PREFIX) {
+            for provider in providers {
+                match provider.lookup(&meal.id).await {
+                    Ok(Some(fresh)) => {
+                        if let Err(e) = db.update_favorite(&fresh) {
+                            eprintln!("Failed to save favorite {}: {}", fresh.id, e);
+                        }
+                        meal = fresh;
+                        break;
+                    }
+                    Ok(None) => {}
+                    Err(e) => tracing::warn!(id = meal.id.as_str(), provider = provider.name(), "favorite refresh failed: {}", e),
+                }
+            }
+        }
+        for url in images::recipe_photos(&meal.thumb) {
+            let path = images::offline_file(image_dir, &url);
+            if path.exists() {
+                continue;
+            }
+            if let Err(e) = download(client, &url, &path).await {
+                tracing::warn!(url = url.as_str(), "photo download failed: {}", e);
+            }
+        }
+    }
+}
+
+/// Refreshes every favorite and downloads its photos right away, then every
+/// `interval` if there is one. Favorites are re-read each round.
+pub async fn keep_favorites_offline(
+    providers: Vec<Arc<dyn RecipeProvider>>,
+    db: Arc<Database>,
+    client: Client,
+    image_dir: PathBuf,
+    interval: Option<Duration>,
+) {
+    let Some(interval) = interval else {
+        refresh_favorites_once(&providers, &db, &client, &image_dir).await;
+        return;
+    };
+    let mut ticks = tokio::time::interval(interval);
+    loop {
+        ticks.tick().await;
+        refresh_favorites_once(&providers, &db, &client, &image_dir).await;
+    }
+}
@@ -0,0 +1,71 @@
+// Named searches: everything in the search form, sort order included, saved
+// under a name so a regular search is one click away.
+
+use crate::search::{SearchQuery, SortOrder};
+use recipe_core::db::Database;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Preset {
+    #[serde(skip)]
+    pub name: String,
+    /// Ingredients, taste and filters; the serialized part of a query.
+    pub query: SearchQuery,
+    pub sort: SortOrder,
+    /// The exclude box as typed; allergies are added when it runs.
+    pub exclude: String,
+}
+
+#[derive(Default)]
+pub struct Presets {
+    presets: Vec<Preset>,
+}
+
+impl Presets {
+    pub fn load(db: &Database) -> rusqlite::Result<Self> {
+        let presets = db
+            .load_presets()?
+            .into_iter()
+            .map(|(name, json)| Preset {
+                name,
+                ..serde_json::from_str(&json).unwrap_or_default()
+            })
+            .collect();
+        Ok(Self { presets })
+    }
+
+    pub fn save(&self, db: &Database) -> rusqlite::Result<()> {
+        let rows: Vec<(String, String)> = self
+            .presets
+            .iter()
+            .map(|p| (p.name.clone(), serde_json::to_string(p).unwrap()))
+            .collect();
+        db.save_presets(&rows)
+    }
+
+    /// Adds the preset, or replaces the one with the same name. Returns
+    /// false for a blank name.
+    pub fn set(&mut self, preset: Preset) -> bool {
+        if preset.name.trim().is_empty() {
+            return false;
+        }
+        match self.presets.iter_mut().find(|p| p.name.eq_ignore_ascii_case(preset.name.trim())) {
+            Some(existing) => *existing = Preset { name: existing.name.clone(), ..preset },
+            None => self.presets.push(Preset { name: preset.name.trim().to_string(), ..preset }),
+        }
+        true
+    }
+
+    pub fn remove(&mut self, name: &str) {
+        self.presets.retain(|p| p.name != name);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Preset> {
+        self.presets.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.presets.is_empty()
+    }
+}
@@ -0,0 +1,115 @@
+use crate::allergens::Allergen;
+use crate::diet::Diet;
+use crate::nutrition::NutritionGoals;
+use recipe_core::db::{Database, DEFAULT_PROFILE};
+use serde::{Deserialize, Serialize};
+
+/// One person's search preferences. Favorites and allergies are kept per
+/// profile too, under the profile's name.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    #[serde(skip)]
+    pub name: String,
+    /// Filled into the taste field when the profile is picked.
+    pub taste: String,
+    /// Applied to every search while the profile is active.
+    pub diet: Option<Diet>,
+    /// Recipes containing these get a warning badge.
+    pub allergens: Vec<Allergen>,
+    /// What the nutrition-aware ranking favours for this profile.
+    pub nutrition_goals: NutritionGoals,
+}
+
+impl Profile {
+    fn named(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Self::default()
+        }
+    }
+}
+
+/// All profiles, the default one always first.
+pub struct Profiles {
+    profiles: Vec<Profile>,
+}
+
+impl Default for Profiles {
+    fn default() -> Self {
+        Self {
+            profiles: vec![Profile::named(DEFAULT_PROFILE)],
+        }
+    }
+}
+
+impl Profiles {
+    pub fn load(db: &Database) -> rusqlite::Result<Self> {
+        let mut profiles = Self::default();
+        for (name, json) in db.load_profiles()? {
+            let mut profile: Profile = serde_json::from_str(&json).unwrap_or_default();
+            profile.name = name;
+            match profiles.get_mut(&profile.name) {
+                Some(existing) => *existing = profile,
+                None => profiles.profiles.push(profile),
+            }
+        }
+        Ok(profiles)
+    }
+
+    pub fn save(&self, db: &Database) -> rusqlite::Result<()> {
+        let rows: Vec<(String, String)> = self
+            .profiles
+            .iter()
+            .map(|p| (p.name.clone(), serde_json::to_string(p).unwrap()))
+            .collect();
+        db.save_profiles(&rows)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Profile> {
+        self.profiles.iter().find(|p| p.name == name)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Profile> {
+        self.profiles.iter_mut().find(|p| p.name == name)
+    }
+
+    /// Adds an empty profile unless the name is blank or taken. Returns whether it was added.
+    pub fn add(&mut self, name: &str) -> bool {
+        let name = name.trim();
+        if name.is_empty() || self.profiles.iter().any(|p| p.name.eq_ignore_ascii_case(name)) {
+            return false;
+        }
+        self.profiles.push(Profile::named(name));
+        true
+    }
+
+    /// Removes the profile along with its favorites and allergies. The
+    /// default profile stays.
+    pub fn remove(&mut self, db: &Database, name: &str) -> rusqlite::Result<()> {
+        if name == DEFAULT_PROFILE {
+            return Ok(());
+        }
+        self.profiles.retain(|p| p.name != name);
+        db.save_favorites(name, &[])?;
+        db.save_list(&allergies_list(name), &[])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Profile> {
+        self.profiles.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.profiles.len()
+    }
+}
+
+/// Database list holding the profile's allergies. The default profile keeps
+/// the list from before profiles existed.
+pub fn allergies_list(profile: &str) -> String {
+    if profile == DEFAULT_PROFILE {
+        crate::filters::ALLERGIES_LIST.to_string()
+    } else {
+        format!("{}:{}", crate::filters::ALLERGIES_LIST, profile)
+    }
+}
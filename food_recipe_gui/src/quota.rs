@@ -0,0 +1,83 @@
+// Counts the requests sent to the recipe providers, for this run and for
+// the day, so a rate-limited API key's allowance can be kept an eye on.
+// The limit is a soft one: the counter warns, searches still go out.
+
+use chrono::{Local, NaiveDate};
+use recipe_core::provider::http;
+use serde::{Deserialize, Serialize};
+
+/// Share of the limit at which the counter starts warning.
+const NEAR_LIMIT: f64 = 0.8;
+
+/// Today's count as of the last exit, so it carries over a restart.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DailyCount {
+    pub day: Option<NaiveDate>,
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Fine,
+    Near,
+    Over,
+}
+
+pub struct RequestCounter {
+    day: NaiveDate,
+    /// Sent today before this run started.
+    earlier: u64,
+    /// `http::requests_sent()` when the day began, for runs past midnight.
+    day_start: u64,
+}
+
+impl RequestCounter {
+    pub fn new(saved: &DailyCount) -> Self {
+        let today = Local::now().date_naive();
+        Self {
+            day: today,
+            earlier: if saved.day == Some(today) { saved.count } else { 0 },
+            day_start: 0,
+        }
+    }
+
+    /// Starts a fresh day count once the date has changed.
+    pub fn roll_over(&mut self) {
+        let today = Local::now().date_naive();
+        if today != self.day {
+            self.day = today;
+            self.earlier = 0;
+            self.day_start = http::requests_sent();
+        }
+    }
+
+    pub fn session(&self) -> u64 {
+        http::requests_sent()
+    }
+
+    pub fn today(&self) -> u64 {
+        self.earlier + http::requests_sent() - self.day_start
+    }
+
+    pub fn level(&self, limit: Option<u64>) -> Level {
+        let Some(limit) = limit.filter(|&l| l > 0) else {
+            return Level::Fine;
+        };
+        let today = self.today();
+        if today >= limit {
+            Level::Over
+        } else if today as f64 >= limit as f64 * NEAR_LIMIT {
+            Level::Near
+        } else {
+            Level::Fine
+        }
+    }
+
+    pub fn saved(&self) -> DailyCount {
+        DailyCount {
+            day: Some(self.day),
+            count: self.today(),
+        }
+    }
+}
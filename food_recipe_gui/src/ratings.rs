@@ -0,0 +1,38 @@
+use recipe_core::db::Database;
+use std::collections::HashMap;
+
+pub const MAX_STARS: u8 = 5;
+
+/// Star ratings the user gave after cooking, by meal ID.
+#[derive(Default)]
+pub struct Ratings {
+    by_meal: HashMap<String, u8>,
+}
+
+impl Ratings {
+    pub fn load(db: &Database) -> rusqlite::Result<Self> {
+        Ok(Self {
+            by_meal: db.load_ratings()?,
+        })
+    }
+
+    /// 0 for a meal that hasn't been rated.
+    pub fn get(&self, meal_id: &str) -> u8 {
+        self.by_meal.get(meal_id).copied().unwrap_or(0)
+    }
+
+    pub fn set(&mut self, db: &Database, meal_id: &str, stars: u8) -> rusqlite::Result<()> {
+        let stars = stars.min(MAX_STARS);
+        if stars == 0 {
+            self.by_meal.remove(meal_id);
+        } else {
+            self.by_meal.insert(meal_id.to_string(), stars);
+        }
+        db.save_rating(meal_id, stars)
+    }
+}
+
+/// "★★★☆☆"
+pub fn stars_text(stars: u8) -> String {
+    (1..=MAX_STARS).map(|i| if i <= stars { '★' } else { '☆' }).collect()
+}
@@ -0,0 +1,51 @@
+use recipe_core::MealDetail;
+use recipe_core::db::{Database, MealTable};
+
+/// How many opened recipes are remembered.
+const MAX_RECENT: usize = 15;
+
+/// Recipes whose details were opened, most recent first.
+#[derive(Default)]
+pub struct RecentlyViewed {
+    meals: Vec<MealDetail>,
+}
+
+impl RecentlyViewed {
+    pub fn load(db: &Database) -> rusqlite::Result<Self> {
+        Ok(Self {
+            meals: db.load_meals(MealTable::RecentlyViewed)?,
+        })
+    }
+
+    pub fn save(&self, db: &Database) -> rusqlite::Result<()> {
+        db.save_meals(MealTable::RecentlyViewed, &self.meals)
+    }
+
+    /// Moves the meal to the front, dropping the oldest beyond the limit.
+    /// Returns false if it was already the most recent one.
+    pub fn record(&mut self, meal: &MealDetail) -> bool {
+        if self.meals.first().is_some_and(|m| m.id == meal.id) {
+            return false;
+        }
+        self.meals.retain(|m| m.id != meal.id);
+        self.meals.insert(0, meal.clone());
+        self.meals.truncate(MAX_RECENT);
+        true
+    }
+
+    pub fn clear(&mut self) {
+        self.meals.clear();
+    }
+
+    pub fn get(&self, id: &str) -> Option<&MealDetail> {
+        self.meals.iter().find(|m| m.id == id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &MealDetail> {
+        self.meals.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.meals.is_empty()
+    }
+}
@@ -0,0 +1,48 @@
+// What changed between a search and the one refining it: recipes that are
+// new, recipes that dropped out and scores that moved, so tweaking the form
+// shows its effect instead of a fresh list.
+
+use recipe_core::MealDetail;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    New,
+    /// Score difference to the earlier search, never 0.
+    Score(i32),
+    Same,
+}
+
+/// The results of the earlier search, to compare the current ones against.
+pub struct ResultDiff {
+    before: Vec<MealDetail>,
+    scores: HashMap<String, i32>,
+}
+
+impl ResultDiff {
+    pub fn new(results: &[MealDetail]) -> Self {
+        Self {
+            before: results.to_vec(),
+            scores: results.iter().map(|m| (m.id.clone(), m.score)).collect(),
+        }
+    }
+
+    pub fn change(&self, meal: &MealDetail) -> Change {
+        match self.scores.get(&meal.id) {
+            None => Change::New,
+            Some(&score) if score == meal.score => Change::Same,
+            Some(&score) => Change::Score(meal.score - score),
+        }
+    }
+
+    /// Earlier results missing from `results`, best first.
+    pub fn dropped(&self, results: &[MealDetail]) -> Vec<&MealDetail> {
+        let ids: HashSet<&str> = results.iter().map(|m| m.id.as_str()).collect();
+        self.before.iter().filter(|m| !ids.contains(m.id.as_str())).collect()
+    }
+
+    /// An earlier result, so a dropped recipe can still be opened.
+    pub fn get(&self, id: &str) -> Option<&MealDetail> {
+        self.before.iter().find(|m| m.id == id)
+    }
+}
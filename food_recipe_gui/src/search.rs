@@ -0,0 +1,543 @@
+use crate::cost::{self, Price};
+use crate::diet::Diet;
+use crate::difficulty::{self, Difficulty};
+use crate::ratings::Ratings;
+use crate::{leftovers, seasonal};
+use crate::nutrition::{NutritionAware, NutritionGoals};
+use crate::{filters, i18n, nutrition, pantry, steps};
+use eframe::egui;
+use futures::stream::{self, StreamExt};
+use recipe_core::matching::ingredient_matches;
+use recipe_core::provider::http::RequestRecord;
+use recipe_core::provider::{FetchResult, RecipeProvider};
+use recipe_core::scoring::{
+    Contribution, IngredientOverlap, PantryCoverage, Reason, RecipeScorer, ScoreInput, ScoringConfig,
+};
+use recipe_core::synonyms;
+use recipe_core::MealDetail;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+
+/// Everything a search was asked for. The serialized form is what the search
+/// history keeps; the derived parts are filled in again when it is re-run.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SearchQuery {
+    /// Search by dish name instead of by ingredients.
+    pub name: Option<String>,
+    /// Browse every meal whose name starts with this letter.
+    pub first_letter: Option<char>,
+    pub main: Vec<String>,
+    pub sub: Vec<String>,
+    pub taste: Option<String>,
+    /// Set in "cook from what I have" mode: recipes are ranked by pantry coverage instead.
+    pub pantry: Option<Vec<String>>,
+    /// Pantry contents; these never count against a recipe.
+    #[serde(skip)]
+    pub on_hand: Vec<String>,
+    /// Recipes using any of these are dropped before scoring.
+    #[serde(skip)]
+    pub excluded: Vec<String>,
+    pub category: Option<String>,
+    pub area: Option<String>,
+    pub diet: Option<Diet>,
+    /// Recipes estimated to cost more than this in total are dropped.
+    pub max_cost: Option<f64>,
+    /// Recipes estimated above this many kcal per serving are dropped.
+    pub max_calories: Option<f64>,
+    /// Recipes with more ingredients than this are dropped.
+    pub max_ingredients: Option<usize>,
+    /// Recipes whose instructions add up to more minutes than this are
+    /// dropped; recipes without instructions are kept.
+    pub max_minutes: Option<u64>,
+    /// Recipes rated harder than this are dropped.
+    pub max_difficulty: Option<Difficulty>,
+    /// Price table `max_cost` is judged by.
+    #[serde(skip)]
+    pub prices: Vec<Price>,
+    /// Produce in season now; empty unless the seasonal boost is on.
+    #[serde(skip)]
+    pub seasonal: Vec<String>,
+    /// Leftovers to use up; empty unless the leftover boost is on.
+    #[serde(skip)]
+    pub leftovers: Vec<String>,
+    #[serde(skip)]
+    pub weights: ScoringConfig,
+    #[serde(skip)]
+    pub strategy: ScoringStrategy,
+    /// The active profile's, for the nutrition-aware strategy.
+    #[serde(skip)]
+    pub nutrition_goals: NutritionGoals,
+}
+
+impl SearchQuery {
+    pub fn matches_filters(&self, meal: &MealDetail) -> bool {
+        let matches = |wanted: &Option<String>, actual: &str| {
+            wanted.as_ref().is_none_or(|w| w.eq_ignore_ascii_case(actual))
+        };
+        matches(&self.category, &meal.category)
+            && matches(&self.area, &meal.area)
+            && filters::excluded_matches(meal, &self.excluded).is_empty()
+            && self.diet.is_none_or(|diet| diet.violations(meal).is_empty())
+            && self.max_cost.is_none_or(|max| cost::estimate(meal, &self.prices).total <= max)
+            && self.max_calories.is_none_or(|max| nutrition::estimate(meal).per_serving().calories <= max)
+            && self.max_ingredients.is_none_or(|max| meal.ingredients.len() <= max)
+            && self.max_minutes.is_none_or(|max| {
+                steps::estimated_minutes(&meal.instructions).is_none_or(|minutes| minutes <= max)
+            })
+            && self.max_difficulty.is_none_or(|max| difficulty::rate(meal) <= max)
+    }
+
+    /// Short summary for the search history, empty for a query with no inputs.
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(name) = &self.name {
+            parts.push(format!("name: {}", name));
+        }
+        if let Some(letter) = self.first_letter {
+            parts.push(format!("letter: {}", letter));
+        }
+        if self.pantry.is_some() {
+            parts.push("from pantry".to_string());
+        } else {
+            if !self.main.is_empty() {
+                parts.push(format!("main: {}", self.main.join(", ")));
+            }
+            if !self.sub.is_empty() {
+                parts.push(format!("sub: {}", self.sub.join(", ")));
+            }
+        }
+        if let Some(taste) = &self.taste {
+            parts.push(format!("taste: {}", taste));
+        }
+        if let Some(category) = &self.category {
+            parts.push(format!("category: {}", category));
+        }
+        if let Some(area) = &self.area {
+            parts.push(format!("area: {}", area));
+        }
+        if let Some(diet) = self.diet {
+            parts.push(format!("diet: {}", diet.label().to_lowercase()));
+        }
+        if let Some(max) = self.max_cost {
+            parts.push(format!("max cost: {:.2}", max));
+        }
+        if let Some(max) = self.max_calories {
+            parts.push(format!("max kcal: {:.0}", max));
+        }
+        if let Some(max) = self.max_ingredients {
+            parts.push(format!("max ingredients: {}", max));
+        }
+        if let Some(max) = self.max_minutes {
+            parts.push(format!("max time: {} min", max));
+        }
+        if let Some(max) = self.max_difficulty {
+            parts.push(format!("max difficulty: {}", max.name()));
+        }
+        parts.join("; ")
+    }
+
+    /// Whether a recipe we hold locally belongs in the results, mirroring what
+    /// `find_candidates` asks the provider for.
+    pub fn selects(&self, meal: &MealDetail) -> bool {
+        if !self.matches_filters(meal) {
+            return false;
+        }
+        if let Some(name) = &self.name {
+            return meal.title.to_lowercase().contains(&name.to_lowercase());
+        }
+        if let Some(letter) = self.first_letter {
+            return meal
+                .title
+                .chars()
+                .next()
+                .is_some_and(|c| c.to_lowercase().eq(letter.to_lowercase()));
+        }
+        let uses = |want: &String| meal.ingredients.iter().any(|ing| ingredient_matches(ing, want));
+        if !self.main.is_empty() {
+            return self.main.iter().all(uses);
+        }
+        if !self.sub.is_empty() {
+            return self.sub.iter().any(uses);
+        }
+        self.category.is_some() || self.area.is_some()
+    }
+
+    fn score_input(&self) -> ScoreInput<'_> {
+        ScoreInput {
+            main: &self.main,
+            sub: &self.sub,
+            taste: &self.taste,
+            on_hand: &self.on_hand,
+            weights: &self.weights,
+        }
+    }
+
+    pub fn score(&self, meal: &MealDetail) -> i32 {
+        let base = match &self.pantry {
+            Some(items) => pantry::score(meal, items),
+            None => self.strategy.scorer(self.nutrition_goals).score(meal, &self.score_input()),
+        };
+        base + seasonal::seasonal_count(meal, &self.seasonal) as i32 * self.weights.seasonal_ingredient
+            + leftovers::leftover_count(meal, &self.leftovers) as i32 * self.weights.leftover_ingredient
+    }
+
+    /// `score` line by line, for the score breakdown. Scorers that can't
+    /// explain themselves leave a gap the caller shows as unexplained.
+    pub fn explain(&self, meal: &MealDetail) -> Vec<Contribution> {
+        let mut lines = match &self.pantry {
+            Some(items) => {
+                let (have, _) = pantry::coverage(meal, items);
+                have.into_iter().map(|ing| Contribution::new(Reason::Have(ing), 1)).collect()
+            }
+            None => self.strategy.scorer(self.nutrition_goals).explain(meal, &self.score_input()),
+        };
+        let seasonal = seasonal::seasonal_count(meal, &self.seasonal);
+        if seasonal > 0 {
+            let reason = Reason::Other(tr!("breakdown-seasonal", count = seasonal));
+            lines.push(Contribution::new(reason, seasonal as i32 * self.weights.seasonal_ingredient));
+        }
+        let leftover = leftovers::leftover_count(meal, &self.leftovers);
+        if leftover > 0 {
+            let reason = Reason::Other(tr!("breakdown-leftovers", count = leftover));
+            lines.push(Contribution::new(reason, leftover as i32 * self.weights.leftover_ingredient));
+        }
+        lines
+    }
+
+    /// Best first; in pantry mode ties go to the recipe with less left to buy.
+    pub fn sort(&self, meals: &mut [MealDetail]) {
+        match &self.pantry {
+            Some(items) => meals.sort_by_key(|m| (Reverse(m.score), pantry::missing_count(m, items))),
+            None => meals.sort_by_key(|m| Reverse(m.score)),
+        }
+    }
+}
+
+/// Which `RecipeScorer` ranks results outside pantry mode; picked in the settings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScoringStrategy {
+    #[default]
+    IngredientOverlap,
+    PantryCoverage,
+    NutritionAware,
+}
+
+impl ScoringStrategy {
+    pub const ALL: [ScoringStrategy; 3] = [
+        ScoringStrategy::IngredientOverlap,
+        ScoringStrategy::PantryCoverage,
+        ScoringStrategy::NutritionAware,
+    ];
+
+    pub fn label(self) -> String {
+        match self {
+            ScoringStrategy::IngredientOverlap => tr!("scorer-ingredient-overlap"),
+            ScoringStrategy::PantryCoverage => tr!("scorer-pantry-coverage"),
+            ScoringStrategy::NutritionAware => tr!("scorer-nutrition-aware"),
+        }
+    }
+
+    pub fn scorer(self, goals: NutritionGoals) -> Box<dyn RecipeScorer> {
+        match self {
+            ScoringStrategy::IngredientOverlap => Box::new(IngredientOverlap),
+            ScoringStrategy::PantryCoverage => Box::new(PantryCoverage),
+            ScoringStrategy::NutritionAware => Box::new(NutritionAware { goals }),
+        }
+    }
+}
+
+/// How the result list is ordered. Anything but `Score` is applied on top of
+/// the score order, so ties still put the better match first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SortOrder {
+    #[default]
+    Score,
+    Title,
+    Category,
+    Area,
+    IngredientCount,
+    Rating,
+}
+
+impl SortOrder {
+    pub const ALL: [SortOrder; 6] = [
+        SortOrder::Score,
+        SortOrder::Title,
+        SortOrder::Category,
+        SortOrder::Area,
+        SortOrder::IngredientCount,
+        SortOrder::Rating,
+    ];
+
+    pub fn label(self) -> String {
+        match self {
+            SortOrder::Score => tr!("sort-score"),
+            SortOrder::Title => tr!("sort-title"),
+            SortOrder::Category => tr!("sort-category"),
+            SortOrder::Area => tr!("sort-area"),
+            SortOrder::IngredientCount => tr!("sort-ingredient-count"),
+            SortOrder::Rating => tr!("sort-rating"),
+        }
+    }
+
+    pub fn apply(self, query: &SearchQuery, ratings: &Ratings, meals: &mut [MealDetail]) {
+        query.sort(meals);
+        match self {
+            SortOrder::Score => {}
+            SortOrder::Title => meals.sort_by_key(|m| m.title.to_lowercase()),
+            SortOrder::Category => meals.sort_by_key(|m| m.category.to_lowercase()),
+            SortOrder::Area => meals.sort_by_key(|m| m.area.to_lowercase()),
+            SortOrder::IngredientCount => meals.sort_by_key(|m| m.ingredients.len()),
+            SortOrder::Rating => meals.sort_by_key(|m| Reverse(ratings.get(&m.id))),
+        }
+    }
+}
+
+/// Meal lookups allowed in flight at once during a search.
+const MAX_CONCURRENT_LOOKUPS: usize = 6;
+
+/// Progress of a running search, streamed to the UI as it happens.
+#[derive(Debug)]
+pub enum SearchEvent {
+    /// Number of meals about to be looked up.
+    CandidatesFound(usize),
+    /// A meal that passed the filters, already scored.
+    MealLoaded(Box<MealDetail>),
+    /// A candidate was looked up but didn't pass the filters.
+    MealSkipped,
+    /// A request failed even after retrying; the search carries on if it can.
+    Error(String),
+    /// The search was stopped before it finished; nothing else follows.
+    Cancelled,
+    Done,
+}
+
+async fn find_candidates(provider: &dyn RecipeProvider, query: &SearchQuery) -> FetchResult<HashSet<String>> {
+    if query.main.is_empty() && query.sub.is_empty() {
+        // No ingredients: browse by category and/or area instead
+        return match (&query.category, &query.area) {
+            (Some(c), Some(a)) => {
+                let by_area = provider.search_by_area(a).await?;
+                Ok(provider
+                    .search_by_category(c)
+                    .await?
+                    .into_iter()
+                    .filter(|id| by_area.contains(id))
+                    .collect())
+            }
+            (Some(c), None) => provider.search_by_category(c).await,
+            (None, Some(a)) => provider.search_by_area(a).await,
+            (None, None) => Ok(HashSet::new()),
+        };
+    }
+
+    // Every main ingredient is required; sub ingredients only widen the net
+    // when there are no main ones. Providers know them by TheMealDB's names.
+    let canonical = |names: &[String]| names.iter().map(|n| synonyms::canonical_name(n)).collect::<Vec<_>>();
+    if !query.main.is_empty() {
+        provider.search_by_all_ingredients(&canonical(&query.main)).await
+    } else {
+        provider.search_by_ingredients(&canonical(&query.sub)).await
+    }
+}
+
+// Runs one complete search on the tokio runtime. The receiver may be dropped
+// when a newer search replaces this one, so send failures are ignored.
+pub async fn run_search(
+    provider: Arc<dyn RecipeProvider>,
+    query: SearchQuery,
+    events: Sender<SearchEvent>,
+    cancel: CancellationToken,
+    ctx: egui::Context,
+) {
+    let send = |event| {
+        let _ = events.send(event);
+        ctx.request_repaint();
+    };
+
+    tracing::info!(provider = provider.name(), query = %query.describe(), "search started");
+
+    // Name and letter searches come back as full meals, so there is nothing to look up
+    let direct = match (&query.name, query.first_letter) {
+        (Some(name), _) => Some(provider.search_by_name(name)),
+        (None, Some(letter)) => Some(provider.search_by_first_letter(letter)),
+        (None, None) => None,
+    };
+    if let Some(direct) = direct {
+        let Some(found) = cancel.run_until_cancelled(direct).await else {
+            send(SearchEvent::Cancelled);
+            return;
+        };
+        match found {
+            Ok(meals) => {
+                send(SearchEvent::CandidatesFound(meals.len()));
+                for mut meal in meals {
+                    if query.matches_filters(&meal) {
+                        meal.score = query.score(&meal);
+                        send(SearchEvent::MealLoaded(Box::new(meal)));
+                    } else {
+                        send(SearchEvent::MealSkipped);
+                    }
+                }
+            }
+            Err(e) => send(SearchEvent::Error(tr!("search-failed", error = i18n::fetch_error(&e)))),
+        }
+        send(SearchEvent::Done);
+        return;
+    }
+
+    let Some(candidates) = cancel.run_until_cancelled(find_candidates(provider.as_ref(), &query)).await else {
+        send(SearchEvent::Cancelled);
+        return;
+    };
+    let ids_to_use = match candidates {
+        Ok(ids) => ids,
+        Err(e) => {
+            tracing::warn!("candidate search failed: {}", e);
+            send(SearchEvent::Error(tr!("search-failed", error = i18n::fetch_error(&e))));
+            send(SearchEvent::Done);
+            return;
+        }
+    };
+    tracing::info!(count = ids_to_use.len(), "candidates found");
+    send(SearchEvent::CandidatesFound(ids_to_use.len()));
+
+    let mut lookups = stream::iter(ids_to_use)
+        .map(|id| {
+            let provider = Arc::clone(&provider);
+            async move {
+                let meal = provider.lookup(&id).await;
+                (id, meal)
+            }
+        })
+        .buffer_unordered(MAX_CONCURRENT_LOOKUPS);
+
+    loop {
+        // Dropping the stream on cancel drops every lookup still in flight
+        let Some(next) = cancel.run_until_cancelled(lookups.next()).await else {
+            send(SearchEvent::Cancelled);
+            return;
+        };
+        let Some((id, lookup)) = next else {
+            break;
+        };
+        match lookup {
+            Ok(Some(mut meal)) if query.matches_filters(&meal) => {
+                meal.score = query.score(&meal);
+                send(SearchEvent::MealLoaded(Box::new(meal)));
+            }
+            Ok(_) => send(SearchEvent::MealSkipped),
+            Err(e) => {
+                tracing::warn!(id = id.as_str(), "lookup failed: {}", e);
+                send(SearchEvent::Error(tr!("meal-failed", id = id.as_str(), error = i18n::fetch_error(&e))));
+            }
+        }
+    }
+    tracing::info!("search finished");
+    send(SearchEvent::Done);
+}
+
+/// Random pick, reported through the same events as a search.
+pub async fn run_random(
+    provider: Arc<dyn RecipeProvider>,
+    events: Sender<SearchEvent>,
+    cancel: CancellationToken,
+    ctx: egui::Context,
+) {
+    let send = |event| {
+        let _ = events.send(event);
+        ctx.request_repaint();
+    };
+
+    send(SearchEvent::CandidatesFound(1));
+    let Some(meal) = cancel.run_until_cancelled(provider.random()).await else {
+        send(SearchEvent::Cancelled);
+        return;
+    };
+    match meal {
+        Ok(Some(meal)) => send(SearchEvent::MealLoaded(Box::new(meal))),
+        Ok(None) => send(SearchEvent::MealSkipped),
+        Err(e) => send(SearchEvent::Error(tr!("random-failed", error = i18n::fetch_error(&e)))),
+    }
+    send(SearchEvent::Done);
+}
+
+/// Loads the meals the provider added most recently.
+pub async fn run_latest(
+    provider: Arc<dyn RecipeProvider>,
+    events: Sender<SearchEvent>,
+    cancel: CancellationToken,
+    ctx: egui::Context,
+) {
+    let send = |event| {
+        let _ = events.send(event);
+        ctx.request_repaint();
+    };
+
+    let Some(meals) = cancel.run_until_cancelled(provider.latest()).await else {
+        send(SearchEvent::Cancelled);
+        return;
+    };
+    match meals {
+        Ok(meals) => {
+            send(SearchEvent::CandidatesFound(meals.len()));
+            for meal in meals {
+                send(SearchEvent::MealLoaded(Box::new(meal)));
+            }
+        }
+        Err(e) => send(SearchEvent::Error(tr!("latest-failed", error = i18n::fetch_error(&e)))),
+    }
+    send(SearchEvent::Done);
+}
+
+/// Counters behind the progress bar, reset for every search.
+#[derive(Debug, Default)]
+pub struct SearchProgress {
+    /// Unknown until the candidate list is in.
+    pub total: Option<usize>,
+    /// Candidates looked up so far, whether they were kept or not.
+    pub processed: usize,
+    pub errors: Vec<String>,
+    /// Failed and slow request attempts, retries included.
+    pub requests: Vec<RequestRecord>,
+    pub done: bool,
+    pub cancelled: bool,
+}
+
+impl SearchProgress {
+    pub fn record(&mut self, event: &SearchEvent) {
+        match event {
+            SearchEvent::CandidatesFound(n) => self.total = Some(*n),
+            SearchEvent::MealLoaded(_) | SearchEvent::MealSkipped => self.processed += 1,
+            SearchEvent::Error(e) => {
+                // Errors before the candidate list is known aren't lookups
+                if self.total.is_some() {
+                    self.processed += 1;
+                }
+                self.errors.push(e.clone());
+            }
+            SearchEvent::Cancelled => {
+                self.cancelled = true;
+                self.done = true;
+            }
+            SearchEvent::Done => self.done = true,
+        }
+    }
+
+    pub fn slow_requests(&self) -> usize {
+        self.requests.iter().filter(|r| r.error.is_none()).count()
+    }
+
+    pub fn fraction(&self) -> f32 {
+        match self.total {
+            Some(0) => 1.0,
+            Some(total) => self.processed as f32 / total as f32,
+            None => 0.0,
+        }
+    }
+}
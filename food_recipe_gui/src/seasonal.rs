@@ -0,0 +1,108 @@
+// Which produce is in season when, for the suggestions strip and the optional
+// seasonal boost in scoring. The table is for a temperate northern climate;
+// the southern hemisphere uses the same table six months on.
+
+use chrono::Datelike;
+use recipe_core::matching::ingredient_matches;
+use recipe_core::MealDetail;
+use serde::{Deserialize, Serialize};
+
+/// Produce with the first and last month (1–12) of its season. Seasons
+/// running over the new year have `from` after `to`.
+const SEASONS: &[(&str, u32, u32)] = &[
+    ("leek", 9, 3),
+    ("squash", 9, 12),
+    ("pumpkin", 9, 11),
+    ("kale", 10, 3),
+    ("brussels sprouts", 10, 2),
+    ("parsnip", 10, 3),
+    ("celeriac", 10, 3),
+    ("red cabbage", 9, 2),
+    ("cauliflower", 7, 3),
+    ("beetroot", 7, 1),
+    ("swede", 10, 3),
+    ("apple", 8, 12),
+    ("pear", 8, 12),
+    ("rhubarb", 3, 6),
+    ("asparagus", 4, 6),
+    ("wild garlic", 3, 5),
+    ("spring onion", 4, 9),
+    ("radish", 4, 9),
+    ("peas", 6, 9),
+    ("broad beans", 6, 9),
+    ("new potatoes", 5, 7),
+    ("strawberries", 6, 8),
+    ("raspberries", 6, 9),
+    ("cherries", 6, 8),
+    ("courgette", 6, 9),
+    ("tomato", 7, 9),
+    ("cucumber", 6, 9),
+    ("green beans", 7, 9),
+    ("sweetcorn", 8, 9),
+    ("aubergine", 7, 9),
+    ("peppers", 7, 9),
+    ("plums", 8, 9),
+    ("blackberries", 8, 10),
+    ("mushrooms", 9, 11),
+    ("chestnuts", 10, 12),
+    ("blood orange", 1, 3),
+    ("carrots", 6, 2),
+    ("spinach", 4, 10),
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Region {
+    #[default]
+    NorthernHemisphere,
+    SouthernHemisphere,
+}
+
+impl Region {
+    pub const ALL: [Region; 2] = [Region::NorthernHemisphere, Region::SouthernHemisphere];
+
+    pub fn label(self) -> String {
+        match self {
+            Region::NorthernHemisphere => tr!("region-northern"),
+            Region::SouthernHemisphere => tr!("region-southern"),
+        }
+    }
+
+    // The month in the northern table that matches `month` here
+    fn table_month(self, month: u32) -> u32 {
+        match self {
+            Region::NorthernHemisphere => month,
+            Region::SouthernHemisphere => (month + 5) % 12 + 1,
+        }
+    }
+}
+
+fn in_range(month: u32, from: u32, to: u32) -> bool {
+    if from <= to {
+        (from..=to).contains(&month)
+    } else {
+        month >= from || month <= to
+    }
+}
+
+/// Produce in season in `month` (1–12), in table order.
+pub fn in_season(region: Region, month: u32) -> Vec<String> {
+    let month = region.table_month(month);
+    SEASONS
+        .iter()
+        .filter(|(_, from, to)| in_range(month, *from, *to))
+        .map(|(name, _, _)| name.to_string())
+        .collect()
+}
+
+/// Produce in season today.
+pub fn in_season_now(region: Region) -> Vec<String> {
+    in_season(region, chrono::Local::now().month())
+}
+
+/// How many of the meal's ingredients are among `produce`.
+pub fn seasonal_count(meal: &MealDetail, produce: &[String]) -> usize {
+    meal.ingredients
+        .iter()
+        .filter(|ing| produce.iter().any(|p| ingredient_matches(ing, p)))
+        .count()
+}
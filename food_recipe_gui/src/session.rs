@@ -0,0 +1,40 @@
+use crate::diet::Diet;
+use crate::difficulty::Difficulty;
+use crate::quota::DailyCount;
+use crate::search::SortOrder;
+use crate::theme::ThemeConfig;
+use serde::{Deserialize, Serialize};
+
+/// Key the session is stored under in eframe's app storage.
+pub const STORAGE_KEY: &str = "session";
+
+/// What the window looked like at the last exit, put back on the next launch.
+/// eframe restores the window size and position itself.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    pub name_query: String,
+    pub taste: String,
+    pub main_ingredients: String,
+    pub sub_ingredients: String,
+    pub exclude_ingredients: String,
+    pub category: Option<String>,
+    pub area: Option<String>,
+    pub diet: Option<Diet>,
+    pub max_cost: Option<f64>,
+    pub max_calories: Option<f64>,
+    pub max_ingredients: Option<usize>,
+    pub max_minutes: Option<u64>,
+    pub max_difficulty: Option<Difficulty>,
+    pub sort_order: SortOrder,
+    pub grid_view: bool,
+    pub seasonal_boost: bool,
+    pub leftover_boost: bool,
+    /// Also covers changes that were never saved from the settings window.
+    pub theme: ThemeConfig,
+    /// Recipe open in the detail view; found again in the recently viewed list.
+    pub selected_id: Option<String>,
+    /// Profile in use; the startup picker still asks when there are several.
+    pub profile: Option<String>,
+    pub requests: DailyCount,
+}
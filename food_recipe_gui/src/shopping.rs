@@ -0,0 +1,233 @@
+use crate::aisles::Aisle;
+use crate::cost::{self, Price};
+use recipe_core::quantity::Quantity;
+use recipe_core::MealDetail;
+use std::{
+    fs::File,
+    io::{self, Write},
+};
+
+pub const SHOPPING_LIST_FILE: &str = "shopping_list.txt";
+
+pub struct ShoppingItem {
+    pub ingredient: String,
+    /// Non-empty measures from every recipe that needs this ingredient,
+    /// added up where the units allow: "200g" and "300g" are one "500g".
+    pub measures: Vec<String>,
+    pub recipes: Vec<String>,
+}
+
+impl ShoppingItem {
+    // Into the first measure it adds up with, or listed alongside
+    fn add_measure(&mut self, measure: &str) {
+        let quantity = Quantity::parse(measure);
+        for existing in &mut self.measures {
+            if let Some(sum) = Quantity::parse(existing).checked_add(&quantity) {
+                *existing = sum.to_string();
+                return;
+            }
+        }
+        self.measures.push(measure.to_string());
+    }
+}
+
+/// Combines the ingredients of all given recipes, merging case-insensitive
+/// duplicates and summing their amounts.
+pub fn build<'a>(meals: impl IntoIterator<Item = &'a MealDetail>) -> Vec<ShoppingItem> {
+    let mut items: Vec<ShoppingItem> = Vec::new();
+    for meal in meals {
+        for (i, ing) in meal.ingredients.iter().enumerate() {
+            let measure = meal.measures.get(i).map(|m| m.trim()).unwrap_or("");
+            let item = match items
+                .iter_mut()
+                .position(|it| it.ingredient.eq_ignore_ascii_case(ing))
+            {
+                Some(pos) => &mut items[pos],
+                None => {
+                    items.push(ShoppingItem {
+                        ingredient: ing.clone(),
+                        measures: Vec::new(),
+                        recipes: Vec::new(),
+                    });
+                    items.last_mut().unwrap()
+                }
+            };
+            if !measure.is_empty() {
+                item.add_measure(measure);
+            }
+            if !item.recipes.contains(&meal.title) {
+                item.recipes.push(meal.title.clone());
+            }
+        }
+    }
+    items.sort_by_key(|it| it.ingredient.to_lowercase());
+    items
+}
+
+/// One aisle's part of the list, in store order.
+pub struct AisleSection<'a> {
+    pub aisle: Aisle,
+    pub items: Vec<&'a ShoppingItem>,
+    /// Estimated cost of the priced items.
+    pub subtotal: f64,
+    /// Items without a price, left out of `subtotal`.
+    pub unpriced: usize,
+}
+
+/// Groups the items by aisle, keeping them alphabetical within each one.
+pub fn by_aisle<'a>(items: &'a [ShoppingItem], prices: &[Price]) -> Vec<AisleSection<'a>> {
+    let mut sections: Vec<AisleSection> = Vec::new();
+    for item in items {
+        let aisle = Aisle::of(&item.ingredient);
+        let section = match sections.iter().position(|s| s.aisle == aisle) {
+            Some(pos) => &mut sections[pos],
+            None => {
+                sections.push(AisleSection {
+                    aisle,
+                    items: Vec::new(),
+                    subtotal: 0.0,
+                    unpriced: 0,
+                });
+                sections.last_mut().unwrap()
+            }
+        };
+        match cost::item_cost(&item.ingredient, &item.measures, prices) {
+            Some(cost) => section.subtotal += cost,
+            None => section.unpriced += 1,
+        }
+        section.items.push(item);
+    }
+    sections.sort_by_key(|s| s.aisle);
+    sections
+}
+
+/// "Produce (5) – about €3.20"
+pub fn section_heading(section: &AisleSection, currency: &str) -> String {
+    tr!(
+        "aisle-heading",
+        aisle = section.aisle.label(),
+        count = section.items.len(),
+        subtotal = cost::format_cost(section.subtotal, currency)
+    )
+}
+
+pub fn item_line(item: &ShoppingItem) -> String {
+    if item.measures.is_empty() {
+        item.ingredient.clone()
+    } else {
+        format!("{} ({})", item.ingredient, item.measures.join(" + "))
+    }
+}
+
+pub fn export(path: &str, items: &[ShoppingItem], prices: &[Price], currency: &str) -> io::Result<()> {
+    let mut f = File::create(path)?;
+    writeln!(f, "Shopping list")?;
+    for section in by_aisle(items, prices) {
+        writeln!(f)?;
+        writeln!(f, "{}", section_heading(&section, currency))?;
+        for item in section.items {
+            writeln!(f, "[ ] {}", item_line(item))?;
+        }
+    }
+    f.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cost::PriceUnit;
+
+    fn item(ingredient: &str, measures: &[&str]) -> ShoppingItem {
+        ShoppingItem {
+            ingredient: ingredient.to_string(),
+            measures: measures.iter().map(|m| m.to_string()).collect(),
+            recipes: Vec::new(),
+        }
+    }
+
+    fn price(ingredient: &str, unit: PriceUnit, amount: f64) -> Price {
+        Price {
+            ingredient: ingredient.to_string(),
+            unit,
+            amount,
+        }
+    }
+
+    fn meal(title: &str, ingredients: &[(&str, &str)]) -> MealDetail {
+        MealDetail {
+            title: title.to_string(),
+            ingredients: ingredients.iter().map(|(name, _)| name.to_string()).collect(),
+            measures: ingredients.iter().map(|(_, measure)| measure.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn sums_matching_measures() {
+        let bread = meal("Bread", &[("Flour", "200g")]);
+        let cake = meal("Cake", &[("Flour", "300g")]);
+        let items = build([&bread, &cake]);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].measures, ["500g"]);
+        assert_eq!(items[0].recipes, ["Bread", "Cake"]);
+    }
+
+    #[test]
+    fn merges_names_case_insensitively() {
+        let a = meal("A", &[("Garlic", "2 cloves"), ("onion", "1")]);
+        let b = meal("B", &[("garlic", "1 clove"), ("Butter", "50g")]);
+        let items = build([&a, &b]);
+        let names: Vec<&str> = items.iter().map(|i| i.ingredient.as_str()).collect();
+        assert_eq!(names, ["Butter", "Garlic", "onion"]);
+        assert_eq!(items[1].measures, ["3 cloves"]);
+    }
+
+    #[test]
+    fn lists_measures_that_do_not_add_up_separately() {
+        let a = meal("A", &[("Milk", "200 ml"), ("Sugar", "1 cup")]);
+        let b = meal("B", &[("Milk", "1 tbsp"), ("Sugar", "100g"), ("Milk", "300 ml")]);
+        let items = build([&a, &b]);
+        assert_eq!(items[0].measures, ["500 ml", "1 tbsp"]);
+        assert_eq!(items[1].measures, ["1 cup", "100g"]);
+    }
+
+    #[test]
+    fn skips_empty_measures() {
+        let a = meal("A", &[("Salt", ""), ("Pepper", "  ")]);
+        let b = meal("B", &[("Salt", "1 tsp")]);
+        let items = build([&a, &b]);
+        assert!(items[0].measures.is_empty());
+        assert_eq!(items[1].measures, ["1 tsp"]);
+        assert_eq!(items[1].recipes, ["A", "B"]);
+    }
+
+    #[test]
+    fn groups_by_aisle_with_subtotals() {
+        let items = [
+            item("Butter", &["250g"]),
+            item("Flour", &["500g"]),
+            item("Lemon", &["2"]),
+            item("Milk", &["1 l"]),
+            item("Saffron", &["pinch"]),
+        ];
+        let prices = [
+            price("butter", PriceUnit::Kilogram, 8.0),
+            price("milk", PriceUnit::Kilogram, 1.0),
+            price("flour", PriceUnit::Kilogram, 0.8),
+            price("lemon", PriceUnit::Piece, 0.5),
+        ];
+        let sections = by_aisle(&items, &prices);
+        let aisles: Vec<Aisle> = sections.iter().map(|s| s.aisle).collect();
+        assert_eq!(aisles, [Aisle::Produce, Aisle::Dairy, Aisle::Pantry, Aisle::Other]);
+
+        let dairy = &sections[1];
+        let names: Vec<&str> = dairy.items.iter().map(|i| i.ingredient.as_str()).collect();
+        assert_eq!(names, ["Butter", "Milk"]);
+        assert!((dairy.subtotal - (0.25 * 8.0 + 1.0)).abs() < 1e-9);
+        assert_eq!(dairy.unpriced, 0);
+        assert!((sections[0].subtotal - 1.0).abs() < 1e-9);
+        assert_eq!(sections[3].subtotal, 0.0);
+        assert_eq!(sections[3].unpriced, 1);
+    }
+}
@@ -0,0 +1,117 @@
+// Reads a recipe's instructions aloud, one step per utterance, through the
+// system's speech engine. Steps follow on from each other by themselves;
+// pausing stops mid-step and resuming starts that step over, since not
+// every engine can pick up where it left off.
+
+use crate::steps;
+use recipe_core::MealDetail;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tts::Tts;
+
+pub struct ReadAloud {
+    tts: Tts,
+    /// Meal whose instructions are being read.
+    pub meal_id: String,
+    steps: Vec<String>,
+    /// Zero-based step being read, or next to be read when paused.
+    pub current: usize,
+    pub paused: bool,
+    /// Set by the engine's callback when the current step has been spoken.
+    /// Interrupted steps don't count; engines report those separately.
+    ended: Arc<AtomicBool>,
+}
+
+impl ReadAloud {
+    /// Starts reading `meal` from its first step. `repaint` is called from the
+    /// engine's thread when a step has been spoken so the next one follows.
+    pub fn start(meal: &MealDetail, repaint: impl Fn() + Send + 'static) -> Result<Self, tts::Error> {
+        let tts = Tts::default()?;
+        let ended = Arc::new(AtomicBool::new(false));
+        let shared = Arc::clone(&ended);
+        if tts.supported_features().utterance_callbacks {
+            tts.on_utterance_end(Some(Box::new(move |_| {
+                shared.store(true, Ordering::Relaxed);
+                repaint();
+            })))?;
+        }
+        let steps = steps::parse_steps(&meal.instructions)
+            .into_iter()
+            .enumerate()
+            .map(|(i, step)| tr!("speech-step", step = i + 1, text = step.text))
+            .collect();
+        let mut reader = Self {
+            tts,
+            meal_id: meal.id.clone(),
+            steps,
+            current: 0,
+            paused: false,
+            ended,
+        };
+        reader.speak()?;
+        Ok(reader)
+    }
+
+    pub fn step_count(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// False once the last step has been spoken.
+    pub fn is_active(&self) -> bool {
+        self.current < self.steps.len()
+    }
+
+    fn speak(&mut self) -> Result<(), tts::Error> {
+        self.ended.store(false, Ordering::Relaxed);
+        if let Some(text) = self.steps.get(self.current) {
+            self.tts.speak(text.as_str(), true)?;
+        }
+        Ok(())
+    }
+
+    /// Moves on to the next step once the engine has finished the current one.
+    /// Call every frame while reading. Engines without utterance callbacks
+    /// don't move on by themselves; `skip` does.
+    pub fn poll(&mut self) -> Result<(), tts::Error> {
+        if self.paused || !self.is_active() {
+            return Ok(());
+        }
+        if self.ended.swap(false, Ordering::Relaxed) {
+            self.current += 1;
+            self.speak()?;
+        }
+        Ok(())
+    }
+
+    pub fn pause(&mut self) -> Result<(), tts::Error> {
+        self.paused = true;
+        self.tts.stop()?;
+        Ok(())
+    }
+
+    pub fn resume(&mut self) -> Result<(), tts::Error> {
+        self.paused = false;
+        self.speak()
+    }
+
+    /// Jumps to the next step, or back to the previous one, and reads it
+    /// unless paused.
+    pub fn skip(&mut self, forward: bool) -> Result<(), tts::Error> {
+        self.current = if forward {
+            (self.current + 1).min(self.steps.len())
+        } else {
+            self.current.saturating_sub(1)
+        };
+        if self.paused {
+            return Ok(());
+        }
+        self.tts.stop()?;
+        self.speak()
+    }
+
+    pub fn stop(&mut self) -> Result<(), tts::Error> {
+        self.current = self.steps.len();
+        self.tts.stop()?;
+        Ok(())
+    }
+}
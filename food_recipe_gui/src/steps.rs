@@ -0,0 +1,172 @@
+// Turns a recipe's instruction text into separate steps. Providers format
+// instructions every which way: one paragraph, one step per line, "STEP 1"
+// headings, "1." prefixes. All of those end up as one clean step per action.
+
+use std::time::Duration;
+
+/// One instruction step, without any numbering from the source text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    pub text: String,
+    /// How long the step says to wait, e.g. "simmer for 20 minutes".
+    pub duration: Option<Duration>,
+}
+
+/// Allowed for a step that doesn't say how long it takes: chopping, mixing, plating.
+const UNTIMED_STEP: Duration = Duration::from_secs(3 * 60);
+
+fn unit_seconds(word: &str) -> Option<u64> {
+    let word = word.trim_end_matches(|c: char| !c.is_alphabetic()).to_lowercase();
+    match word.as_str() {
+        "sec" | "secs" | "second" | "seconds" => Some(1),
+        "min" | "mins" | "minute" | "minutes" => Some(60),
+        "hr" | "hrs" | "hour" | "hours" => Some(60 * 60),
+        _ => None,
+    }
+}
+
+// "20 minutes", "1 hour 30 mins", "10-15 min" (the longer end, so nothing comes
+// out underdone); a step with several times gets their sum
+fn find_duration(text: &str) -> Option<Duration> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let mut total = 0;
+    for pair in words.windows(2) {
+        let Some(seconds) = unit_seconds(pair[1]) else {
+            continue;
+        };
+        let amount = pair[0]
+            .split(['-', '–'])
+            .filter_map(|n| n.parse::<u64>().ok())
+            .max();
+        if let Some(amount) = amount {
+            total += amount * seconds;
+        }
+    }
+    (total > 0).then(|| Duration::from_secs(total))
+}
+
+// "STEP 3", "Step 3:", "3." and "3)" are numbering, not content
+fn strip_numbering(line: &str) -> &str {
+    let mut rest = line.trim();
+    if rest.get(..4).is_some_and(|w| w.eq_ignore_ascii_case("step"))
+        && rest[4..].starts_with(|c: char| c.is_whitespace() || c.is_ascii_digit())
+    {
+        rest = rest[4..].trim_start();
+    }
+    let digits = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 {
+        let after = &rest[digits..];
+        match after.chars().next() {
+            None => return "",
+            Some('.' | ')' | ':' | '-') => rest = after[1..].trim_start(),
+            Some(c) if c.is_whitespace() => rest = after.trim_start(),
+            _ => {}
+        }
+    }
+    rest.trim_start_matches([':', '-']).trim()
+}
+
+// Splits after ". ", "! " or "? " when the next sentence starts with a capital,
+// so "approx. 5 minutes" stays in one piece
+fn sentences(text: &str) -> Vec<&str> {
+    let mut out = Vec::new();
+    let mut start = 0;
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    for i in 0..chars.len() {
+        let (pos, c) = chars[i];
+        if !matches!(c, '.' | '!' | '?') {
+            continue;
+        }
+        let followed_by_space = chars.get(i + 1).is_some_and(|(_, n)| n.is_whitespace());
+        let next_word = chars[i + 1..].iter().find(|(_, n)| !n.is_whitespace());
+        if followed_by_space && next_word.is_some_and(|(_, n)| n.is_uppercase()) {
+            out.push(text[start..pos + c.len_utf8()].trim());
+            start = pos + c.len_utf8();
+        }
+    }
+    out.push(text[start..].trim());
+    out.retain(|s| !s.is_empty());
+    out
+}
+
+pub fn parse_steps(instructions: &str) -> Vec<Step> {
+    instructions
+        .lines()
+        .map(strip_numbering)
+        .filter(|line| !line.is_empty())
+        .flat_map(sentences)
+        .map(|text| Step {
+            text: text.to_string(),
+            duration: find_duration(text),
+        })
+        .collect()
+}
+
+/// Rough total time: every wait the steps state plus a few minutes for each
+/// step that doesn't state one. `None` without instructions.
+pub fn estimated_time(instructions: &str) -> Option<Duration> {
+    let steps = parse_steps(instructions);
+    if steps.is_empty() {
+        return None;
+    }
+    Some(steps.iter().map(|step| step.duration.unwrap_or(UNTIMED_STEP)).sum())
+}
+
+/// `estimated_time` in whole minutes, rounded up to the next five.
+pub fn estimated_minutes(instructions: &str) -> Option<u64> {
+    estimated_time(instructions).map(|time| time.as_secs().div_ceil(5 * 60) * 5)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(instructions: &str) -> Vec<String> {
+        parse_steps(instructions).into_iter().map(|step| step.text).collect()
+    }
+
+    #[test]
+    fn strips_numbering() {
+        assert_eq!(strip_numbering("STEP 3"), "");
+        assert_eq!(strip_numbering("Step 3: Stir well."), "Stir well.");
+        assert_eq!(strip_numbering("3) Stir well."), "Stir well.");
+        assert_eq!(strip_numbering("3. Stir well."), "Stir well.");
+        assert_eq!(strip_numbering("Stepped pyramid cake"), "Stepped pyramid cake");
+    }
+
+    #[test]
+    fn splits_sentences_only_before_capitals() {
+        assert_eq!(sentences("Bake for approx. 5 minutes. Serve hot."), ["Bake for approx. 5 minutes.", "Serve hot."]);
+        assert_eq!(sentences("Done! Enjoy?"), ["Done!", "Enjoy?"]);
+        assert!(sentences("   ").is_empty());
+    }
+
+    #[test]
+    fn finds_durations() {
+        assert_eq!(find_duration("Simmer for 20 minutes."), Some(Duration::from_secs(20 * 60)));
+        assert_eq!(find_duration("Bake 10-15 min until golden"), Some(Duration::from_secs(15 * 60)));
+        assert_eq!(find_duration("Roast for 1 hour 30 mins."), Some(Duration::from_secs(90 * 60)));
+        assert_eq!(find_duration("Cook for approx. 5 minutes"), Some(Duration::from_secs(5 * 60)));
+        assert_eq!(find_duration("Season to taste."), None);
+    }
+
+    #[test]
+    fn parses_headed_and_numbered_steps() {
+        let instructions = "STEP 1\r\nHeat the oil.\r\nSTEP 2\r\nFry the onion for approx. 5 minutes. Add the garlic.\n3) Simmer 10-15 min.";
+        assert_eq!(
+            texts(instructions),
+            ["Heat the oil.", "Fry the onion for approx. 5 minutes.", "Add the garlic.", "Simmer 10-15 min."]
+        );
+        let steps = parse_steps(instructions);
+        assert_eq!(steps[0].duration, None);
+        assert_eq!(steps[1].duration, Some(Duration::from_secs(5 * 60)));
+        assert_eq!(steps[3].duration, Some(Duration::from_secs(15 * 60)));
+    }
+
+    #[test]
+    fn estimates_total_time() {
+        // 90 minutes stated plus one untimed step
+        assert_eq!(estimated_minutes("Roast for 1 hour 30 mins.\nRest and carve."), Some(95));
+        assert_eq!(estimated_minutes(""), None);
+    }
+}
@@ -0,0 +1,89 @@
+use recipe_core::MealDetail;
+use recipe_core::matching::normalize;
+use std::collections::{HashMap, HashSet};
+
+// A hit in the title says more than one buried in the instructions
+const TITLE_WEIGHT: i32 = 5;
+const INGREDIENT_WEIGHT: i32 = 3;
+const TAG_WEIGHT: i32 = 2;
+const INSTRUCTIONS_WEIGHT: i32 = 1;
+
+fn tokens(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.chars().count() >= 2)
+        .map(normalize)
+}
+
+/// Inverted index over recipes we already have on disk, for searching
+/// without the network.
+#[derive(Default)]
+pub struct TextIndex {
+    docs: Vec<MealDetail>,
+    /// Normalized word -> (document, summed field weight).
+    postings: HashMap<String, HashMap<usize, i32>>,
+}
+
+impl TextIndex {
+    /// Builds the index; a recipe appearing twice (same ID) is indexed once.
+    pub fn build(meals: impl IntoIterator<Item = MealDetail>) -> Self {
+        let mut index = Self::default();
+        let mut seen = HashSet::new();
+        for meal in meals {
+            if seen.insert(meal.id.clone()) {
+                index.add(meal);
+            }
+        }
+        index
+    }
+
+    fn add(&mut self, meal: MealDetail) {
+        let doc = self.docs.len();
+        let mut post = |text: &str, weight: i32| {
+            for word in tokens(text) {
+                *self.postings.entry(word).or_default().entry(doc).or_default() += weight;
+            }
+        };
+        post(&meal.title, TITLE_WEIGHT);
+        for ing in &meal.ingredients {
+            post(ing, INGREDIENT_WEIGHT);
+        }
+        for tag in &meal.tags {
+            post(tag, TAG_WEIGHT);
+        }
+        post(&meal.instructions, INSTRUCTIONS_WEIGHT);
+        self.docs.push(meal);
+    }
+
+    pub fn len(&self) -> usize {
+        self.docs.len()
+    }
+
+    /// Recipes containing every word of `query`, best first, with `score` set
+    /// to the summed weight of the matches.
+    pub fn search(&self, query: &str) -> Vec<MealDetail> {
+        let words: Vec<String> = tokens(query).collect();
+        let Some((first, rest)) = words.split_first() else {
+            return Vec::new();
+        };
+        let Some(first_hits) = self.postings.get(first) else {
+            return Vec::new();
+        };
+        let mut scores: HashMap<usize, i32> = first_hits.clone();
+        for word in rest {
+            let hits = self.postings.get(word);
+            scores = scores
+                .into_iter()
+                .filter_map(|(doc, score)| Some((doc, score + hits?.get(&doc)?)))
+                .collect();
+        }
+        let mut found: Vec<MealDetail> = scores
+            .into_iter()
+            .map(|(doc, score)| MealDetail {
+                score,
+                ..self.docs[doc].clone()
+            })
+            .collect();
+        found.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.title.cmp(&b.title)));
+        found
+    }
+}
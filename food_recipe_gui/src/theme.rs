@@ -0,0 +1,92 @@
+use eframe::egui::{self, style::Spacing, Color32, Stroke, Visuals};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ThemeMode {
+    #[default]
+    Dark,
+    Light,
+}
+
+/// Look of the app, edited in the settings window and saved with the config.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeConfig {
+    pub mode: ThemeMode,
+    /// Selection and link colour, as sRGB.
+    pub accent: [u8; 3],
+    /// Multiplier on egui's default gaps between widgets.
+    pub spacing: f32,
+    /// Multiplier on every font size, for reading from across the kitchen.
+    pub text_scale: f32,
+    /// Pure black/white backgrounds with maximum-contrast text and outlines.
+    pub high_contrast: bool,
+}
+
+impl Default for ThemeConfig {
+    fn default() -> Self {
+        Self {
+            mode: ThemeMode::Dark,
+            accent: [0, 92, 128],
+            spacing: 1.0,
+            text_scale: 1.0,
+            high_contrast: false,
+        }
+    }
+}
+
+impl ThemeConfig {
+    pub fn apply(&self, ctx: &egui::Context) {
+        let mut visuals = match self.mode {
+            ThemeMode::Dark => Visuals::dark(),
+            ThemeMode::Light => Visuals::light(),
+        };
+        let [r, g, b] = self.accent;
+        let accent = Color32::from_rgb(r, g, b);
+        visuals.selection.bg_fill = accent;
+        visuals.hyperlink_color = accent;
+        if self.high_contrast {
+            high_contrast(&mut visuals, self.mode);
+        }
+        ctx.set_visuals(visuals);
+
+        let defaults = Spacing::default();
+        let default_text = egui::Style::default().text_styles;
+        ctx.style_mut(|style| {
+            for (text_style, font) in style.text_styles.iter_mut() {
+                if let Some(default) = default_text.get(text_style) {
+                    font.size = default.size * self.text_scale;
+                }
+            }
+            style.spacing.item_spacing = defaults.item_spacing * self.spacing;
+            style.spacing.button_padding = defaults.button_padding * self.spacing;
+            style.spacing.window_margin = defaults.window_margin * self.spacing;
+        });
+    }
+}
+
+fn high_contrast(visuals: &mut Visuals, mode: ThemeMode) {
+    let (fg, bg) = match mode {
+        ThemeMode::Dark => (Color32::WHITE, Color32::BLACK),
+        ThemeMode::Light => (Color32::BLACK, Color32::WHITE),
+    };
+    visuals.override_text_color = Some(fg);
+    visuals.panel_fill = bg;
+    visuals.window_fill = bg;
+    visuals.extreme_bg_color = bg;
+    visuals.faint_bg_color = bg;
+    visuals.window_stroke = Stroke::new(2.0, fg);
+    for widget in [
+        &mut visuals.widgets.noninteractive,
+        &mut visuals.widgets.inactive,
+        &mut visuals.widgets.hovered,
+        &mut visuals.widgets.active,
+        &mut visuals.widgets.open,
+    ] {
+        widget.fg_stroke = Stroke::new(widget.fg_stroke.width.max(1.5), fg);
+        widget.bg_stroke = Stroke::new(widget.bg_stroke.width.max(1.0), fg);
+    }
+    visuals.widgets.noninteractive.bg_fill = bg;
+    visuals.widgets.inactive.bg_fill = bg;
+    visuals.widgets.inactive.weak_bg_fill = bg;
+}
@@ -0,0 +1,38 @@
+use std::time::{Duration, Instant};
+
+/// Countdown started from an instruction step.
+pub struct StepTimer {
+    pub label: String,
+    ends_at: Instant,
+    /// Set once the user has seen the "done" alert.
+    pub dismissed: bool,
+}
+
+impl StepTimer {
+    pub fn start(label: impl Into<String>, duration: Duration) -> Self {
+        Self {
+            label: label.into(),
+            ends_at: Instant::now() + duration,
+            dismissed: false,
+        }
+    }
+
+    pub fn remaining(&self) -> Duration {
+        self.ends_at.saturating_duration_since(Instant::now())
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.remaining().is_zero()
+    }
+}
+
+/// "1:05:00" or "4:30".
+pub fn format_duration(d: Duration) -> String {
+    let secs = d.as_secs();
+    let (h, m, s) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if h > 0 {
+        format!("{}:{:02}:{:02}", h, m, s)
+    } else {
+        format!("{}:{:02}", m, s)
+    }
+}
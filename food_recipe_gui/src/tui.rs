@@ -0,0 +1,366 @@
+// `--tui`: the recipe finder in a terminal, for SSH sessions and machines
+// without a desktop. Same config, database, providers and scoring as the GUI;
+// only the ingredient search is offered.
+
+use crate::config::{AppConfig, CONFIG_FILE};
+use crate::headless::{add_saved_inputs, own_recipes, split_list};
+use crate::search::{self, SearchEvent, SearchProgress, SearchQuery};
+use crate::{build_providers, i18n, measure, open_database, steps};
+use eframe::egui;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Position, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use recipe_core::db::{Database, DEFAULT_PROFILE};
+use recipe_core::provider::RecipeProvider;
+use recipe_core::MealDetail;
+use std::io;
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::runtime::Runtime;
+use tokio_util::sync::CancellationToken;
+
+pub const FLAG: &str = "--tui";
+
+/// How long to wait for a key before checking on the running search.
+const TICK: Duration = Duration::from_millis(100);
+/// Lines moved by PageUp/PageDown in the detail pane.
+const PAGE_LINES: u16 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Focus {
+    Main,
+    Sub,
+    Taste,
+    Results,
+    Detail,
+}
+
+impl Focus {
+    const ORDER: [Focus; 5] = [Focus::Main, Focus::Sub, Focus::Taste, Focus::Results, Focus::Detail];
+
+    fn step(self, by: isize) -> Focus {
+        let i = Self::ORDER.iter().position(|f| *f == self).unwrap() as isize;
+        Self::ORDER[(i + by).rem_euclid(Self::ORDER.len() as isize) as usize]
+    }
+
+    fn is_text(self) -> bool {
+        matches!(self, Focus::Main | Focus::Sub | Focus::Taste)
+    }
+}
+
+struct Tui {
+    config: AppConfig,
+    db: Arc<Database>,
+    provider: Arc<dyn RecipeProvider>,
+    runtime: Runtime,
+    main_ingredients: String,
+    sub_ingredients: String,
+    taste: String,
+    focus: Focus,
+    /// Results of the current search, best first.
+    results: Vec<MealDetail>,
+    list: ListState,
+    detail_scroll: u16,
+    query: SearchQuery,
+    search_events: Option<Receiver<SearchEvent>>,
+    search_cancel: CancellationToken,
+    progress: SearchProgress,
+    quit: bool,
+}
+
+impl Tui {
+    fn new() -> Self {
+        let config = AppConfig::load(CONFIG_FILE).unwrap_or_default();
+        i18n::set_language(config.language);
+        let db = open_database(&config);
+        let provider = Arc::clone(&build_providers(&config, &db)[0]);
+        Self {
+            config,
+            db,
+            provider,
+            runtime: Runtime::new().expect("failed to start tokio runtime"),
+            main_ingredients: String::new(),
+            sub_ingredients: String::new(),
+            taste: String::new(),
+            focus: Focus::Main,
+            results: Vec::new(),
+            list: ListState::default(),
+            detail_scroll: 0,
+            query: SearchQuery::default(),
+            search_events: None,
+            search_cancel: CancellationToken::new(),
+            progress: SearchProgress::default(),
+            quit: false,
+        }
+    }
+
+    fn field(&mut self, focus: Focus) -> Option<&mut String> {
+        match focus {
+            Focus::Main => Some(&mut self.main_ingredients),
+            Focus::Sub => Some(&mut self.sub_ingredients),
+            Focus::Taste => Some(&mut self.taste),
+            Focus::Results | Focus::Detail => None,
+        }
+    }
+
+    fn selected(&self) -> Option<&MealDetail> {
+        self.list.selected().and_then(|i| self.results.get(i))
+    }
+
+    fn search(&mut self) {
+        let taste = self.taste.trim();
+        let mut query = SearchQuery {
+            main: split_list(&self.main_ingredients),
+            sub: split_list(&self.sub_ingredients),
+            taste: (!taste.is_empty()).then(|| taste.to_string()),
+            ..Default::default()
+        };
+        if query.main.is_empty() && query.sub.is_empty() {
+            return;
+        }
+        add_saved_inputs(&mut query, &self.db, &self.config, DEFAULT_PROFILE);
+
+        // A new search supersedes whatever is still running
+        self.search_cancel.cancel();
+        self.search_cancel = CancellationToken::new();
+        let (tx, rx) = mpsc::channel();
+        self.search_events = Some(rx);
+        self.progress = SearchProgress::default();
+        self.results = own_recipes(&self.db, &query);
+        query.sort(&mut self.results);
+        self.list.select((!self.results.is_empty()).then_some(0));
+        self.detail_scroll = 0;
+        self.query = query.clone();
+        // Nothing is drawn with egui here; the context only absorbs repaint requests
+        self.runtime.spawn(search::run_search(
+            Arc::clone(&self.provider),
+            query,
+            tx,
+            self.search_cancel.clone(),
+            egui::Context::default(),
+        ));
+    }
+
+    fn poll_search(&mut self) {
+        let Some(events) = &self.search_events else {
+            return;
+        };
+        let mut loaded = Vec::new();
+        for event in events.try_iter() {
+            self.progress.record(&event);
+            if let SearchEvent::MealLoaded(meal) = event {
+                loaded.push(*meal);
+            }
+        }
+        if !loaded.is_empty() {
+            // Keep the cursor on the same recipe while results come in
+            let selected_id = self.selected().map(|m| m.id.clone());
+            self.results.extend(loaded);
+            self.query.sort(&mut self.results);
+            let index = selected_id.and_then(|id| self.results.iter().position(|m| m.id == id));
+            self.list.select(index.or(Some(0)));
+        }
+        if self.progress.done {
+            self.search_events = None;
+        }
+    }
+
+    fn select(&mut self, by: isize) {
+        if self.results.is_empty() {
+            return;
+        }
+        let current = self.list.selected().unwrap_or(0) as isize;
+        let next = (current + by).clamp(0, self.results.len() as isize - 1);
+        self.list.select(Some(next as usize));
+        self.detail_scroll = 0;
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            self.quit = true;
+            return;
+        }
+        match key.code {
+            KeyCode::Esc => self.quit = true,
+            KeyCode::Tab => self.focus = self.focus.step(1),
+            KeyCode::BackTab => self.focus = self.focus.step(-1),
+            _ if self.focus.is_text() => self.edit(key.code),
+            KeyCode::Char('q') => self.quit = true,
+            _ if self.focus == Focus::Results => match key.code {
+                KeyCode::Up | KeyCode::Char('k') => self.select(-1),
+                KeyCode::Down | KeyCode::Char('j') => self.select(1),
+                KeyCode::PageUp => self.select(-(PAGE_LINES as isize)),
+                KeyCode::PageDown => self.select(PAGE_LINES as isize),
+                KeyCode::Enter | KeyCode::Right => self.focus = Focus::Detail,
+                _ => {}
+            },
+            _ => match key.code {
+                KeyCode::Up | KeyCode::Char('k') => self.detail_scroll = self.detail_scroll.saturating_sub(1),
+                KeyCode::Down | KeyCode::Char('j') => self.detail_scroll = self.detail_scroll.saturating_add(1),
+                KeyCode::PageUp => self.detail_scroll = self.detail_scroll.saturating_sub(PAGE_LINES),
+                KeyCode::PageDown => self.detail_scroll = self.detail_scroll.saturating_add(PAGE_LINES),
+                KeyCode::Left | KeyCode::Backspace => self.focus = Focus::Results,
+                _ => {}
+            },
+        }
+    }
+
+    fn edit(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Enter => {
+                self.search();
+                self.focus = Focus::Results;
+            }
+            KeyCode::Char(c) => {
+                if let Some(text) = self.field(self.focus) {
+                    text.push(c);
+                }
+            }
+            KeyCode::Backspace => {
+                if let Some(text) = self.field(self.focus) {
+                    text.pop();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        while !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+            if event::poll(TICK)? {
+                if let Event::Key(key) = event::read()? {
+                    if key.kind == KeyEventKind::Press {
+                        self.handle_key(key);
+                    }
+                }
+            }
+            self.poll_search();
+        }
+        self.search_cancel.cancel();
+        Ok(())
+    }
+
+    fn block(&self, title: String, focus: Focus) -> Block<'static> {
+        let block = Block::bordered().title(title);
+        if self.focus == focus {
+            block.border_style(Style::default().fg(Color::Yellow))
+        } else {
+            block
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [inputs, body, status] =
+            Layout::vertical([Constraint::Length(3), Constraint::Min(5), Constraint::Length(1)]).areas(frame.area());
+        let [main_area, sub_area, taste_area] = Layout::horizontal([
+            Constraint::Percentage(40),
+            Constraint::Percentage(40),
+            Constraint::Percentage(20),
+        ])
+        .areas(inputs);
+        let fields = [
+            (Focus::Main, tr!("main-label"), main_area),
+            (Focus::Sub, tr!("sub-label"), sub_area),
+            (Focus::Taste, tr!("taste-label"), taste_area),
+        ];
+        for (focus, label, area) in fields {
+            let text = self.field(focus).cloned().unwrap_or_default();
+            let title = label.trim_end_matches(':').to_string();
+            frame.render_widget(Paragraph::new(text.as_str()).block(self.block(title, focus)), area);
+            if self.focus == focus {
+                let x = area.x + 1 + text.chars().count() as u16;
+                frame.set_cursor_position(Position::new(x.min(area.right().saturating_sub(2)), area.y + 1));
+            }
+        }
+
+        let [results_area, detail_area] =
+            Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)]).areas(body);
+        self.draw_results(frame, results_area);
+        self.draw_detail(frame, detail_area);
+        frame.render_widget(Paragraph::new(self.status_line()).style(Style::default().fg(Color::DarkGray)), status);
+    }
+
+    fn draw_results(&mut self, frame: &mut Frame, area: Rect) {
+        let items: Vec<ListItem> = self
+            .results
+            .iter()
+            .map(|meal| ListItem::new(format!("{:>4}  {}", meal.score, meal.title)))
+            .collect();
+        let title = tr!("results-heading").trim_end_matches(':').to_string();
+        let list = List::new(items)
+            .block(self.block(title, Focus::Results))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .highlight_symbol("> ");
+        frame.render_stateful_widget(list, area, &mut self.list);
+    }
+
+    fn draw_detail(&self, frame: &mut Frame, area: Rect) {
+        let title = tr!("details-heading").trim_end_matches(':').to_string();
+        let block = self.block(title, Focus::Detail);
+        let Some(meal) = self.selected() else {
+            frame.render_widget(block, area);
+            return;
+        };
+        let meal = measure::convert_meal(meal, self.config.units);
+        let mut lines = vec![
+            Line::styled(meal.title.clone(), Style::default().add_modifier(Modifier::BOLD)),
+            Line::from(tr!("detail-category", category = meal.category.as_str())),
+            Line::from(tr!("detail-area", area = meal.area.as_str())),
+        ];
+        if !meal.tags.is_empty() {
+            let tags: Vec<String> = meal.tags.iter().map(|t| format!("#{}", t)).collect();
+            lines.push(Line::from(tags.join(" ")));
+        }
+        lines.push(Line::default());
+        lines.push(Line::styled(tr!("ingredients-heading"), Style::default().add_modifier(Modifier::BOLD)));
+        for (i, ing) in meal.ingredients.iter().enumerate() {
+            let line = match meal.measures.get(i).filter(|m| !m.is_empty()) {
+                Some(measure) => format!("- {} {}", measure, ing),
+                None => format!("- {}", ing),
+            };
+            lines.push(Line::from(line));
+        }
+        lines.push(Line::default());
+        lines.push(Line::styled(tr!("instructions-label"), Style::default().add_modifier(Modifier::BOLD)));
+        for (i, step) in steps::parse_steps(&meal.instructions).iter().enumerate() {
+            lines.push(Line::from(vec![Span::raw(format!("{}. ", i + 1)), Span::raw(step.text.clone())]));
+        }
+        let detail = Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false })
+            .scroll((self.detail_scroll, 0));
+        frame.render_widget(detail, area);
+    }
+
+    fn status_line(&self) -> String {
+        let progress = if self.search_events.is_some() {
+            match self.progress.total {
+                Some(total) => tr!("progress-loaded", done = self.progress.processed, total = total),
+                None => tr!("progress-candidates"),
+            }
+        } else if self.progress.done && self.results.is_empty() {
+            tr!("no-results")
+        } else {
+            String::new()
+        };
+        let errors = match self.progress.errors.last() {
+            Some(last) => format!(" ⚠ {}", last),
+            None => String::new(),
+        };
+        format!("{}{}  {}", progress, errors, tr!("tui-help"))
+    }
+}
+
+/// Runs the terminal interface until the user quits.
+pub fn run() -> io::Result<()> {
+    let mut app = Tui::new();
+    let mut terminal = ratatui::init();
+    let result = app.run(&mut terminal);
+    ratatui::restore();
+    result
+}